@@ -1,27 +1,123 @@
-use crate::core::GameState;
-use crate::game::GameBuilder;
-use crate::input::{DefaultInputMapper, GameAction, InputMapper};
-use crate::renderer::GameRenderer;
+use crate::campaign::Campaign;
+use crate::core::{GameState, Vec2};
+use crate::daily::DailyChallenge;
+use crate::game::{Game, GameBuilder, GameConfig};
+use crate::glyphs::GlyphSet;
+use crate::input::{
+    action_id, key_name, DefaultInputMapper, GameAction, HeldActions, InputMapper, KeyBindings,
+    REBINDABLE,
+};
+use crate::renderer::{cell_at, GameRenderer, HudLayout, RenderConfig};
+use crate::storage::{
+    CampaignProgress, CampaignStore, DailyStore, HighScores, ScoreEntry, ScoreKey, ScoreStore,
+    Settings, SettingsStore, StatsStore,
+};
+use crate::systems::{Effects, EventBus, EventFeed, GameMode, ScopeStyle, StatsTracker};
+use crate::theme::{ColorDepth, Theme, ThemeName};
+use crate::ui::{
+    DebugOverlay, DebugTimings, HelpScreen, LevelComplete, Menu, MenuOutcome, NameEntry,
+    NameEntryOutcome, ScoresScreen, Screen, ScreenStack, Sidebar, StatsScreen, Themed, Toasts,
+    SIDEBAR_WIDTH,
+};
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyEventKind};
-use ratatui::DefaultTerminal;
+use crossterm::event::{
+    self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+    KeyCode, KeyEvent, KeyEventKind, KeyboardEnhancementFlags, MouseButton, MouseEvent,
+    MouseEventKind, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::terminal::supports_keyboard_enhancement;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    DefaultTerminal, Frame,
+};
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
+pub const CLASSIC_MODE: &str = GameMode::Classic.as_str();
+pub const CAMPAIGN_MODE: &str = "campaign";
+pub const DAILY_MODE: &str = "daily";
+
+const MODES: [(&str, &str); 5] = [
+    (CLASSIC_MODE, GameMode::Classic.title()),
+    (GameMode::TimeAttack.as_str(), GameMode::TimeAttack.title()),
+    (GameMode::Survival.as_str(), GameMode::Survival.title()),
+    (GameMode::Zen.as_str(), GameMode::Zen.title()),
+    (CAMPAIGN_MODE, "Campaign"),
+];
+
+const MIN_ARENA_WIDTH: u16 = 42;
+
+pub fn tick_duration_for_speed(speed: u8) -> Duration {
+    let tick_ms = match speed {
+        1 => 120,
+        2 => 70,
+        3 => 40,
+        _ => 70,
+    };
+    Duration::from_millis(tick_ms)
+}
+
 pub struct AppConfig {
     pub tick_duration: Duration,
+    pub speed: u8,
+    pub theme: ThemeName,
+    pub color_depth: ColorDepth,
+    pub glyphs: GlyphSet,
+    pub scope: ScopeStyle,
+    pub hud: HudLayout,
+    pub bindings: KeyBindings,
+    pub mode: Option<GameMode>,
+    pub daily: Option<DailyChallenge>,
+    pub mouse: bool,
+    // Set by `run` once it knows whether the terminal reports key releases.
+    pub key_releases: bool,
 }
 
-impl Default for AppConfig {
-    fn default() -> Self {
+impl AppConfig {
+    pub fn for_speed(speed: u8) -> Self {
+        Self {
+            tick_duration: tick_duration_for_speed(speed),
+            speed,
+            theme: ThemeName::default(),
+            color_depth: ColorDepth::detect(),
+            glyphs: GlyphSet::default(),
+            scope: ScopeStyle::default(),
+            hud: HudLayout::default(),
+            bindings: KeyBindings::new(),
+            mode: None,
+            daily: None,
+            mouse: true,
+            key_releases: false,
+        }
+    }
+
+    pub fn from_settings(settings: &Settings) -> Self {
         Self {
-            tick_duration: Duration::from_millis(70),
+            theme: settings.theme,
+            glyphs: settings.glyphs,
+            scope: settings.scope,
+            hud: settings.hud,
+            bindings: settings.bindings.clone(),
+            ..Self::for_speed(settings.speed)
         }
     }
 }
 
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self::for_speed(2)
+    }
+}
+
 pub struct App<M: InputMapper> {
     config: AppConfig,
     input_mapper: M,
+    score_store: ScoreStore,
+    stats_store: StatsStore,
+    settings_store: SettingsStore,
+    campaign_store: CampaignStore,
+    daily_store: DailyStore,
 }
 
 impl App<DefaultInputMapper> {
@@ -35,6 +131,11 @@ impl<M: InputMapper> App<M> {
         Self {
             config: AppConfig::default(),
             input_mapper: mapper,
+            score_store: ScoreStore::default_location(),
+            stats_store: StatsStore::default_location(),
+            settings_store: SettingsStore::default_location(),
+            campaign_store: CampaignStore::default_location(),
+            daily_store: DailyStore::default_location(),
         }
     }
 
@@ -43,48 +144,75 @@ impl<M: InputMapper> App<M> {
         self
     }
 
+    pub fn with_score_store(mut self, store: ScoreStore) -> Self {
+        self.score_store = store;
+        self
+    }
+
+    pub fn with_stats_store(mut self, store: StatsStore) -> Self {
+        self.stats_store = store;
+        self
+    }
+
+    pub fn with_settings_store(mut self, store: SettingsStore) -> Self {
+        self.settings_store = store;
+        self
+    }
+
+    pub fn with_campaign_store(mut self, store: CampaignStore) -> Self {
+        self.campaign_store = store;
+        self
+    }
+
+    pub fn with_daily_store(mut self, store: DailyStore) -> Self {
+        self.daily_store = store;
+        self
+    }
+
     pub fn run(self, mut terminal: DefaultTerminal) -> Result<()> {
-        let mut game_state = GameSessionState::new();
+        let mut game_state = GameSessionState::new(
+            &self.config,
+            self.score_store,
+            self.stats_store,
+            self.settings_store,
+            self.campaign_store,
+            self.daily_store,
+        );
         let mut last_tick = Instant::now();
 
         loop {
-            terminal.draw(|f| {
-                let area = f.area();
-                let (w, h) = (
-                    (area.width.saturating_sub(2) / 2) as i16,
-                    (area.height.saturating_sub(2)) as i16,
-                );
-
-                game_state.ensure_initialized(w, h);
+            let frame_start = Instant::now();
+            terminal.draw(|f| game_state.draw(f))?;
+            game_state.timings.frame.record(frame_start.elapsed());
 
-                if let Some(game) = game_state.game() {
-                    f.render_widget(GameRenderer::new(game), area);
-                }
-            })?;
-
-            let timeout = self
-                .config
-                .tick_duration
-                .saturating_sub(last_tick.elapsed());
+            let tick_duration = game_state.current_tick_duration();
+            let timeout = tick_duration.saturating_sub(last_tick.elapsed());
 
             if event::poll(timeout)? {
-                if let Event::Key(k) = event::read()? {
-                    if k.kind != KeyEventKind::Press {
-                        continue;
-                    }
-
-                    if let Some(action) = self.input_mapper.map(k.code) {
-                        match game_state.handle_action(action) {
-                            ActionResult::Continue => {}
-                            ActionResult::Quit => return Ok(()),
-                        }
+                let result = match event::read()? {
+                    Event::Key(k) => game_state.handle_key_event(k, &self.input_mapper),
+                    Event::Mouse(m) => game_state.handle_mouse(m),
+                    // A release that happens while unfocused never arrives.
+                    Event::FocusLost => {
+                        game_state.held.clear();
+                        ActionResult::Continue
                     }
+                    _ => ActionResult::Continue,
+                };
+                if let ActionResult::Quit = result {
+                    return Ok(());
                 }
             }
 
-            if last_tick.elapsed() >= self.config.tick_duration {
+            if last_tick.elapsed() >= tick_duration {
+                let tick_start = Instant::now();
+                game_state
+                    .timings
+                    .tick_interval
+                    .record(tick_start - last_tick);
                 game_state.tick();
-                last_tick = Instant::now();
+                game_state.timings.tick_work.record(tick_start.elapsed());
+                last_tick = tick_start;
             }
         }
     }
@@ -96,53 +224,1070 @@ impl Default for App<DefaultInputMapper> {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TitleItem {
+    Resume,
+    NewGame,
+    HighScores,
+    Settings,
+    Help,
+    Quit,
+}
+
+impl TitleItem {
+    fn label(self) -> &'static str {
+        match self {
+            TitleItem::Resume => "Resume",
+            TitleItem::NewGame => "New Game",
+            TitleItem::HighScores => "High Scores",
+            TitleItem::Settings => "Settings",
+            TitleItem::Help => "Help",
+            TitleItem::Quit => "Quit",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SettingsItem {
+    Speed,
+    Theme,
+    Glyphs,
+    Scope,
+    Hud,
+    Key(GameAction),
+    ResetKeys,
+    Save,
+    Back,
+}
+
+fn settings_items() -> Vec<SettingsItem> {
+    let mut items = vec![
+        SettingsItem::Speed,
+        SettingsItem::Theme,
+        SettingsItem::Glyphs,
+        SettingsItem::Scope,
+        SettingsItem::Hud,
+    ];
+    items.extend(
+        REBINDABLE
+            .iter()
+            .map(|&(action, _)| SettingsItem::Key(action)),
+    );
+    items.extend([
+        SettingsItem::ResetKeys,
+        SettingsItem::Save,
+        SettingsItem::Back,
+    ]);
+    items
+}
+
 struct GameSessionState {
-    game: Option<crate::game::Game>,
+    game: Option<Game>,
     current_bounds: (i16, i16),
+    terminal_size: (u16, u16),
+    score_store: ScoreStore,
+    mode: &'static str,
+    speed: u8,
+    run_speed: u8,
+    tick_duration: Duration,
+    game_over_handled: bool,
+    name_entry: Option<NameEntry>,
+    stats_store: StatsStore,
+    stats: Rc<RefCell<StatsTracker>>,
+    event_bus: EventBus,
+    effects: Rc<RefCell<Effects>>,
+    toasts: Toasts,
+    show_stats: bool,
+    paused_for_stats: bool,
+    theme: Theme,
+    glyphs: GlyphSet,
+    screens: ScreenStack,
+    title_menu: Menu,
+    mode_menu: Menu,
+    settings_menu: Menu,
+    high_scores: HighScores,
+    scope_style: ScopeStyle,
+    hud: HudLayout,
+    feed: Rc<RefCell<EventFeed>>,
+    show_debug: bool,
+    timings: DebugTimings,
+    bindings: KeyBindings,
+    awaiting_key: Option<GameAction>,
+    settings_store: SettingsStore,
+    campaign: Campaign,
+    campaign_store: CampaignStore,
+    campaign_progress: CampaignProgress,
+    campaign_menu: Menu,
+    campaign_level: Option<usize>,
+    level_complete: Option<LevelComplete>,
+    daily: Option<DailyChallenge>,
+    daily_store: DailyStore,
+    // Where the last frame put things, for mapping mouse positions back.
+    frame_area: Rect,
+    arena: Rect,
+    held: HeldActions,
 }
 
 impl GameSessionState {
-    fn new() -> Self {
-        Self {
+    fn new(
+        config: &AppConfig,
+        score_store: ScoreStore,
+        stats_store: StatsStore,
+        settings_store: SettingsStore,
+        campaign_store: CampaignStore,
+        daily_store: DailyStore,
+    ) -> Self {
+        let snapshot = stats_store.load().unwrap_or_default();
+        let stats = Rc::new(RefCell::new(StatsTracker::with_lifetime(
+            snapshot.stats,
+            snapshot.achievements,
+        )));
+
+        let mut event_bus = EventBus::new();
+        event_bus.subscribe(stats.clone());
+        let effects = Rc::new(RefCell::new(Effects::new()));
+        event_bus.subscribe(effects.clone());
+        let feed = Rc::new(RefCell::new(EventFeed::default()));
+        event_bus.subscribe(feed.clone());
+
+        let mut session = Self {
             game: None,
             current_bounds: (0, 0),
+            terminal_size: (0, 0),
+            score_store,
+            mode: CLASSIC_MODE,
+            speed: config.speed,
+            run_speed: config.speed,
+            tick_duration: config.tick_duration,
+            game_over_handled: false,
+            name_entry: None,
+            stats_store,
+            stats,
+            event_bus,
+            effects,
+            toasts: Toasts::new(),
+            show_stats: false,
+            paused_for_stats: false,
+            theme: Theme::new(config.theme, config.color_depth),
+            glyphs: config.glyphs,
+            screens: ScreenStack::new(),
+            title_menu: Menu::new("COOL SNAKE", Vec::<String>::new()),
+            mode_menu: Menu::new("MODE", MODES.iter().map(|(_, label)| *label)),
+            settings_menu: Menu::new("SETTINGS", Vec::<String>::new()),
+            high_scores: HighScores::default(),
+            scope_style: config.scope,
+            hud: config.hud,
+            feed,
+            show_debug: false,
+            timings: DebugTimings::default(),
+            bindings: config.bindings.clone(),
+            awaiting_key: None,
+            settings_store,
+            campaign: Campaign::builtin(),
+            campaign_store,
+            campaign_progress: CampaignProgress::default(),
+            campaign_menu: Menu::new("CAMPAIGN", Vec::<String>::new()),
+            campaign_level: None,
+            level_complete: None,
+            daily: None,
+            daily_store,
+            frame_area: Rect::default(),
+            arena: Rect::default(),
+            held: HeldActions::new(config.key_releases),
+        };
+        session.refresh_title_menu();
+        session.refresh_settings_menu();
+        if let Some(challenge) = config.daily {
+            session.start_daily(challenge);
+        } else if let Some(mode) = config.mode {
+            session.start_game(mode.as_str());
         }
+        session
     }
 
-    fn ensure_initialized(&mut self, w: i16, h: i16) {
-        if self.game.is_none() || self.current_bounds != (w, h) {
-            self.game = Some(GameBuilder::new().with_bounds(w, h).build());
-            self.current_bounds = (w, h);
+    fn draw(&mut self, f: &mut Frame) {
+        let area = f.area();
+        let theme = self.theme;
+        self.frame_area = area;
+
+        match self.screens.current() {
+            Screen::Game => self.draw_game(f),
+            Screen::Title => f.render_widget(Themed::new(&self.title_menu, &theme), area),
+            Screen::ModeSelect => f.render_widget(Themed::new(&self.mode_menu, &theme), area),
+            Screen::Campaign => f.render_widget(Themed::new(&self.campaign_menu, &theme), area),
+            Screen::Settings => f.render_widget(Themed::new(&self.settings_menu, &theme), area),
+            Screen::HighScores => {
+                f.render_widget(ScoresScreen::new(&self.high_scores, &theme), area)
+            }
+            Screen::Help => f.render_widget(HelpScreen::new(&theme), area),
         }
+
+        f.render_widget(Themed::new(self.toasts(), &theme), area);
     }
 
-    fn game(&self) -> Option<&crate::game::Game> {
-        self.game.as_ref()
+    // The arena is sized when a game starts and keeps that size until the
+    // terminal is resized, so switching HUD layouts mid-run never restarts
+    // it; a sidebar that no longer fits collapses to the compact HUD.
+    fn draw_game(&mut self, f: &mut Frame) {
+        let area = f.area();
+        let wants_sidebar =
+            self.hud == HudLayout::Sidebar && area.width >= SIDEBAR_WIDTH + MIN_ARENA_WIDTH;
+        let arena_width = if wants_sidebar {
+            area.width - SIDEBAR_WIDTH
+        } else {
+            area.width
+        };
+        let (w, h) = (
+            (arena_width.saturating_sub(2) / 2) as i16,
+            (area.height.saturating_sub(2)) as i16,
+        );
+
+        self.ensure_initialized((area.width, area.height), w, h);
+
+        let theme = self.theme;
+        if let Some((bw, bh)) = self.game().map(Game::bounds) {
+            self.arena = Rect {
+                width: (bw.max(0) as u16 * 2 + 2).min(area.width),
+                height: (bh.max(0) as u16 + 2).min(area.height),
+                ..area
+            };
+        }
+
+        if let Some(game) = self.game() {
+            let arena = self.arena;
+            let show_sidebar =
+                self.hud == HudLayout::Sidebar && area.width >= arena.width + SIDEBAR_WIDTH;
+
+            let config = RenderConfig {
+                theme,
+                glyphs: self.glyphs,
+                hud: match self.hud {
+                    HudLayout::Sidebar if !show_sidebar => HudLayout::Compact,
+                    hud => hud,
+                },
+                show_debug: self.show_debug,
+            };
+            let effects = self.effects.borrow();
+            f.render_widget(
+                GameRenderer::with_config(game, config).with_effects(&effects),
+                arena,
+            );
+
+            if show_sidebar {
+                let [_, side] = Layout::horizontal([
+                    Constraint::Length(arena.width),
+                    Constraint::Length(SIDEBAR_WIDTH),
+                ])
+                .areas(area);
+                let feed = self.feed.borrow();
+                let mode = match self.daily {
+                    Some(challenge) => format!("Daily {}", challenge.mode.title()),
+                    None => self
+                        .campaign_level
+                        .and_then(|i| self.campaign.get(i))
+                        .map(|level| level.name.as_str())
+                        .or_else(|| {
+                            MODES
+                                .iter()
+                                .find(|&&(id, _)| id == self.mode)
+                                .map(|&(_, title)| title)
+                        })
+                        .unwrap_or(self.mode)
+                        .to_string(),
+                };
+                let sidebar = Sidebar::new(game, &feed, &theme)
+                    .with_glyphs(self.glyphs)
+                    .with_mode(&mode)
+                    .with_timing(game.elapsed(), game.tick_duration());
+                f.render_widget(sidebar, side);
+            }
+
+            if self.show_debug {
+                f.render_widget(DebugOverlay::new(game, &self.timings, &theme), arena);
+            }
+        }
+
+        if self.show_stats {
+            f.render_widget(StatsScreen::new(&self.stats(), &theme), area);
+        }
+
+        if let Some(entry) = self.name_entry() {
+            f.render_widget(Themed::new(entry, &theme), area);
+        }
+
+        if let Some(panel) = self.level_complete.as_ref() {
+            f.render_widget(Themed::new(panel, &theme), area);
+        }
+    }
+
+    fn handle_key<M: InputMapper>(&mut self, code: KeyCode, mapper: &M) -> ActionResult {
+        match self.screens.current() {
+            Screen::Game => {
+                if self.handle_name_entry_key(code) {
+                    return ActionResult::Continue;
+                }
+                match self.bindings.map(code).or_else(|| mapper.map(code)) {
+                    Some(action) => self.apply_action(action),
+                    None => ActionResult::Continue,
+                }
+            }
+            Screen::Title => {
+                let outcome = self.title_menu.handle_key(code);
+                self.on_title_menu(outcome)
+            }
+            Screen::ModeSelect => {
+                let outcome = self.mode_menu.handle_key(code);
+                self.on_mode_menu(outcome);
+                ActionResult::Continue
+            }
+            Screen::Campaign => {
+                let outcome = self.campaign_menu.handle_key(code);
+                self.on_campaign_menu(outcome);
+                ActionResult::Continue
+            }
+            Screen::Settings => {
+                if let Some(action) = self.awaiting_key.take() {
+                    self.rebind(action, code);
+                    return ActionResult::Continue;
+                }
+                let outcome = self.settings_menu.handle_key(code);
+                self.on_settings_menu(outcome);
+                ActionResult::Continue
+            }
+            Screen::HighScores | Screen::Help => {
+                if matches!(
+                    code,
+                    KeyCode::Esc
+                        | KeyCode::Enter
+                        | KeyCode::Backspace
+                        | KeyCode::Char('q')
+                        | KeyCode::Char(' ')
+                ) {
+                    self.screens.pop();
+                }
+                ActionResult::Continue
+            }
+        }
+    }
+
+    fn handle_key_event<M: InputMapper>(&mut self, key: KeyEvent, mapper: &M) -> ActionResult {
+        let in_play = self.screens.current() == Screen::Game && self.name_entry.is_none();
+        let action = in_play
+            .then(|| self.bindings.map(key.code).or_else(|| mapper.map(key.code)))
+            .flatten();
+
+        match key.kind {
+            KeyEventKind::Release => {
+                if let Some(action) = action {
+                    self.held.release(action);
+                }
+                ActionResult::Continue
+            }
+            // Only movement and firing auto-repeat, and a held action
+            // already repeats once per tick.
+            KeyEventKind::Repeat => match action {
+                Some(action) if action.repeats_while_held() && !self.held.is_held(action) => {
+                    self.handle_key(key.code, mapper)
+                }
+                _ => ActionResult::Continue,
+            },
+            KeyEventKind::Press => {
+                let result = self.handle_key(key.code, mapper);
+                if let Some(action) = action {
+                    self.held.press(action);
+                }
+                result
+            }
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> ActionResult {
+        let area = self.frame_area;
+        match self.screens.current() {
+            Screen::Game => {
+                self.on_arena_mouse(event);
+                ActionResult::Continue
+            }
+            Screen::Title => {
+                let outcome = self.title_menu.handle_mouse(event, area);
+                self.on_title_menu(outcome)
+            }
+            Screen::ModeSelect => {
+                let outcome = self.mode_menu.handle_mouse(event, area);
+                self.on_mode_menu(outcome);
+                ActionResult::Continue
+            }
+            Screen::Campaign => {
+                let outcome = self.campaign_menu.handle_mouse(event, area);
+                self.on_campaign_menu(outcome);
+                ActionResult::Continue
+            }
+            Screen::Settings if self.awaiting_key.is_none() => {
+                let outcome = self.settings_menu.handle_mouse(event, area);
+                self.on_settings_menu(outcome);
+                ActionResult::Continue
+            }
+            Screen::Settings => ActionResult::Continue,
+            Screen::HighScores | Screen::Help => {
+                if matches!(event.kind, MouseEventKind::Down(_)) {
+                    self.screens.pop();
+                }
+                ActionResult::Continue
+            }
+        }
+    }
+
+    // Hovering aims the scope at the cursor; a left click also fires.
+    fn on_arena_mouse(&mut self, event: MouseEvent) {
+        let overlay_open =
+            self.show_stats || self.name_entry.is_some() || self.level_complete.is_some();
+        let fire = match event.kind {
+            MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => false,
+            MouseEventKind::Down(MouseButton::Left) => true,
+            _ => return,
+        };
+        if overlay_open {
+            return;
+        }
+
+        self.observe_snake();
+        let arena = self.arena;
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+        let (w, h) = game.bounds();
+        let Some(cell) = cell_at(arena, Vec2::new(w, h), event.column, event.row) else {
+            return;
+        };
+        game.aim_at(cell);
+        if fire {
+            game.fire();
+        }
+    }
+
+    fn title_items(&self) -> Vec<TitleItem> {
+        let in_progress = self.game().is_some_and(|g| !g.state().is_over());
+        let mut items = Vec::new();
+        if in_progress {
+            items.push(TitleItem::Resume);
+        }
+        items.extend([
+            TitleItem::NewGame,
+            TitleItem::HighScores,
+            TitleItem::Settings,
+            TitleItem::Help,
+            TitleItem::Quit,
+        ]);
+        items
+    }
+
+    fn refresh_title_menu(&mut self) {
+        let labels: Vec<_> = self
+            .title_items()
+            .into_iter()
+            .map(TitleItem::label)
+            .collect();
+        self.title_menu.set_items(labels);
+    }
+
+    fn on_title_menu(&mut self, outcome: MenuOutcome) -> ActionResult {
+        match outcome {
+            MenuOutcome::Selected(i) => match self.title_items().get(i) {
+                Some(TitleItem::Resume) => self.screens.push(Screen::Game),
+                Some(TitleItem::NewGame) => self.screens.push(Screen::ModeSelect),
+                Some(TitleItem::HighScores) => {
+                    self.high_scores = self.score_store.load().unwrap_or_default();
+                    self.screens.push(Screen::HighScores);
+                }
+                Some(TitleItem::Settings) => self.open_settings(),
+                Some(TitleItem::Help) => self.screens.push(Screen::Help),
+                Some(TitleItem::Quit) => return self.quit(),
+                None => {}
+            },
+            MenuOutcome::Back => return self.quit(),
+            MenuOutcome::None | MenuOutcome::Adjust(..) => {}
+        }
+        ActionResult::Continue
+    }
+
+    fn on_mode_menu(&mut self, outcome: MenuOutcome) {
+        match outcome {
+            MenuOutcome::Selected(i) => match MODES.get(i) {
+                Some(&(CAMPAIGN_MODE, _)) => self.open_campaign(),
+                Some(&(mode, _)) => self.start_game(mode),
+                None => {}
+            },
+            MenuOutcome::Back => {
+                self.screens.pop();
+            }
+            MenuOutcome::None | MenuOutcome::Adjust(..) => {}
+        }
+    }
+
+    fn open_campaign(&mut self) {
+        self.campaign_progress = self.campaign_store.load().unwrap_or_default();
+        self.refresh_campaign_menu();
+        self.screens.push(Screen::Campaign);
+    }
+
+    fn refresh_campaign_menu(&mut self) {
+        let labels: Vec<_> = self
+            .campaign
+            .levels()
+            .iter()
+            .enumerate()
+            .map(|(i, level)| {
+                let status = if self.campaign_progress.is_unlocked(&self.campaign, i) {
+                    let stars = self.campaign_progress.stars(&level.id);
+                    (0..3).map(|s| self.glyphs.star(s < stars)).collect()
+                } else {
+                    "locked".to_string()
+                };
+                format!("{}. {:<15} {}", i + 1, level.name, status)
+            })
+            .collect();
+        self.campaign_menu.set_items(labels);
+    }
+
+    fn on_campaign_menu(&mut self, outcome: MenuOutcome) {
+        match outcome {
+            MenuOutcome::Selected(i) => {
+                if self.campaign_progress.is_unlocked(&self.campaign, i) {
+                    self.start_campaign_level(i);
+                } else {
+                    self.toasts
+                        .push("LOCKED", "Finish the previous level to unlock it");
+                }
+            }
+            MenuOutcome::Back => {
+                self.screens.pop();
+            }
+            MenuOutcome::None | MenuOutcome::Adjust(..) => {}
+        }
+    }
+
+    fn start_campaign_level(&mut self, index: usize) {
+        self.start_game(CAMPAIGN_MODE);
+        self.campaign_level = Some(index);
+        self.campaign_menu.select(index);
+    }
+
+    fn next_campaign_level(&mut self) {
+        match self.campaign_level {
+            Some(i) if i + 1 < self.campaign.len() => self.start_campaign_level(i + 1),
+            _ => {}
+        }
     }
 
-    fn game_mut(&mut self) -> Option<&mut crate::game::Game> {
-        self.game.as_mut()
+    fn complete_campaign_level(&mut self) {
+        let (Some(index), Some(game)) = (self.campaign_level, self.game.as_ref()) else {
+            return;
+        };
+        let Some(level) = self.campaign.get(index) else {
+            return;
+        };
+
+        let score = game.score();
+        let stars = level.stars_for(score);
+        let new_best = stars > self.campaign_progress.stars(&level.id);
+        match self.campaign_store.record(&level.id, stars) {
+            Ok(progress) => self.campaign_progress = progress,
+            Err(e) => self.toasts.push("SAVE FAILED", e.to_string()),
+        }
+
+        self.level_complete = Some(
+            LevelComplete::new(&level.name, score, stars)
+                .with_new_best(new_best)
+                .with_next(index + 1 < self.campaign.len())
+                .with_glyphs(self.glyphs),
+        );
+    }
+
+    fn settings_label(&self, item: SettingsItem) -> String {
+        match item {
+            SettingsItem::Speed => format!("Speed     < {} >", self.speed),
+            SettingsItem::Theme => format!("Theme     < {} >", self.theme.name()),
+            SettingsItem::Glyphs => format!("Glyphs    < {} >", self.glyphs),
+            SettingsItem::Scope => format!("Scope     < {} >", self.scope_style),
+            SettingsItem::Hud => format!("HUD       < {} >", self.hud),
+            SettingsItem::Key(action) => {
+                let id = action_id(action).unwrap_or("?");
+                if self.awaiting_key == Some(action) {
+                    format!("Key {:<6} press a key (ESC cancels)", id)
+                } else {
+                    let key = self
+                        .bindings
+                        .key_for(action)
+                        .and_then(key_name)
+                        .unwrap_or_else(|| "default".to_string());
+                    format!("Key {:<6}  {}", id, key)
+                }
+            }
+            SettingsItem::ResetKeys => "Reset key bindings".to_string(),
+            SettingsItem::Save => "Save as defaults".to_string(),
+            SettingsItem::Back => "Back".to_string(),
+        }
+    }
+
+    fn refresh_settings_menu(&mut self) {
+        let labels: Vec<_> = settings_items()
+            .into_iter()
+            .map(|item| self.settings_label(item))
+            .collect();
+        self.settings_menu.set_items(labels);
+    }
+
+    fn on_settings_menu(&mut self, outcome: MenuOutcome) {
+        let (index, delta) = match outcome {
+            MenuOutcome::Selected(i) => (i, 1),
+            MenuOutcome::Adjust(i, delta) => (i, delta),
+            MenuOutcome::Back => {
+                self.screens.pop();
+                return;
+            }
+            MenuOutcome::None => return,
+        };
+        let selected = matches!(outcome, MenuOutcome::Selected(_));
+
+        match settings_items().get(index) {
+            Some(SettingsItem::Speed) => {
+                let speed = (self.speed as i8 - 1 + delta).rem_euclid(3) + 1;
+                self.set_speed(speed as u8);
+            }
+            Some(SettingsItem::Theme) => {
+                let name = cycle(&ThemeName::ALL, self.theme.name(), delta);
+                self.theme = self.theme.with_name(name);
+            }
+            Some(SettingsItem::Glyphs) => {
+                self.glyphs = cycle(&GlyphSet::ALL, self.glyphs, delta);
+            }
+            Some(SettingsItem::Scope) => {
+                self.scope_style = cycle(&ScopeStyle::ALL, self.scope_style, delta);
+                if let Some(game) = self.game.as_mut() {
+                    game.set_scope_config(self.scope_style.config());
+                }
+            }
+            Some(SettingsItem::Hud) => {
+                self.hud = cycle(&HudLayout::ALL, self.hud, delta);
+            }
+            Some(&SettingsItem::Key(action)) if selected => self.awaiting_key = Some(action),
+            Some(&SettingsItem::Key(action)) => self.bindings.unbind(action),
+            Some(SettingsItem::ResetKeys) if selected => self.bindings.clear(),
+            Some(SettingsItem::Save) if selected => self.save_settings(),
+            Some(SettingsItem::Back) if selected => {
+                self.screens.pop();
+            }
+            Some(SettingsItem::ResetKeys | SettingsItem::Save | SettingsItem::Back) | None => {}
+        }
+        self.refresh_settings_menu();
+    }
+
+    fn rebind(&mut self, action: GameAction, code: KeyCode) {
+        if code != KeyCode::Esc && !self.bindings.bind(action, code) {
+            self.toasts.push(
+                "KEY NOT AVAILABLE",
+                "That key is reserved or can't be bound",
+            );
+        }
+        self.refresh_settings_menu();
+    }
+
+    fn current_settings(&self) -> Settings {
+        Settings {
+            speed: self.speed,
+            theme: self.theme.name(),
+            glyphs: self.glyphs,
+            scope: self.scope_style,
+            hud: self.hud,
+            bindings: self.bindings.clone(),
+        }
+    }
+
+    fn save_settings(&mut self) {
+        match self.settings_store.save(&self.current_settings()) {
+            Ok(()) => self
+                .toasts
+                .push("SETTINGS SAVED", "Used as defaults next launch"),
+            Err(e) => self.toasts.push("SAVE FAILED", e.to_string()),
+        }
+    }
+
+    fn open_settings(&mut self) {
+        if self.show_stats {
+            self.toggle_stats();
+        }
+        if let Some(game) = self.game.as_mut() {
+            if game.state() == GameState::Playing {
+                game.toggle_pause();
+            }
+        }
+        self.refresh_settings_menu();
+        self.screens.push(Screen::Settings);
+    }
+
+    // A run is filed under the slowest speed it was played at, so slowing
+    // down mid-run can't post a score to a faster table.
+    fn set_speed(&mut self, speed: u8) {
+        self.speed = speed;
+        self.run_speed = self.run_speed.min(speed);
+        self.tick_duration = tick_duration_for_speed(speed);
+        if self.daily.is_some() {
+            return;
+        }
+        if let Some(game) = self.game.as_mut() {
+            game.set_tick_duration(self.tick_duration);
+        }
+    }
+
+    fn start_game(&mut self, mode: &'static str) {
+        self.dispatch_events();
+        self.flush_stats();
+
+        self.mode = mode;
+        self.game = None;
+        self.campaign_level = None;
+        self.daily = None;
+        self.game_over_handled = false;
+        self.name_entry = None;
+        self.level_complete = None;
+        self.show_stats = false;
+        self.paused_for_stats = false;
+        self.effects.borrow_mut().clear();
+
+        self.screens.return_to_title();
+        self.screens.push(Screen::Game);
+    }
+
+    fn start_daily(&mut self, challenge: DailyChallenge) {
+        self.start_game(DAILY_MODE);
+        self.daily = Some(challenge);
+    }
+
+    fn finish_daily(&mut self) {
+        let (Some(challenge), Some(game)) = (self.daily, self.game.as_ref()) else {
+            return;
+        };
+        let score = game.score();
+        match self.daily_store.finish(challenge.date, score) {
+            Ok(_) => self.toasts.push(
+                format!("DAILY {} DONE", challenge.date),
+                format!("Scored {}. New challenge tomorrow", score),
+            ),
+            Err(e) => self.toasts.push("SAVE FAILED", e.to_string()),
+        }
+    }
+
+    fn go_to_menu(&mut self) {
+        if self.show_stats {
+            self.toggle_stats();
+        }
+        if let Some(game) = self.game.as_mut() {
+            if game.state() == GameState::Playing {
+                game.toggle_pause();
+            }
+        }
+        self.dispatch_events();
+        self.flush_stats();
+
+        self.screens.return_to_title();
+        self.refresh_title_menu();
+        self.title_menu.select(0);
+    }
+
+    fn awaiting_game_over(&self) -> bool {
+        self.game().is_some_and(|g| g.state().is_over()) && !self.game_over_handled
+    }
+
+    fn quit(&mut self) -> ActionResult {
+        self.dispatch_events();
+        self.flush_stats();
+        ActionResult::Quit
+    }
+
+    // Campaign maps and daily challenges have a fixed size, so only free-play
+    // games are rebuilt to fit a resized terminal.
+    fn ensure_initialized(&mut self, terminal_size: (u16, u16), w: i16, h: i16) {
+        let resized = self.terminal_size != terminal_size;
+        self.terminal_size = terminal_size;
+        let fixed_arena = self.campaign_level.is_some() || self.daily.is_some();
+        if self.game.is_some() && (fixed_arena || !resized) {
+            return;
+        }
+
+        let level = self.campaign_level.and_then(|i| self.campaign.get(i));
+        let game = match (level, self.daily) {
+            (Some(level), _) => Game::with_config(GameConfig {
+                scope: self.scope_style.config(),
+                ..level.game_config(self.tick_duration)
+            }),
+            (None, Some(challenge)) => Game::with_config(GameConfig {
+                scope: self.scope_style.config(),
+                ..challenge.game_config()
+            }),
+            (None, None) => GameBuilder::new()
+                .with_bounds(w, h)
+                .with_mode(self.mode.parse().unwrap_or_default())
+                .with_scope(self.scope_style.config())
+                .with_tick_duration(self.tick_duration)
+                .build(),
+        };
+        self.current_bounds = game.bounds();
+        self.game = Some(game);
+        self.run_speed = self.speed;
+        self.feed.borrow_mut().clear();
+        self.game_over_handled = false;
+        self.name_entry = None;
+        self.level_complete = None;
+        self.effects.borrow_mut().clear();
+    }
+
+    fn observe_snake(&self) {
+        if let Some(game) = self.game.as_ref() {
+            let segments = game.snake().segments().iter().copied();
+            self.effects.borrow_mut().observe_snake(segments);
+        }
+    }
+
+    fn stats(&self) -> Ref<'_, StatsTracker> {
+        self.stats.borrow()
+    }
+
+    fn toasts(&self) -> &Toasts {
+        &self.toasts
+    }
+
+    fn dispatch_events(&mut self) {
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+        self.timings.queue_high_water = self.timings.queue_high_water.max(game.event_queue().len());
+        self.event_bus.dispatch(game.events());
+        self.timings.dispatched = self.event_bus.dispatched();
+        self.timings.dropped = self.event_bus.dropped();
+
+        let run_over = game.state().is_over();
+        for achievement in self.stats.borrow_mut().take_newly_unlocked() {
+            self.toasts.push(
+                format!("UNLOCKED: {}", achievement.title()),
+                achievement.description(),
+            );
+        }
+
+        if run_over {
+            self.flush_stats();
+        }
+    }
+
+    fn flush_stats(&mut self) {
+        let mut stats = self.stats.borrow_mut();
+        if stats.unsaved().is_empty() {
+            return;
+        }
+        if let Ok(snapshot) = self.stats_store.merge(stats.unsaved(), stats.unlocked()) {
+            stats.mark_saved(snapshot.stats, snapshot.achievements);
+        }
+    }
+
+    fn toggle_stats(&mut self) {
+        self.show_stats = !self.show_stats;
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+
+        if self.show_stats && game.state() == GameState::Playing {
+            game.toggle_pause();
+            self.paused_for_stats = true;
+        } else if !self.show_stats && self.paused_for_stats {
+            if game.state() == GameState::Paused {
+                game.toggle_pause();
+            }
+            self.paused_for_stats = false;
+        }
+    }
+
+    fn name_entry(&self) -> Option<&NameEntry> {
+        self.name_entry.as_ref()
+    }
+
+    fn score_key(&self) -> ScoreKey {
+        let (w, h) = self.current_bounds;
+        ScoreKey::new(self.mode, self.run_speed, w, h)
+    }
+
+    fn check_game_over(&mut self) {
+        let Some(game) = self.game.as_ref() else {
+            return;
+        };
+
+        let state = game.state();
+        if !state.is_over() {
+            self.game_over_handled = false;
+            return;
+        }
+
+        if self.game_over_handled || self.effects.borrow().is_death_animation_playing() {
+            return;
+        }
+        self.game_over_handled = true;
+
+        if self.daily.is_some() {
+            self.finish_daily();
+            return;
+        }
+
+        // Campaign runs are judged by stars, not the high-score tables.
+        if self.campaign_level.is_some() {
+            if state == GameState::Won {
+                self.complete_campaign_level();
+            }
+            return;
+        }
+
+        let score = game.score();
+        let qualifies = self
+            .score_store
+            .load()
+            .is_ok_and(|scores| scores.qualifies(&self.score_key(), score));
+
+        if qualifies {
+            let default_name = std::env::var("USER").unwrap_or_default();
+            self.name_entry = Some(NameEntry::new(score, &default_name));
+        }
+    }
+
+    fn handle_name_entry_key(&mut self, code: KeyCode) -> bool {
+        let key = self.score_key();
+        let Some(entry) = self.name_entry.as_mut() else {
+            return false;
+        };
+
+        match entry.handle_key(code) {
+            NameEntryOutcome::Editing => {}
+            NameEntryOutcome::Cancelled => self.name_entry = None,
+            NameEntryOutcome::Submitted => {
+                let record = ScoreEntry::new(entry.name(), entry.score());
+                match self.score_store.submit(key, record) {
+                    Ok(_) => self.name_entry = None,
+                    Err(e) => entry.set_error(format!("save failed: {e}")),
+                }
+            }
+        }
+        true
+    }
+
+    // Levels speed the simulation up on top of the chosen speed setting.
+    fn current_tick_duration(&self) -> Duration {
+        match self.game() {
+            Some(game) if self.screens.current() == Screen::Game => game.tick_duration(),
+            _ => self.tick_duration,
+        }
+    }
+
+    fn game(&self) -> Option<&Game> {
+        self.game.as_ref()
     }
 
     fn tick(&mut self) {
+        if self.screens.current() != Screen::Game {
+            self.held.clear();
+            self.toasts.tick();
+            return;
+        }
+
+        self.repeat_held();
+        self.observe_snake();
         if let Some(g) = self.game.as_mut() {
             g.tick();
         }
+        self.dispatch_events();
+        if self.game().is_some_and(|g| g.state() != GameState::Paused) {
+            self.effects.borrow_mut().tick();
+        }
+        self.toasts.tick();
+        self.check_game_over();
     }
 
-    fn handle_action(&mut self, action: GameAction) -> ActionResult {
-        let Some(game) = self.game_mut() else {
+    // Holding fire keeps shooting as the cooldown allows; holding a direction
+    // keeps the snake stepping every tick.
+    fn repeat_held(&mut self) {
+        if self.show_stats || self.name_entry.is_some() || self.level_complete.is_some() {
+            return;
+        }
+        for action in self.held.held().to_vec() {
+            self.apply_action(action);
+        }
+    }
+
+    fn apply_action(&mut self, action: GameAction) -> ActionResult {
+        match action {
+            GameAction::Quit => return self.quit(),
+            GameAction::Menu => {
+                if !self.awaiting_game_over() {
+                    self.go_to_menu();
+                }
+                return ActionResult::Continue;
+            }
+            GameAction::Stats => {
+                self.toggle_stats();
+                return ActionResult::Continue;
+            }
+            GameAction::Settings => {
+                self.open_settings();
+                return ActionResult::Continue;
+            }
+            GameAction::ToggleDebug => {
+                self.show_debug = !self.show_debug;
+                return ActionResult::Continue;
+            }
+            GameAction::CycleTheme => {
+                self.theme = self.theme.with_name(self.theme.name().next());
+                return ActionResult::Continue;
+            }
+            GameAction::CycleGlyphs => {
+                self.glyphs = self.glyphs.next();
+                return ActionResult::Continue;
+            }
+            _ if self.show_stats => {
+                self.toggle_stats();
+                return ActionResult::Continue;
+            }
+            _ => {}
+        }
+
+        if self.level_complete.is_some() && action == GameAction::Pause {
+            self.next_campaign_level();
+            return ActionResult::Continue;
+        }
+
+        let run_over = self.game().is_some_and(|g| g.state().is_over());
+        let restarting = matches!(action, GameAction::Restart | GameAction::Pause);
+        if self.daily.is_some() && run_over && restarting {
+            self.toasts
+                .push("ONE ATTEMPT PER DAY", "Press M for the menu or Q to quit");
+            return ActionResult::Continue;
+        }
+
+        self.observe_snake();
+        // Game over is only raised from `tick`, once the death animation has
+        // played; restarting before then would skip the name prompt.
+        let game_over_pending = self.awaiting_game_over();
+        let Some(game) = self.game.as_mut() else {
             return ActionResult::Continue;
         };
 
         match action {
-            GameAction::Quit => ActionResult::Quit,
-            GameAction::Restart if game.state() == GameState::Dead => {
-                game.restart();
-                ActionResult::Continue
-            }
-            GameAction::Pause if game.state() == GameState::Dead => {
+            GameAction::Quit
+            | GameAction::Menu
+            | GameAction::Settings
+            | GameAction::ToggleDebug
+            | GameAction::Stats
+            | GameAction::CycleTheme
+            | GameAction::CycleGlyphs => ActionResult::Continue,
+            GameAction::Restart | GameAction::Pause if game_over_pending => ActionResult::Continue,
+            GameAction::Restart | GameAction::Pause if game.state().is_over() => {
                 game.restart();
+                self.run_speed = self.speed;
+                self.level_complete = None;
                 ActionResult::Continue
             }
             GameAction::Pause => {
@@ -154,8 +1299,7 @@ impl GameSessionState {
                 ActionResult::Continue
             }
             GameAction::Move(dir) => {
-                game.set_direction(dir);
-                game.move_snake();
+                game.queue_step(dir);
                 ActionResult::Continue
             }
             GameAction::Restart => ActionResult::Continue,
@@ -163,15 +1307,73 @@ impl GameSessionState {
     }
 }
 
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, delta: i8) -> T {
+    let len = all.len() as i32;
+    let idx = all.iter().position(|&v| v == current).unwrap_or(0) as i32;
+    all[(idx + delta as i32).rem_euclid(len) as usize]
+}
+
 enum ActionResult {
     Continue,
     Quit,
 }
 
-pub fn run(terminal: DefaultTerminal, tick: Duration) -> Result<()> {
-    App::new()
-        .with_config(AppConfig {
-            tick_duration: tick,
-        })
-        .run(terminal)
+// Mouse capture stops the terminal from selecting text, so it is only
+// switched on for as long as the game runs. Key release reporting is asked
+// for wherever the terminal supports it.
+pub fn run(terminal: DefaultTerminal, mut config: AppConfig) -> Result<()> {
+    config.key_releases = supports_keyboard_enhancement().unwrap_or(false);
+    let modes = TerminalModes::enable(config.mouse, config.key_releases)?;
+    let result = App::new().with_config(config).run(terminal);
+    drop(modes);
+    result
+}
+
+// Each mode is recorded as soon as it is on, so dropping the guard turns off
+// exactly what was switched on, even when a later step failed.
+#[derive(Default)]
+struct TerminalModes {
+    mouse: bool,
+    keyboard: bool,
+    focus: bool,
+}
+
+impl TerminalModes {
+    fn enable(mouse: bool, key_releases: bool) -> Result<Self> {
+        let mut stdout = std::io::stdout();
+        let mut modes = Self::default();
+        if mouse {
+            crossterm::execute!(stdout, EnableMouseCapture)?;
+            modes.mouse = true;
+        }
+        if key_releases {
+            crossterm::execute!(
+                stdout,
+                PushKeyboardEnhancementFlags(
+                    KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                        | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                )
+            )?;
+            modes.keyboard = true;
+            crossterm::execute!(stdout, EnableFocusChange)?;
+            modes.focus = true;
+        }
+        Ok(modes)
+    }
+}
+
+impl Drop for TerminalModes {
+    // Best effort: there is nowhere to report a failure from here.
+    fn drop(&mut self) {
+        let mut stdout = std::io::stdout();
+        if self.focus {
+            let _ = crossterm::execute!(stdout, DisableFocusChange);
+        }
+        if self.keyboard {
+            let _ = crossterm::execute!(stdout, PopKeyboardEnhancementFlags);
+        }
+        if self.mouse {
+            let _ = crossterm::execute!(stdout, DisableMouseCapture);
+        }
+    }
 }
@@ -0,0 +1,218 @@
+use super::process::BotError;
+use super::protocol::{self, BotAction};
+use crate::core::{Direction, GameState, Vec2};
+use crate::game::{Game, GameBuilder, SnakeStart};
+use crate::systems::{DeathCause, GameEvent, GameMode};
+use serde_json::Value;
+use std::fmt;
+
+// Anything that picks an action from a state message. External processes
+// are the main case; in-process bots make matches easy to test.
+pub trait Bot {
+    fn name(&self) -> &str;
+
+    fn act(&mut self, state: &Value) -> Result<BotAction, BotError>;
+
+    // Told how the match ended. Nothing is expected back.
+    fn finish(&mut self, _end: &Value) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaConfig {
+    pub width: i16,
+    pub height: i16,
+    pub mode: GameMode,
+    pub seed: u64,
+    // Matches that reach this many ticks stop there, so a bot that never
+    // dies can't hold the arena forever.
+    pub max_ticks: u64,
+}
+
+impl ArenaConfig {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            width: 30,
+            height: 20,
+            mode: GameMode::Classic,
+            seed,
+            max_ticks: 5000,
+        }
+    }
+
+    // One snake per bot, spread evenly along the middle row with the left
+    // half heading right and the rest heading left. A lone snake starts in
+    // the centre, as in a normal game.
+    pub fn build_game(&self, snakes: usize) -> Game {
+        let starts = (0..snakes).map(|i| {
+            let x = (2 * i + 1) * self.width.max(0) as usize / (2 * snakes);
+            SnakeStart {
+                position: Vec2::new(x as i16, self.height / 2),
+                direction: if i < snakes.div_ceil(2) {
+                    Direction::Right
+                } else {
+                    Direction::Left
+                },
+                length: 1,
+            }
+        });
+        let mut builder = GameBuilder::new()
+            .with_bounds(self.width, self.height)
+            .with_mode(self.mode)
+            .with_seed(self.seed);
+        for (i, start) in starts.enumerate() {
+            builder = if i == 0 {
+                builder.with_snake_start(start)
+            } else {
+                builder.with_rival(start)
+            };
+        }
+        builder.build()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatchEnd {
+    Died(DeathCause),
+    // The game was won, or every other snake was out.
+    Won,
+    TickLimit,
+    // The bot broke the protocol and forfeits the rest of the match; its
+    // snake leaves the board and the score so far still stands.
+    Fault(BotError),
+}
+
+impl MatchEnd {
+    pub fn is_fault(&self) -> bool {
+        matches!(self, MatchEnd::Fault(_))
+    }
+
+    // Still on the board when the match stopped.
+    pub fn survived(&self) -> bool {
+        matches!(self, MatchEnd::Won | MatchEnd::TickLimit)
+    }
+}
+
+impl fmt::Display for MatchEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchEnd::Died(cause) => {
+                write!(f, "died: {}", GameEvent::SnakeDied { cause: *cause })
+            }
+            MatchEnd::Won => f.write_str("won"),
+            MatchEnd::TickLimit => f.write_str("tick limit"),
+            MatchEnd::Fault(err) => write!(f, "bot fault: {err}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchReport {
+    pub end: MatchEnd,
+    pub ticks: u64,
+    pub score: u32,
+    pub length: usize,
+}
+
+impl MatchReport {
+    fn new(game: &Game, snake: usize, end: MatchEnd) -> Self {
+        Self {
+            end,
+            ticks: game.ticks(),
+            score: game.score_of(snake),
+            length: game.snakes().get(snake).map_or(0, |s| s.length()),
+        }
+    }
+}
+
+// Plays one headless game with a snake for each bot, numbered in the order
+// given. Each turn every bot still in sees the same position and answers;
+// the actions are then applied as key presses would be, starting from a
+// different snake each turn so no seat always moves first, and the game
+// ticks. A snake is reported as soon as it's out, and with two or more
+// bots the last one left wins.
+pub fn run_match(config: &ArenaConfig, bots: &mut [&mut dyn Bot]) -> Vec<MatchReport> {
+    let seats = bots.len();
+    let mut game = config.build_game(seats);
+    let mut reports: Vec<Option<MatchReport>> = vec![None; seats];
+    let mut events = Vec::new();
+    // A duel is settled once one snake is left, at the tick it happened.
+    let contested = |reports: &[Option<MatchReport>]| {
+        reports.iter().filter(|r| r.is_none()).count() >= seats.min(2)
+    };
+
+    loop {
+        events.clear();
+        events.extend(game.events().drain().map(|e| e.event));
+        let playing: Vec<usize> = (0..seats).filter(|&id| reports[id].is_none()).collect();
+        if playing.is_empty() {
+            break;
+        }
+        let end = if game.state() == GameState::Won || !contested(&reports) {
+            Some(MatchEnd::Won)
+        } else if game.ticks() >= config.max_ticks {
+            Some(MatchEnd::TickLimit)
+        } else {
+            None
+        };
+        if let Some(end) = end {
+            for id in playing {
+                conclude(
+                    bots,
+                    &mut reports,
+                    id,
+                    MatchReport::new(&game, id, end.clone()),
+                );
+            }
+            break;
+        }
+
+        let mut actions = Vec::new();
+        for id in playing {
+            let state = protocol::state_message(&game, id, &events);
+            match bots[id].act(&state) {
+                Ok(action) => actions.push((id, action)),
+                Err(err) => {
+                    game.withdraw(id);
+                    let report = MatchReport::new(&game, id, MatchEnd::Fault(err));
+                    conclude(bots, &mut reports, id, report);
+                }
+            }
+        }
+        let first = game.ticks() as usize % seats;
+        actions.sort_by_key(|&(id, _)| (id + seats - first) % seats);
+        for (id, action) in actions {
+            action.apply(&mut game, id);
+        }
+        record_deaths(&game, bots, &mut reports);
+        if contested(&reports) {
+            game.tick();
+            record_deaths(&game, bots, &mut reports);
+        }
+    }
+
+    reports.into_iter().flatten().collect()
+}
+
+fn record_deaths(game: &Game, bots: &mut [&mut dyn Bot], reports: &mut [Option<MatchReport>]) {
+    for (id, player) in game.players().iter().enumerate() {
+        if let Some(cause) = player.death().filter(|_| reports[id].is_none()) {
+            conclude(
+                bots,
+                reports,
+                id,
+                MatchReport::new(game, id, MatchEnd::Died(cause)),
+            );
+        }
+    }
+}
+
+// Tells the bot how its match went the moment its snake is out.
+fn conclude(
+    bots: &mut [&mut dyn Bot],
+    reports: &mut [Option<MatchReport>],
+    id: usize,
+    report: MatchReport,
+) {
+    bots[id].finish(&protocol::end_message(&report));
+    reports[id] = Some(report);
+}
@@ -0,0 +1,14 @@
+mod arena;
+mod process;
+mod protocol;
+mod strategies;
+mod tournament;
+
+pub use arena::{run_match, ArenaConfig, Bot, MatchEnd, MatchReport};
+pub use process::{BotError, BotProcess, STARTUP_GRACE};
+pub use protocol::{end_message, state_message, BotAction, PROTOCOL_VERSION};
+pub use strategies::Strategy;
+pub use tournament::{
+    elo_update, expected_score, replay, Entrant, MatchRecord, MatchSide, Standing, Tournament,
+    TournamentConfig, TournamentResult, INITIAL_RATING,
+};
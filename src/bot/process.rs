@@ -0,0 +1,236 @@
+use super::arena::Bot;
+use super::protocol::BotAction;
+use serde_json::{json, Value};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// Interpreters and JIT runtimes can take a while to come up, so by default
+// the first reply gets this much on top of the usual limit.
+pub const STARTUP_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BotError {
+    // The command couldn't be started at all.
+    Spawn(String),
+    // No reply within the limit.
+    Timeout(Duration),
+    // A reply came back that isn't a valid action.
+    Malformed { reply: String, reason: String },
+    // The process exited or closed its end of the pipe.
+    Crashed(Option<i32>),
+}
+
+// Replays store faults as `{"kind":"timeout","ms":200}` and the like, so
+// a match plays back to the same end.
+impl BotError {
+    pub fn to_json(&self) -> Value {
+        match self {
+            BotError::Spawn(reason) => json!({ "kind": "spawn", "reason": reason }),
+            BotError::Timeout(limit) => {
+                let ms = u64::try_from(limit.as_millis()).unwrap_or(u64::MAX);
+                json!({ "kind": "timeout", "ms": ms })
+            }
+            BotError::Malformed { reply, reason } => {
+                json!({ "kind": "malformed", "reply": reply, "reason": reason })
+            }
+            BotError::Crashed(code) => json!({ "kind": "crashed", "status": code }),
+        }
+    }
+
+    pub fn from_json(json: &Value) -> Result<Self, String> {
+        let text = |key: &str| {
+            json.get(key)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or(format!("bad \"{key}\""))
+        };
+        match json.get("kind").and_then(Value::as_str) {
+            Some("spawn") => Ok(BotError::Spawn(text("reason")?)),
+            Some("timeout") => json
+                .get("ms")
+                .and_then(Value::as_u64)
+                .map(|ms| BotError::Timeout(Duration::from_millis(ms)))
+                .ok_or_else(|| "bad \"ms\"".to_string()),
+            Some("malformed") => Ok(BotError::Malformed {
+                reply: text("reply")?,
+                reason: text("reason")?,
+            }),
+            Some("crashed") => match json.get("status") {
+                None | Some(Value::Null) => Ok(BotError::Crashed(None)),
+                Some(status) => status
+                    .as_i64()
+                    .and_then(|code| i32::try_from(code).ok())
+                    .map(|code| BotError::Crashed(Some(code)))
+                    .ok_or_else(|| "bad \"status\"".to_string()),
+            },
+            _ => Err("unknown fault, expected spawn, timeout, malformed or crashed".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BotError::Spawn(reason) => write!(f, "couldn't start: {reason}"),
+            BotError::Timeout(limit) => write!(f, "no reply within {}ms", limit.as_millis()),
+            BotError::Malformed { reply, reason } => {
+                write!(f, "bad reply ({reason}): {}", truncate(reply, 80))
+            }
+            BotError::Crashed(Some(code)) => write!(f, "exited with status {code}"),
+            BotError::Crashed(None) => f.write_str("closed its output"),
+        }
+    }
+}
+
+impl std::error::Error for BotError {}
+
+fn truncate(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((i, _)) => format!("{}...", &s[..i]),
+        None => s.to_string(),
+    }
+}
+
+// A bot running as a child process. States go to its stdin and replies are
+// read from its stdout, one JSON object per line; stderr is left alone so
+// bots can log to the terminal.
+pub struct BotProcess {
+    command: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    replies: Receiver<io::Result<String>>,
+    timeout: Duration,
+    startup_grace: Duration,
+    answered: bool,
+}
+
+impl BotProcess {
+    // `command` is split on whitespace into the program and its arguments,
+    // e.g. `./my_bot` or `python3 bots/greedy.py`. No shell is involved.
+    pub fn spawn(command: &str, timeout: Duration) -> Result<Self, BotError> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| BotError::Spawn("empty command".to_string()))?;
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| BotError::Spawn(format!("{program}: {e}")))?;
+        let stdin = child.stdin.take();
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| BotError::Spawn("no stdout pipe".to_string()))?;
+
+        // A blocking read can't time out, so lines are read on their own
+        // thread and waited for with a deadline. The thread ends when the
+        // pipe closes.
+        let (sender, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            command: command.to_string(),
+            child,
+            stdin,
+            replies,
+            timeout,
+            startup_grace: STARTUP_GRACE,
+            answered: false,
+        })
+    }
+
+    pub fn with_startup_grace(mut self, grace: Duration) -> Self {
+        self.startup_grace = grace;
+        self
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    fn send(&mut self, message: &Value) -> Result<(), BotError> {
+        let Some(stdin) = self.stdin.as_mut() else {
+            return Err(self.crashed());
+        };
+        match writeln!(stdin, "{message}").and_then(|_| stdin.flush()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(self.crashed()),
+        }
+    }
+
+    fn receive(&mut self) -> Result<String, BotError> {
+        let limit = if self.answered {
+            self.timeout
+        } else {
+            self.timeout + self.startup_grace
+        };
+        match self.replies.recv_timeout(limit) {
+            Ok(Ok(line)) => {
+                self.answered = true;
+                Ok(line)
+            }
+            Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => Err(self.crashed()),
+            Err(RecvTimeoutError::Timeout) => Err(BotError::Timeout(self.timeout)),
+        }
+    }
+
+    // The pipe closing usually means the process is on its way out; give it
+    // a moment so the report can carry the exit status.
+    fn crashed(&mut self) -> BotError {
+        for _ in 0..20 {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                return BotError::Crashed(status.code());
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        BotError::Crashed(None)
+    }
+}
+
+impl Bot for BotProcess {
+    fn name(&self) -> &str {
+        &self.command
+    }
+
+    fn act(&mut self, state: &Value) -> Result<BotAction, BotError> {
+        self.send(state)?;
+        let reply = self.receive()?;
+        reply
+            .parse()
+            .map_err(|reason| BotError::Malformed { reply, reason })
+    }
+
+    fn finish(&mut self, end: &Value) {
+        let _ = self.send(end);
+        self.stdin = None;
+    }
+}
+
+// Closing stdin is the bot's cue to exit; it gets a moment to do so before
+// being killed.
+impl Drop for BotProcess {
+    fn drop(&mut self) {
+        self.stdin = None;
+        for _ in 0..20 {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,196 @@
+use super::arena::MatchReport;
+use crate::core::{Direction, GameState, Vec2};
+use crate::game::Game;
+use crate::systems::GameEvent;
+use crate::traits::{Active, Moveable, Positioned};
+use serde_json::{json, Value};
+use std::fmt;
+use std::str::FromStr;
+
+// Bumped whenever a message changes shape, so bots can refuse a game they
+// don't understand.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+// One reply per state message: `{"action":"left"}`, `{"action":"wait"}` or
+// `{"action":"fire","target":[x,y]}`, where the target is optional.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotAction {
+    Wait,
+    Move(Direction),
+    Fire(Option<Vec2>),
+}
+
+impl BotAction {
+    pub fn apply(self, game: &mut Game, snake: usize) {
+        match self {
+            BotAction::Wait => {}
+            BotAction::Move(dir) => {
+                game.set_direction_for(snake, dir);
+                game.move_snake_for(snake);
+            }
+            BotAction::Fire(target) => {
+                if let Some(target) = target {
+                    game.aim_at_for(snake, target);
+                }
+                game.fire_for(snake);
+            }
+        }
+    }
+
+    pub fn from_json(reply: &Value) -> Result<Self, String> {
+        let action = reply
+            .get("action")
+            .ok_or("missing \"action\"")?
+            .as_str()
+            .ok_or("\"action\" must be a string")?;
+        match action {
+            "wait" => Ok(BotAction::Wait),
+            "up" => Ok(BotAction::Move(Direction::Up)),
+            "down" => Ok(BotAction::Move(Direction::Down)),
+            "left" => Ok(BotAction::Move(Direction::Left)),
+            "right" => Ok(BotAction::Move(Direction::Right)),
+            "fire" => match reply.get("target") {
+                None | Some(Value::Null) => Ok(BotAction::Fire(None)),
+                Some(target) => vec2_from_json(target)
+                    .map(|v| BotAction::Fire(Some(v)))
+                    .ok_or_else(|| "\"target\" must be [x, y]".to_string()),
+            },
+            other => Err(format!(
+                "unknown action '{other}', expected wait, up, down, left, right or fire"
+            )),
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        match self {
+            BotAction::Wait => json!({ "action": "wait" }),
+            BotAction::Move(dir) => json!({ "action": direction_name(dir) }),
+            BotAction::Fire(None) => json!({ "action": "fire" }),
+            BotAction::Fire(Some(target)) => {
+                json!({ "action": "fire", "target": vec2_to_json(target) })
+            }
+        }
+    }
+}
+
+impl FromStr for BotAction {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let reply: Value = serde_json::from_str(line).map_err(|e| e.to_string())?;
+        Self::from_json(&reply)
+    }
+}
+
+impl fmt::Display for BotAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+pub fn direction_name(dir: Direction) -> &'static str {
+    match dir {
+        Direction::Up => "up",
+        Direction::Down => "down",
+        Direction::Left => "left",
+        Direction::Right => "right",
+    }
+}
+
+pub fn state_name(state: GameState) -> &'static str {
+    match state {
+        GameState::Playing => "playing",
+        GameState::Paused => "paused",
+        GameState::Dead => "dead",
+        GameState::Won => "won",
+    }
+}
+
+pub(super) fn vec2_to_json(v: Vec2) -> Value {
+    json!([v.x, v.y])
+}
+
+fn cells_to_json<'a>(cells: impl IntoIterator<Item = &'a Vec2>) -> Value {
+    cells.into_iter().copied().map(vec2_to_json).collect()
+}
+
+pub(super) fn vec2_from_json(json: &Value) -> Option<Vec2> {
+    match json.as_array()?.as_slice() {
+        [x, y] => Some(Vec2::new(
+            i16::try_from(x.as_i64()?).ok()?,
+            i16::try_from(y.as_i64()?).ok()?,
+        )),
+        _ => None,
+    }
+}
+
+// Everything the bot steering snake `you` sees before choosing its action.
+// `snake`, `direction`, `score` and `cooldown` are its own; `snakes` lists
+// every snake in the match, out ones included, in snake order. `events`
+// lists what happened since the previous state, in the words the HUD feed
+// uses.
+pub fn state_message(game: &Game, you: usize, events: &[GameEvent]) -> Value {
+    let (width, height) = game.bounds();
+    let (player, snake) = (&game.players()[you], &game.snakes()[you]);
+    let snakes: Vec<Value> = game
+        .players()
+        .iter()
+        .zip(game.snakes())
+        .enumerate()
+        .map(|(id, (p, s))| {
+            json!({
+                "alive": p.is_alive(),
+                "body": cells_to_json(s.segments()),
+                "direction": direction_name(s.direction()),
+                "score": game.score_of(id),
+            })
+        })
+        .collect();
+    let food = game.food();
+    let bullets: Vec<Value> = game
+        .bullets()
+        .iter()
+        .map(|b| {
+            json!({
+                "position": vec2_to_json(b.position()),
+                "velocity": vec2_to_json(b.velocity()),
+            })
+        })
+        .collect();
+    let events: Vec<String> = events.iter().map(|e| e.to_string()).collect();
+    json!({
+        "type": "state",
+        "version": PROTOCOL_VERSION,
+        "tick": game.ticks(),
+        "bounds": [width, height],
+        "mode": game.mode().as_str(),
+        "state": state_name(game.state()),
+        "you": you,
+        "score": game.score_of(you),
+        "snake": cells_to_json(snake.segments()),
+        "direction": direction_name(snake.direction()),
+        "snakes": snakes,
+        "food": {
+            "active": food.is_active(),
+            "position": vec2_to_json(food.position()),
+            "velocity": vec2_to_json(food.velocity()),
+        },
+        "bullets": bullets,
+        "walls": cells_to_json(game.walls().cells()),
+        "cooldown": player.bullet_cooldown(),
+        "events": events,
+    })
+}
+
+// Sent once the bot's snake is out or the match is over; no reply is
+// expected.
+pub fn end_message(report: &MatchReport) -> Value {
+    json!({
+        "type": "end",
+        "version": PROTOCOL_VERSION,
+        "tick": report.ticks,
+        "result": report.end.to_string(),
+        "score": report.score,
+        "length": report.length,
+    })
+}
@@ -0,0 +1,191 @@
+use super::arena::Bot;
+use super::process::BotError;
+use super::protocol::{vec2_from_json, BotAction};
+use crate::core::{Direction, Vec2};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+// Bots that ship with the game, for testing the arena and as sparring
+// partners. They read the same state messages an external bot does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    // Never does anything; the baseline every bot should beat.
+    Idle,
+    // Stays put and shoots at the food whenever the gun is ready.
+    Sniper,
+    // Chases the food, shooting when it lines up.
+    Hunter,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Idle, Strategy::Sniper, Strategy::Hunter];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Strategy::Idle => "idle",
+            Strategy::Sniper => "sniper",
+            Strategy::Hunter => "hunter",
+        }
+    }
+
+    pub fn bot(self) -> Box<dyn Bot> {
+        Box::new(BuiltinBot(self))
+    }
+
+    fn choose(self, view: &View) -> BotAction {
+        match self {
+            Strategy::Idle => BotAction::Wait,
+            Strategy::Sniper if view.cooldown == 0 => view
+                .food
+                .map_or(BotAction::Wait, |food| BotAction::Fire(Some(food))),
+            Strategy::Sniper => BotAction::Wait,
+            Strategy::Hunter => hunt(view),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|st| st.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|st| st.as_str()).collect();
+                format!(
+                    "unknown strategy '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+struct BuiltinBot(Strategy);
+
+impl Bot for BuiltinBot {
+    fn name(&self) -> &str {
+        self.0.as_str()
+    }
+
+    fn act(&mut self, state: &Value) -> Result<BotAction, BotError> {
+        let view = View::read(state).ok_or_else(|| BotError::Malformed {
+            reply: state.to_string(),
+            reason: "state the built-in bot couldn't read".to_string(),
+        })?;
+        Ok(self.0.choose(&view))
+    }
+}
+
+// The parts of a state message the built-ins look at.
+struct View {
+    bounds: Vec2,
+    snake: Vec<Vec2>,
+    direction: Direction,
+    // Walls and every snake's body, gathered once so each candidate step
+    // is a single lookup.
+    blocked: HashSet<Vec2>,
+    // Where the food will be next tick, if there is any.
+    food: Option<Vec2>,
+    cooldown: i64,
+}
+
+impl View {
+    fn read(state: &Value) -> Option<Self> {
+        let cells = |key: &str| -> Option<Vec<Vec2>> {
+            state
+                .get(key)?
+                .as_array()?
+                .iter()
+                .map(vec2_from_json)
+                .collect()
+        };
+        let food = state.get("food")?;
+        let food = if food.get("active")?.as_bool()? {
+            Some(vec2_from_json(food.get("position")?)? + vec2_from_json(food.get("velocity")?)?)
+        } else {
+            None
+        };
+        let direction = match state.get("direction")?.as_str()? {
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            "left" => Direction::Left,
+            _ => Direction::Right,
+        };
+        // Whole bodies count, tails included: the game checks a step
+        // against the snakes before any tail moves out of the way. States
+        // without `snakes` have only the bot's own.
+        let snake = cells("snake")?;
+        let rivals = match state.get("snakes") {
+            Some(snakes) => snakes
+                .as_array()?
+                .iter()
+                .filter(|s| s.get("alive").and_then(Value::as_bool) == Some(true))
+                .map(|s| {
+                    s.get("body")?
+                        .as_array()?
+                        .iter()
+                        .map(vec2_from_json)
+                        .collect()
+                })
+                .collect::<Option<Vec<Vec<Vec2>>>>()?,
+            None => Vec::new(),
+        };
+        let blocked = cells("walls")?
+            .into_iter()
+            .chain(snake.iter().copied())
+            .chain(rivals.into_iter().flatten())
+            .collect();
+        Some(Self {
+            bounds: vec2_from_json(state.get("bounds")?)?,
+            snake,
+            direction,
+            blocked,
+            food,
+            cooldown: state.get("cooldown")?.as_i64()?,
+        })
+    }
+
+    fn head(&self) -> Vec2 {
+        self.snake.first().copied().unwrap_or_default()
+    }
+
+    fn is_safe(&self, pos: Vec2) -> bool {
+        pos.in_bounds(self.bounds) && !self.blocked.contains(&pos)
+    }
+}
+
+// Fires when the food is straight along a row or column; otherwise takes
+// the safe step that gets closest to it. Waiting is safe from everything
+// but another snake, since a snake only moves when told to.
+fn hunt(view: &View) -> BotAction {
+    let Some(food) = view.food else {
+        return BotAction::Wait;
+    };
+    let head = view.head();
+    if view.cooldown == 0 && (food.x == head.x || food.y == head.y) {
+        return BotAction::Fire(Some(food));
+    }
+    [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .into_iter()
+    .filter(|&dir| !dir.is_opposite(view.direction))
+    .map(|dir| (dir, head + dir.to_vec2()))
+    .filter(|&(_, next)| view.is_safe(next))
+    .min_by_key(|&(_, next)| next.manhattan_distance(food))
+    .filter(|&(_, next)| next.manhattan_distance(food) < head.manhattan_distance(food))
+    .map_or(BotAction::Wait, |(dir, _)| BotAction::Move(dir))
+}
@@ -0,0 +1,446 @@
+use super::arena::{run_match, ArenaConfig, Bot, MatchEnd, MatchReport};
+use super::process::{BotError, BotProcess};
+use super::protocol::{BotAction, PROTOCOL_VERSION};
+use super::strategies::Strategy;
+use crate::core::splitmix64;
+use crate::systems::GameMode;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::Duration;
+
+pub const INITIAL_RATING: f64 = 1500.0;
+const ELO_K: f64 = 32.0;
+
+// A built-in strategy name picks that strategy; anything else is run as a
+// command, so `./hunter` still means the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entrant {
+    Builtin(Strategy),
+    Command(String),
+}
+
+impl Entrant {
+    pub fn parse(spec: &str) -> Self {
+        spec.parse()
+            .map_or_else(|_| Entrant::Command(spec.to_string()), Entrant::Builtin)
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Entrant::Builtin(strategy) => strategy.as_str(),
+            Entrant::Command(command) => command,
+        }
+    }
+
+    // Process bots are started fresh for every match, so a crash only costs
+    // the match it happens in.
+    pub fn start(&self, timeout: Duration) -> Result<Box<dyn Bot>, BotError> {
+        match self {
+            Entrant::Builtin(strategy) => Ok(strategy.bot()),
+            Entrant::Command(command) => {
+                BotProcess::spawn(command, timeout).map(|bot| Box::new(bot) as Box<dyn Bot>)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Entrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TournamentConfig {
+    // The arena every match uses; its seed is the base the match seeds are
+    // derived from.
+    pub arena: ArenaConfig,
+    // How many times each pair meets.
+    pub rounds: u32,
+    pub timeout: Duration,
+}
+
+impl TournamentConfig {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            arena: ArenaConfig::new(seed),
+            rounds: 1,
+            timeout: Duration::from_millis(200),
+        }
+    }
+
+    // Mixed, so neighbouring matches get unrelated arenas.
+    pub fn match_seed(&self, index: usize) -> u64 {
+        splitmix64(self.arena.seed, index as u64)
+    }
+}
+
+// One side of a match: how its snake did and every action it took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchSide {
+    pub entrant: usize,
+    pub report: MatchReport,
+    pub actions: Vec<BotAction>,
+}
+
+// A match is a duel: both bots steer a snake in the same arena. The sides
+// are in snake order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchRecord {
+    pub index: usize,
+    pub round: u32,
+    pub arena: ArenaConfig,
+    pub sides: Vec<MatchSide>,
+}
+
+impl MatchRecord {
+    // The two sides, if the record has exactly two; one read back from a
+    // replay may not.
+    fn duel(&self) -> Option<(&MatchSide, &MatchSide)> {
+        match self.sides.as_slice() {
+            [a, b] => Some((a, b)),
+            _ => None,
+        }
+    }
+
+    // From the first side's view: 1 for a win, 0.5 for a draw, 0 for a loss.
+    pub fn outcome(&self) -> Option<f64> {
+        let (a, b) = self.duel()?;
+        Some(match compare(&a.report, &b.report) {
+            Ordering::Greater => 1.0,
+            Ordering::Equal => 0.5,
+            Ordering::Less => 0.0,
+        })
+    }
+
+    // `None` for a draw, or for a record that isn't a duel.
+    pub fn winner(&self) -> Option<usize> {
+        let (a, b) = self.duel()?;
+        match compare(&a.report, &b.report) {
+            Ordering::Greater => Some(a.entrant),
+            Ordering::Equal => None,
+            Ordering::Less => Some(b.entrant),
+        }
+    }
+
+    // Line-delimited JSON: the match header, then one line per side with
+    // its actions, in snake order, and how it faulted if it did. The game
+    // is deterministic for a seed, so that's all `replay` needs to play it
+    // again.
+    pub fn replay(&self, names: &[String]) -> String {
+        let arena = &self.arena;
+        // The seed is a string, since JSON numbers can't hold every u64.
+        let header = json!({
+            "type": "match",
+            "version": PROTOCOL_VERSION,
+            "match": self.index + 1,
+            "round": self.round + 1,
+            "seed": arena.seed.to_string(),
+            "mode": arena.mode.as_str(),
+            "width": arena.width,
+            "height": arena.height,
+            "max_ticks": arena.max_ticks,
+        });
+        let mut out = format!("{header}\n");
+        for side in &self.sides {
+            let name = names.get(side.entrant).map_or("?", String::as_str);
+            let actions: Vec<Value> = side.actions.iter().map(|a| a.to_json()).collect();
+            let line = json!({
+                "type": "side",
+                "bot": name,
+                "result": side.report.end.to_string(),
+                "score": side.report.score,
+                "ticks": side.report.ticks,
+                "actions": actions,
+                "fault": match &side.report.end {
+                    MatchEnd::Fault(err) => err.to_json(),
+                    _ => Value::Null,
+                },
+            });
+            out.push_str(&format!("{line}\n"));
+        }
+        out
+    }
+}
+
+// Faulting loses to playing on, and being out loses to still being on the
+// board; then score, then how long the snake lasted.
+fn compare(a: &MatchReport, b: &MatchReport) -> Ordering {
+    let key = |r: &MatchReport| (!r.end.is_fault(), r.end.survived(), r.score, r.ticks);
+    key(a).cmp(&key(b))
+}
+
+// Parses a replay written by `MatchRecord::replay` and plays the match
+// again from the sides' actions, returning the reports it produces.
+pub fn replay(source: &str) -> Result<Vec<(String, MatchReport)>, String> {
+    let mut lines = source.lines().filter(|l| !l.trim().is_empty());
+    let header: Value =
+        serde_json::from_str(lines.next().ok_or("empty replay")?).map_err(|e| e.to_string())?;
+    let field = |key: &str| header.get(key).ok_or(format!("missing \"{key}\""));
+    let int = |key: &str| field(key)?.as_i64().ok_or(format!("bad \"{key}\""));
+    let arena = ArenaConfig {
+        width: i16::try_from(int("width")?).map_err(|e| e.to_string())?,
+        height: i16::try_from(int("height")?).map_err(|e| e.to_string())?,
+        mode: field("mode")?
+            .as_str()
+            .ok_or("bad \"mode\"")?
+            .parse::<GameMode>()?,
+        seed: field("seed")?
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or("bad \"seed\"")?,
+        max_ticks: u64::try_from(int("max_ticks")?).map_err(|e| e.to_string())?,
+    };
+
+    let (names, mut bots): (Vec<String>, Vec<Playback>) = lines
+        .map(|line| {
+            let side: Value = serde_json::from_str(line).map_err(|e| e.to_string())?;
+            let name = side.get("bot").and_then(Value::as_str).unwrap_or("?");
+            let actions = side
+                .get("actions")
+                .and_then(Value::as_array)
+                .ok_or("bad \"actions\"")?
+                .iter()
+                .map(BotAction::from_json)
+                .collect::<Result<Vec<_>, _>>()?;
+            let fault = match side.get("fault") {
+                None | Some(Value::Null) => None,
+                Some(fault) => Some(BotError::from_json(fault)?),
+            };
+            let bot = Playback {
+                actions: actions.into_iter(),
+                fault,
+            };
+            Ok((name.to_string(), bot))
+        })
+        .collect::<Result<Vec<_>, String>>()?
+        .into_iter()
+        .unzip();
+    let mut seated: Vec<&mut dyn Bot> = bots.iter_mut().map(|b| b as &mut dyn Bot).collect();
+    let reports = run_match(&arena, &mut seated);
+    Ok(names.into_iter().zip(reports).collect())
+}
+
+struct Playback {
+    actions: std::vec::IntoIter<BotAction>,
+    // How the bot faulted, for sides that did.
+    fault: Option<BotError>,
+}
+
+impl Bot for Playback {
+    fn name(&self) -> &str {
+        "replay"
+    }
+
+    // A replay that runs out of actions has caught up with where the bot
+    // faulted, so it faults there too, the same way. A replay cut short
+    // runs out without a recorded fault, which reads as the output closing.
+    fn act(&mut self, _state: &Value) -> Result<BotAction, BotError> {
+        self.actions
+            .next()
+            .ok_or_else(|| self.fault.clone().unwrap_or(BotError::Crashed(None)))
+    }
+}
+
+// Keeps a copy of every action on its way to the game.
+struct Recording<'a> {
+    bot: &'a mut dyn Bot,
+    actions: Vec<BotAction>,
+}
+
+impl Bot for Recording<'_> {
+    fn name(&self) -> &str {
+        self.bot.name()
+    }
+
+    fn act(&mut self, state: &Value) -> Result<BotAction, BotError> {
+        let action = self.bot.act(state)?;
+        self.actions.push(action);
+        Ok(action)
+    }
+
+    fn finish(&mut self, end: &Value) {
+        self.bot.finish(end);
+    }
+}
+
+// Sits in for a bot that couldn't be started, faulting on its first turn
+// so the match still runs and the other side is left alone on the board.
+struct Absent(BotError);
+
+impl Bot for Absent {
+    fn name(&self) -> &str {
+        "absent"
+    }
+
+    fn act(&mut self, _state: &Value) -> Result<BotAction, BotError> {
+        Err(self.0.clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub entrant: usize,
+    pub name: String,
+    pub rating: f64,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub faults: u32,
+    pub total_score: u64,
+    pub games: u32,
+}
+
+impl Standing {
+    fn new(entrant: usize, name: &str) -> Self {
+        Self {
+            entrant,
+            name: name.to_string(),
+            rating: INITIAL_RATING,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            faults: 0,
+            total_score: 0,
+            games: 0,
+        }
+    }
+
+    pub fn average_score(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_score as f64 / f64::from(self.games)
+        }
+    }
+
+    fn record(&mut self, report: &MatchReport, outcome: f64) {
+        self.games += 1;
+        self.total_score += u64::from(report.score);
+        self.faults += u32::from(report.end.is_fault());
+        match outcome {
+            o if o > 0.5 => self.wins += 1,
+            o if o < 0.5 => self.losses += 1,
+            _ => self.draws += 1,
+        }
+    }
+}
+
+// The chance `rating` beats `opponent`, by the Elo logistic curve.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+// Both ratings after one game, `outcome` being the first player's result.
+pub fn elo_update(a: f64, b: f64, outcome: f64) -> (f64, f64) {
+    let delta = ELO_K * (outcome - expected_score(a, b));
+    (a + delta, b - delta)
+}
+
+pub struct Tournament {
+    entrants: Vec<Entrant>,
+    config: TournamentConfig,
+}
+
+pub struct TournamentResult {
+    // Best first: by rating, then wins, then entry order.
+    pub standings: Vec<Standing>,
+    pub matches: Vec<MatchRecord>,
+}
+
+impl Tournament {
+    pub fn new(entrants: Vec<Entrant>, config: TournamentConfig) -> Self {
+        Self { entrants, config }
+    }
+
+    pub fn entrants(&self) -> &[Entrant] {
+        &self.entrants
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.entrants.iter().map(|e| e.name().to_string()).collect()
+    }
+
+    // Every pair meets once a round, in a fixed order, so a tournament with
+    // the same seed and deterministic bots plays out the same every time.
+    // The pair swap snakes every other round, so neither always plays the
+    // player's snake, which the food spawns fairly for.
+    pub fn schedule(&self) -> Vec<(u32, usize, usize)> {
+        let n = self.entrants.len();
+        (0..self.config.rounds)
+            .flat_map(|round| (0..n).flat_map(move |a| (a + 1..n).map(move |b| (round, a, b))))
+            .collect()
+    }
+
+    // `on_match` sees each record as it finishes, for progress output.
+    pub fn run(&self, mut on_match: impl FnMut(&MatchRecord)) -> TournamentResult {
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .enumerate()
+            .map(|(i, e)| Standing::new(i, e.name()))
+            .collect();
+        let mut matches = Vec::new();
+
+        for (index, (round, a, b)) in self.schedule().into_iter().enumerate() {
+            let arena = ArenaConfig {
+                seed: self.config.match_seed(index),
+                ..self.config.arena
+            };
+            let (a, b) = if round % 2 == 0 { (a, b) } else { (b, a) };
+            let record = MatchRecord {
+                index,
+                round,
+                arena,
+                sides: self.play([a, b], &arena),
+            };
+
+            if let (Some(outcome), Some((side_a, side_b))) = (record.outcome(), record.duel()) {
+                let (ra, rb) = elo_update(standings[a].rating, standings[b].rating, outcome);
+                standings[a].rating = ra;
+                standings[b].rating = rb;
+                standings[a].record(&side_a.report, outcome);
+                standings[b].record(&side_b.report, 1.0 - outcome);
+            }
+
+            on_match(&record);
+            matches.push(record);
+        }
+
+        standings.sort_by(|x, y| {
+            y.rating
+                .total_cmp(&x.rating)
+                .then(y.wins.cmp(&x.wins))
+                .then(x.entrant.cmp(&y.entrant))
+        });
+        TournamentResult { standings, matches }
+    }
+
+    fn play(&self, seats: [usize; 2], arena: &ArenaConfig) -> Vec<MatchSide> {
+        let mut bots = seats.map(|entrant| {
+            self.entrants
+                .get(entrant)
+                .ok_or_else(|| BotError::Spawn("no such entrant".to_string()))
+                .and_then(|e| e.start(self.config.timeout))
+                .unwrap_or_else(|err| Box::new(Absent(err)))
+        });
+        let mut recordings = bots.each_mut().map(|bot| Recording {
+            bot: bot.as_mut(),
+            actions: Vec::new(),
+        });
+        let mut seated: Vec<&mut dyn Bot> =
+            recordings.iter_mut().map(|r| r as &mut dyn Bot).collect();
+        let reports = run_match(arena, &mut seated);
+        seats
+            .into_iter()
+            .zip(reports)
+            .zip(recordings)
+            .map(|((entrant, report), recording)| MatchSide {
+                entrant,
+                report,
+                actions: recording.actions,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,241 @@
+use crate::core::{Direction, Vec2};
+use crate::game::{GameConfig, SnakeStart};
+use crate::systems::{Difficulty, LevelCurve, Objective};
+use std::fmt;
+use std::time::Duration;
+
+const MIN_WIDTH: usize = 8;
+const MAX_WIDTH: usize = 60;
+const MIN_HEIGHT: usize = 5;
+const MAX_HEIGHT: usize = 30;
+const MAX_START_LENGTH: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LevelParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl LevelParseError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LevelParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for LevelParseError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CampaignLevel {
+    pub id: String,
+    pub name: String,
+    pub objective: Objective,
+    // Scores needed for the second and third star; finishing earns the first.
+    pub stars: [u32; 2],
+    pub food_speed: i16,
+    pub food_lifetime: u32,
+    pub width: i16,
+    pub height: i16,
+    pub walls: Vec<Vec2>,
+    pub start: SnakeStart,
+}
+
+impl CampaignLevel {
+    // `key = value` header lines, then `map:` followed by the arena drawn
+    // with `.` for open cells, `#` for walls and `S` for the snake's head.
+    pub fn parse(id: &str, source: &str) -> Result<Self, LevelParseError> {
+        let mut name = None;
+        let mut objective = None;
+        let mut stars = None;
+        let mut food_speed = 1;
+        let mut food_lifetime = 0;
+        let mut length = 3;
+        let mut direction = Direction::Right;
+        let mut map: Option<(usize, Vec<&str>)> = None;
+
+        for (idx, raw) in source.lines().enumerate() {
+            let line_no = idx + 1;
+
+            if let Some((_, rows)) = map.as_mut() {
+                rows.push(raw.trim_end());
+                continue;
+            }
+
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == "map:" {
+                map = Some((line_no, Vec::new()));
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(LevelParseError::new(line_no, "expected 'key = value'"));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let err = |message: String| LevelParseError::new(line_no, message);
+
+            match key {
+                "name" => name = Some(value.to_string()),
+                "objective" => objective = Some(value.parse::<Objective>().map_err(err)?),
+                "stars" => stars = Some(parse_stars(value).map_err(err)?),
+                "food_speed" => food_speed = parse_number(value, 0, 3).map_err(err)? as i16,
+                "food_lifetime" => food_lifetime = parse_number(value, 0, 10_000).map_err(err)?,
+                "length" => {
+                    length = parse_number(value, 1, MAX_START_LENGTH as u32).map_err(err)? as usize
+                }
+                "direction" => direction = parse_direction(value).map_err(err)?,
+                _ => return Err(err(format!("unknown key '{}'", key))),
+            }
+        }
+
+        let name = name.ok_or_else(|| LevelParseError::new(0, "missing 'name'"))?;
+        let objective = objective.ok_or_else(|| LevelParseError::new(0, "missing 'objective'"))?;
+        let stars = stars.ok_or_else(|| LevelParseError::new(0, "missing 'stars'"))?;
+        let (map_line, mut rows) = map.ok_or_else(|| LevelParseError::new(0, "missing 'map:'"))?;
+
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = rows.len();
+        if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) || !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height)
+        {
+            return Err(LevelParseError::new(
+                map_line,
+                format!(
+                    "map must be {}-{} wide and {}-{} tall, got {}x{}",
+                    MIN_WIDTH, MAX_WIDTH, MIN_HEIGHT, MAX_HEIGHT, width, height
+                ),
+            ));
+        }
+
+        let mut walls = Vec::new();
+        let mut head = None;
+        for (y, row) in rows.iter().enumerate() {
+            let line_no = map_line + 1 + y;
+            for (x, c) in row.chars().enumerate() {
+                let pos = Vec2::new(x as i16, y as i16);
+                match c {
+                    '.' | ' ' => {}
+                    '#' => walls.push(pos),
+                    'S' if head.is_none() => head = Some(pos),
+                    'S' => return Err(LevelParseError::new(line_no, "more than one 'S'")),
+                    other => {
+                        return Err(LevelParseError::new(
+                            line_no,
+                            format!("unexpected '{}' in map", other),
+                        ))
+                    }
+                }
+            }
+        }
+
+        let head = head.ok_or_else(|| LevelParseError::new(map_line, "map has no 'S'"))?;
+        let bounds = Vec2::new(width as i16, height as i16);
+        let behind = direction.opposite().to_vec2();
+        let body_fits = (0..length as i16).all(|i| {
+            let cell = head + behind * i;
+            cell.in_bounds(bounds) && !walls.contains(&cell)
+        });
+        if !body_fits {
+            return Err(LevelParseError::new(
+                map_line,
+                "starting snake runs into a wall or off the map",
+            ));
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            name,
+            objective,
+            stars,
+            food_speed,
+            food_lifetime,
+            width: width as i16,
+            height: height as i16,
+            walls,
+            start: SnakeStart {
+                position: head,
+                direction,
+                length,
+            },
+        })
+    }
+
+    pub fn stars_for(&self, score: u32) -> u8 {
+        1 + self.stars.iter().filter(|&&needed| score >= needed).count() as u8
+    }
+
+    // Campaign levels keep one difficulty for the whole run; the map and the
+    // objective are what make them harder.
+    pub fn game_config(&self, tick_duration: Duration) -> GameConfig {
+        let mut config = GameConfig::new(self.width, self.height);
+        config.walls = self.walls.clone();
+        config.start = Some(self.start);
+        config.objective = Some(self.objective);
+        config.tick_duration = tick_duration;
+        config.levels = LevelCurve {
+            steps: vec![Difficulty::new(
+                self.food_speed,
+                self.food_lifetime,
+                100,
+                100,
+            )],
+            ..LevelCurve::flat()
+        };
+        config
+    }
+}
+
+fn parse_number(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(n) if (min..=max).contains(&n) => Ok(n),
+        _ => Err(format!(
+            "expected a number from {} to {}, got '{}'",
+            min, max, value
+        )),
+    }
+}
+
+fn parse_stars(value: &str) -> Result<[u32; 2], String> {
+    let numbers: Vec<u32> = value
+        .split_whitespace()
+        .map(|n| n.parse::<u32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("'{}' is not a list of scores", value))?;
+    match numbers[..] {
+        [two, three] if two <= three => Ok([two, three]),
+        _ => Err("expected two ascending scores, e.g. 'stars = 8 12'".to_string()),
+    }
+}
+
+fn parse_direction(value: &str) -> Result<Direction, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "up" => Ok(Direction::Up),
+        "down" => Ok(Direction::Down),
+        "left" => Ok(Direction::Left),
+        "right" => Ok(Direction::Right),
+        _ => Err(format!(
+            "unknown direction '{}', expected up, down, left or right",
+            value
+        )),
+    }
+}
@@ -23,6 +23,30 @@ impl Vec2 {
         (self.x as i32 * other.x as i32) + (self.y as i32 * other.y as i32)
     }
 
+    pub fn manhattan_distance(&self, other: Vec2) -> i32 {
+        (self.x as i32 - other.x as i32).abs() + (self.y as i32 - other.y as i32).abs()
+    }
+
+    pub fn wrapped(&self, bounds: Vec2) -> Self {
+        Self::new(
+            self.x.rem_euclid(bounds.x.max(1)),
+            self.y.rem_euclid(bounds.y.max(1)),
+        )
+    }
+
+    // Reads a difference between neighbouring cells the short way round, so
+    // a step across a wrapping edge comes out as a single step.
+    pub fn wrapped_step(&self, bounds: Vec2) -> Self {
+        let axis = |d: i16, size: i16| {
+            if size > 2 {
+                (d + 1).rem_euclid(size) - 1
+            } else {
+                d
+            }
+        };
+        Self::new(axis(self.x, bounds.x), axis(self.y, bounds.y))
+    }
+
     pub fn in_bounds(&self, bounds: Vec2) -> bool {
         self.x >= 0 && self.x < bounds.x && self.y >= 0 && self.y < bounds.y
     }
@@ -33,6 +57,16 @@ impl Vec2 {
             offset.y as u16 + self.y as u16,
         )
     }
+
+    // Inverse of `to_screen`; either column of a two-wide cell maps back to it.
+    pub fn from_screen(x: u16, y: u16, offset: Vec2) -> Option<Self> {
+        let dx = x as i32 - offset.x as i32;
+        let dy = y as i32 - offset.y as i32;
+        if dx < 0 || dy < 0 {
+            return None;
+        }
+        Some(Self::new((dx / 2) as i16, dy as i16))
+    }
 }
 
 impl Add for Vec2 {
@@ -75,6 +109,25 @@ impl Direction {
         }
     }
 
+    // The axis the vector mostly points along; horizontal wins ties.
+    pub fn dominant(v: Vec2) -> Option<Self> {
+        match (v.x.unsigned_abs(), v.y.unsigned_abs()) {
+            (0, 0) => None,
+            (x, y) if x >= y => Self::from_vec2(Vec2::new(v.x, 0)),
+            _ => Self::from_vec2(Vec2::new(0, v.y)),
+        }
+    }
+
+    pub fn from_vec2(v: Vec2) -> Option<Self> {
+        match (v.x.signum(), v.y.signum()) {
+            (0, -1) => Some(Direction::Up),
+            (0, 1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            (1, 0) => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
     pub const fn opposite(self) -> Self {
         match self {
             Direction::Up => Direction::Down,
@@ -103,6 +156,7 @@ pub enum GameState {
     Playing,
     Paused,
     Dead,
+    Won,
 }
 
 impl GameState {
@@ -113,6 +167,10 @@ impl GameState {
     pub fn is_dead(&self) -> bool {
         matches!(self, GameState::Dead)
     }
+
+    pub fn is_over(&self) -> bool {
+        matches!(self, GameState::Dead | GameState::Won)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -265,3 +323,13 @@ impl From<SegmentIndex> for usize {
         index.0
     }
 }
+
+// SplitMix64's `n`th output for `seed`. Anything derived from a seed goes
+// through this rather than the game's RNG, so a dependency bump can't
+// change what a seed produces.
+pub fn splitmix64(seed: u64, n: u64) -> u64 {
+    let mut z = seed.wrapping_add(n.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
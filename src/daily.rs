@@ -0,0 +1,140 @@
+use crate::core::splitmix64;
+use crate::game::GameConfig;
+use crate::systems::GameMode;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const DAILY_TICK: Duration = Duration::from_millis(70);
+const DAILY_MODES: [GameMode; 3] = [GameMode::Classic, GameMode::TimeAttack, GameMode::Survival];
+const MIN_WIDTH: i16 = 24;
+const MAX_WIDTH: i16 = 36;
+const MIN_HEIGHT: i16 = 14;
+const MAX_HEIGHT: i16 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+        valid.then_some(Self { year, month, day })
+    }
+
+    // Days are counted in UTC so the whole team rolls over to a new
+    // challenge at the same moment.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_days_since_epoch((secs / SECONDS_PER_DAY) as i64)
+    }
+
+    // Howard Hinnant's civil-from-days algorithm.
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    pub fn days_since_epoch(&self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let mp = if month > 2 { month - 3 } else { month + 9 };
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid date '{}', expected YYYY-MM-DD", s);
+        let mut parts = s.trim().splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let (Ok(year), Ok(month), Ok(day)) = (year.parse(), month.parse(), day.parse()) else {
+            return Err(invalid());
+        };
+        Self::new(year, month, day).ok_or_else(invalid)
+    }
+}
+
+fn pick(seed: u64, salt: u64, min: i16, max: i16) -> i16 {
+    min + (splitmix64(seed ^ salt, 0) % (max - min + 1) as u64) as i16
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyChallenge {
+    pub date: Date,
+    pub seed: u64,
+    pub mode: GameMode,
+    pub width: i16,
+    pub height: i16,
+}
+
+impl DailyChallenge {
+    pub fn for_date(date: Date) -> Self {
+        let seed = splitmix64(date.days_since_epoch() as u64, 0);
+        let mode = DAILY_MODES[(splitmix64(seed ^ 3, 0) % DAILY_MODES.len() as u64) as usize];
+        Self {
+            date,
+            seed,
+            mode,
+            width: pick(seed, 1, MIN_WIDTH, MAX_WIDTH),
+            height: pick(seed, 2, MIN_HEIGHT, MAX_HEIGHT),
+        }
+    }
+
+    pub fn today() -> Self {
+        Self::for_date(Date::today())
+    }
+
+    // Speed is part of the challenge too: everyone plays the same tick rate
+    // whatever their settings say.
+    pub fn game_config(&self) -> GameConfig {
+        let mut config = GameConfig::new(self.width, self.height);
+        config.mode = self.mode;
+        config.seed = Some(self.seed);
+        config.tick_duration = DAILY_TICK;
+        config
+    }
+}
@@ -1,8 +1,10 @@
 use crate::core::{Direction, Vec2};
-use crate::traits::{Active, BoundedTickable, Collidable, Moveable, Positioned, Renderable};
-use ratatui::{buffer::Buffer, style::Color, style::Style};
+use crate::renderer::{RenderContext, RenderLayer};
+use crate::traits::{
+    Active, BoundedTickable, Collidable, Layered, Moveable, Positioned, Renderable,
+};
+use ratatui::{buffer::Buffer, style::Style};
 
-#[allow(dead_code)]
 pub struct BulletConfig {
     pub max_lifetime: u8,
     pub speed: i16,
@@ -18,6 +20,9 @@ impl Default for BulletConfig {
 }
 
 pub struct Bullet {
+    // The snake that fired it.
+    owner: usize,
+    origin: Vec2,
     position: Vec2,
     velocity: Vec2,
     active: bool,
@@ -33,6 +38,8 @@ impl Bullet {
     pub fn with_config(position: Vec2, direction: Direction, config: BulletConfig) -> Self {
         let velocity = direction.to_vec2() * config.speed;
         Self {
+            owner: 0,
+            origin: position,
             position,
             velocity,
             active: true,
@@ -41,16 +48,31 @@ impl Bullet {
         }
     }
 
-    #[allow(dead_code)]
+    pub fn with_owner(mut self, owner: usize) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn owner(&self) -> usize {
+        self.owner
+    }
+
     pub fn lifetime(&self) -> u8 {
         self.lifetime
     }
 
-    #[allow(dead_code)]
     pub fn max_lifetime(&self) -> u8 {
         self.max_lifetime
     }
 
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub fn distance_travelled(&self) -> i32 {
+        self.origin.manhattan_distance(self.position)
+    }
+
     pub fn lifetime_fraction(&self) -> f32 {
         if self.max_lifetime == 0 {
             return 0.0;
@@ -109,19 +131,20 @@ impl BoundedTickable for Bullet {
 
 impl Collidable for Bullet {}
 
+impl Layered for Bullet {
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Projectiles
+    }
+}
+
 impl Renderable for Bullet {
-    fn render(&self, offset: Vec2, buf: &mut Buffer) {
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
         if !self.is_active() {
             return;
         }
 
-        let intensity = (self.lifetime_fraction() * 255.0) as u8;
-        let (x, y) = self.position.to_screen(offset);
-        buf.set_string(
-            x,
-            y,
-            "██",
-            Style::default().fg(Color::Rgb(255, intensity, 0)),
-        );
+        let color = ctx.theme.bullet(self.lifetime_fraction());
+        let (x, y) = self.position.to_screen(ctx.offset);
+        buf.set_string(x, y, ctx.glyphs.bullet(), Style::default().fg(color));
     }
 }
@@ -1,35 +1,27 @@
 use crate::core::{Direction, Edge, Vec2};
+use crate::renderer::{RenderContext, RenderLayer};
 use crate::traits::{
-    Active, BoundedTickable, EdgeSpawnable, Moveable, Positioned, Renderable, Targetable,
+    Active, BoundedTickable, EdgeSpawnable, Layered, Moveable, Positioned, Renderable, Targetable,
 };
 use rand::Rng;
-use ratatui::{buffer::Buffer, style::Color, style::Style};
+use ratatui::{buffer::Buffer, style::Style};
 
 #[derive(Clone, Copy, Debug)]
 pub struct FoodConfig {
     pub speed_multiplier: i16,
-    pub color: Color,
 }
 
 impl FoodConfig {
     pub const fn new() -> Self {
         Self {
             speed_multiplier: 1,
-            color: Color::Rgb(138, 43, 226),
         }
     }
 
-    #[allow(dead_code)]
     pub const fn with_speed(mut self, multiplier: i16) -> Self {
         self.speed_multiplier = multiplier;
         self
     }
-
-    #[allow(dead_code)]
-    pub const fn with_color(mut self, color: Color) -> Self {
-        self.color = color;
-        self
-    }
 }
 
 impl Default for FoodConfig {
@@ -42,6 +34,7 @@ pub struct Food {
     position: Vec2,
     velocity: Vec2,
     active: bool,
+    age: u32,
     config: FoodConfig,
 }
 
@@ -55,12 +48,26 @@ impl Food {
             position,
             velocity: Vec2::zero(),
             active: true,
+            age: 0,
             config,
         }
     }
 
-    fn compute_spawn_position(edge: Edge, bounds: Vec2) -> Vec2 {
-        let mut rng = rand::rng();
+    pub fn config(&self) -> FoodConfig {
+        self.config
+    }
+
+    // Takes effect from the next respawn; food already in flight keeps
+    // its velocity.
+    pub fn set_config(&mut self, config: FoodConfig) {
+        self.config = config;
+    }
+
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn compute_spawn_position(edge: Edge, bounds: Vec2, rng: &mut impl Rng) -> Vec2 {
         match edge {
             Edge::Top => Vec2::new(rng.random_range(0..bounds.x), 0),
             Edge::Bottom => Vec2::new(rng.random_range(0..bounds.x), bounds.y - 1),
@@ -74,16 +81,54 @@ impl Food {
         base_velocity.to_vec2() * config.speed_multiplier
     }
 
+    pub fn spawn_with(edge: Edge, bounds: Vec2, config: FoodConfig, rng: &mut impl Rng) -> Self {
+        let position = Self::compute_spawn_position(edge, bounds, rng);
+        Self::launch(position, edge, config)
+    }
+
+    // Food at a chosen cell, heading away from `edge` as if it had spawned
+    // there.
+    pub fn launch(position: Vec2, edge: Edge, config: FoodConfig) -> Self {
+        Self {
+            position,
+            velocity: Self::compute_velocity(edge, &config),
+            active: true,
+            age: 0,
+            config,
+        }
+    }
+
     pub fn respawn_from_random_edge(&mut self, bounds: Vec2) {
-        let edge = Edge::ALL[rand::rng().random_range(0..4)];
-        self.position = Self::compute_spawn_position(edge, bounds);
+        self.respawn_with(bounds, &mut rand::rng());
+    }
+
+    pub fn respawn_with(&mut self, bounds: Vec2, rng: &mut impl Rng) {
+        let edge = Edge::ALL[rng.random_range(0..4)];
+        self.position = Self::compute_spawn_position(edge, bounds, rng);
         self.velocity = Self::compute_velocity(edge, &self.config);
         self.active = true;
+        self.age = 0;
     }
 
     pub fn is_out_of_bounds(&self, bounds: Vec2) -> bool {
         !self.position.in_bounds(bounds)
     }
+
+    pub fn tick_with(&mut self, bounds: Vec2, rng: &mut impl Rng) {
+        if !self.advance(bounds) {
+            self.respawn_with(bounds, rng);
+        }
+    }
+
+    // One step without the respawn; false once the food has left the arena.
+    pub fn advance(&mut self, bounds: Vec2) -> bool {
+        if !self.active {
+            return true;
+        }
+        self.age = self.age.saturating_add(1);
+        self.position = self.position + self.velocity;
+        !self.is_out_of_bounds(bounds)
+    }
 }
 
 impl Default for Food {
@@ -96,14 +141,7 @@ impl EdgeSpawnable for Food {
     type Config = FoodConfig;
 
     fn spawn_at_edge(edge: Edge, bounds: Vec2, config: Self::Config) -> Self {
-        let position = Self::compute_spawn_position(edge, bounds);
-        let velocity = Self::compute_velocity(edge, &config);
-        Self {
-            position,
-            velocity,
-            active: true,
-            config,
-        }
+        Self::spawn_with(edge, bounds, config, &mut rand::rng())
     }
 }
 
@@ -146,22 +184,27 @@ impl Targetable for Food {
 
 impl BoundedTickable for Food {
     fn tick(&mut self, bounds: Vec2) {
-        if !self.active {
-            return;
-        }
-        self.position = self.position + self.velocity;
-        if self.is_out_of_bounds(bounds) {
-            self.respawn_from_random_edge(bounds);
-        }
+        self.tick_with(bounds, &mut rand::rng());
+    }
+}
+
+impl Layered for Food {
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Items
     }
 }
 
 impl Renderable for Food {
-    fn render(&self, offset: Vec2, buf: &mut Buffer) {
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
         if !self.active {
             return;
         }
-        let (x, y) = self.position.to_screen(offset);
-        buf.set_string(x, y, "▓▓", Style::default().fg(self.config.color));
+        let (x, y) = self.position.to_screen(ctx.offset);
+        buf.set_string(
+            x,
+            y,
+            ctx.glyphs.food(),
+            Style::default().fg(ctx.theme.food()),
+        );
     }
 }
@@ -1,7 +1,11 @@
 mod bullet;
 mod food;
 mod snake;
+mod walls;
+mod world;
 
 pub use bullet::Bullet;
-pub use food::Food;
+pub use food::{Food, FoodConfig};
 pub use snake::{DamageResult, Snake, SnakeMoveResult};
+pub use walls::Walls;
+pub use world::{Entity, EntityId, World};
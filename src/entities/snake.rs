@@ -1,25 +1,64 @@
 use crate::core::{Direction, SegmentIndex, Vec2};
-use crate::traits::{Damageable, Positioned, Renderable, Segmented};
-use ratatui::{buffer::Buffer, style::Color, style::Style};
-use std::collections::VecDeque;
+use crate::renderer::{RenderContext, RenderLayer};
+use crate::traits::{Damageable, Layered, Positioned, Renderable, Segmented};
+use ratatui::{buffer::Buffer, style::Style};
+use std::collections::{vec_deque, VecDeque};
 
 pub struct Snake {
     segments: VecDeque<Vec2>,
     direction: Direction,
-    pending_direction: Direction,
+    // Turns waiting to be taken, one per step, so quick key combos made
+    // between steps aren't lost.
+    turns: VecDeque<Direction>,
+    turn_buffer: usize,
     grow_pending: u32,
 }
 
 impl Snake {
+    pub const DEFAULT_TURN_BUFFER: usize = 3;
+
     pub fn new(position: Vec2) -> Self {
         Self {
             segments: VecDeque::from([position]),
             direction: Direction::Right,
-            pending_direction: Direction::Right,
+            turns: VecDeque::new(),
+            turn_buffer: Self::DEFAULT_TURN_BUFFER,
+            grow_pending: 0,
+        }
+    }
+
+    // Lays the body out behind the head, away from the direction of travel.
+    pub fn with_body(head: Vec2, direction: Direction, length: usize) -> Self {
+        let behind = direction.opposite().to_vec2();
+        let mut segments = VecDeque::from([head]);
+        for _ in 1..length {
+            let tail = segments.back().copied().unwrap_or(head);
+            segments.push_back(tail + behind);
+        }
+        Self {
+            segments,
+            direction,
+            turns: VecDeque::new(),
+            turn_buffer: Self::DEFAULT_TURN_BUFFER,
             grow_pending: 0,
         }
     }
 
+    pub fn with_turn_buffer(mut self, depth: usize) -> Self {
+        self.set_turn_buffer(depth);
+        self
+    }
+
+    // At least one turn is always kept, otherwise steering would do nothing.
+    pub fn set_turn_buffer(&mut self, depth: usize) {
+        self.turn_buffer = depth.max(1);
+        self.turns.truncate(self.turn_buffer);
+    }
+
+    pub fn turn_buffer(&self) -> usize {
+        self.turn_buffer
+    }
+
     pub fn head(&self) -> Vec2 {
         self.segments.front().copied().unwrap_or_default()
     }
@@ -39,10 +78,40 @@ impl Snake {
         self.direction
     }
 
+    // The direction the next step will take.
+    pub fn pending_direction(&self) -> Direction {
+        self.turns.front().copied().unwrap_or(self.direction)
+    }
+
+    pub fn queued_turns(&self) -> impl Iterator<Item = Direction> + '_ {
+        self.turns.iter().copied()
+    }
+
+    // Each turn is checked against the one queued before it, so Up then
+    // Left from heading Right is two valid turns, not a reversal. With the
+    // buffer full the newest turn replaces the last queued one.
     pub fn set_direction(&mut self, dir: Direction) {
-        if !self.direction.is_opposite(dir) {
-            self.pending_direction = dir;
+        let full = self.turns.len() >= self.turn_buffer;
+        let kept = if full {
+            self.turns.len() - 1
+        } else {
+            self.turns.len()
+        };
+        let previous = kept
+            .checked_sub(1)
+            .and_then(|i| self.turns.get(i))
+            .copied()
+            .unwrap_or(self.direction);
+        if previous == dir || previous.is_opposite(dir) {
+            return;
         }
+        self.turns.truncate(kept);
+        self.turns.push_back(dir);
+    }
+
+    // Forgets the next queued turn, for a step that was refused.
+    pub fn discard_turn(&mut self) {
+        self.turns.pop_front();
     }
 
     pub fn grow(&mut self) {
@@ -50,14 +119,25 @@ impl Snake {
     }
 
     pub fn tick(&mut self, bounds: Vec2) -> SnakeMoveResult {
-        self.direction = self.pending_direction;
-        let new_head = self.head() + self.direction.to_vec2();
+        self.tick_with(bounds, false, true)
+    }
+
+    // `wrap` carries the head across the arena edge instead of hitting it;
+    // without `solid_body` the snake can pass through itself.
+    pub fn tick_with(&mut self, bounds: Vec2, wrap: bool, solid_body: bool) -> SnakeMoveResult {
+        if let Some(turn) = self.turns.pop_front() {
+            self.direction = turn;
+        }
+        let mut new_head = self.head() + self.direction.to_vec2();
+        if wrap {
+            new_head = new_head.wrapped(bounds);
+        }
 
         if !new_head.in_bounds(bounds) {
             return SnakeMoveResult::HitWall;
         }
 
-        if self.segments.iter().any(|&s| s == new_head) {
+        if solid_body && self.segments.iter().any(|&s| s == new_head) {
             return SnakeMoveResult::HitSelf;
         }
 
@@ -72,6 +152,22 @@ impl Snake {
         SnakeMoveResult::Moved(new_head)
     }
 
+    // Drops up to `count` segments from the tail, never the head.
+    pub fn shed(&mut self, count: usize) -> usize {
+        let keep = self.segments.len().saturating_sub(count);
+        self.cut(keep).count()
+    }
+
+    // Keeps the first `keep` segments, never fewer than the head, and hands
+    // back the cells the rest were on. Growth still owed is forfeit.
+    pub fn cut(&mut self, keep: usize) -> vec_deque::Drain<'_, Vec2> {
+        let keep = keep.max(1).min(self.segments.len());
+        if keep < self.segments.len() {
+            self.grow_pending = 0;
+        }
+        self.segments.drain(keep..)
+    }
+
     pub fn contains(&self, pos: Vec2) -> bool {
         self.segments.iter().any(|&s| s == pos)
     }
@@ -85,9 +181,7 @@ impl Snake {
     }
 
     fn truncate_to_index(&mut self, index: SegmentIndex) -> DamageResult {
-        let segments_lost = self.segments.len().saturating_sub(index.as_usize() + 1);
-        self.segments.truncate(index.as_usize() + 1);
-        self.grow_pending = 0;
+        let segments_lost = self.cut(index.as_usize() + 1).count();
         DamageResult { segments_lost }
     }
 }
@@ -140,15 +234,42 @@ impl Damageable for Snake {
     }
 }
 
+impl Layered for Snake {
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Bodies
+    }
+}
+
 impl Renderable for Snake {
-    fn render(&self, offset: Vec2, buf: &mut Buffer) {
-        let head_style = Style::default().fg(Color::Rgb(200, 255, 200));
-        let body_style = Style::default().fg(Color::White);
-
-        for (i, segment) in self.segments.iter().enumerate() {
-            let (x, y) = segment.to_screen(offset);
-            let style = if i == 0 { head_style } else { body_style };
-            buf.set_string(x, y, "▓▓", style);
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
+        let head_style = Style::default().fg(ctx.theme.snake_head());
+        let body_style = Style::default().fg(ctx.theme.snake_body());
+
+        let neighbour = |from: Vec2, index: Option<usize>| {
+            index
+                .and_then(|i| self.segments.get(i))
+                .and_then(|&n| Direction::from_vec2((n - from).wrapped_step(ctx.bounds)))
+        };
+
+        for (i, &segment) in self.segments.iter().enumerate() {
+            let toward_head = neighbour(segment, i.checked_sub(1));
+            let toward_tail = neighbour(segment, Some(i + 1));
+
+            let (glyph, style) = if i == 0 {
+                (
+                    ctx.glyphs.snake_head(self.direction, toward_tail),
+                    head_style,
+                )
+            } else {
+                (
+                    ctx.glyphs.snake_segment(toward_head, toward_tail),
+                    body_style,
+                )
+            };
+
+            let (x, y) = segment.to_screen(ctx.offset);
+            buf.set_string(x, y, glyph[0], style);
+            buf.set_string(x + 1, y, glyph[1], style);
         }
     }
 }
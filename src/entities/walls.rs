@@ -0,0 +1,53 @@
+use crate::core::Vec2;
+use crate::renderer::{RenderContext, RenderLayer};
+use crate::traits::{Layered, Renderable};
+use ratatui::{buffer::Buffer, style::Style};
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Walls {
+    cells: HashSet<Vec2>,
+}
+
+impl Walls {
+    pub fn new(cells: impl IntoIterator<Item = Vec2>) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+        }
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        self.cells.contains(&pos)
+    }
+
+    pub fn cells(&self) -> &HashSet<Vec2> {
+        &self.cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+impl Layered for Walls {
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Terrain
+    }
+}
+
+impl Renderable for Walls {
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
+        let style = Style::default().fg(ctx.theme.border());
+        for &cell in &self.cells {
+            if !cell.in_bounds(ctx.bounds) {
+                continue;
+            }
+            let (x, y) = cell.to_screen(ctx.offset);
+            buf.set_string(x, y, ctx.glyphs.wall(), style);
+        }
+    }
+}
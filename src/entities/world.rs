@@ -0,0 +1,223 @@
+use crate::core::Vec2;
+use crate::entities::{Food, Snake};
+use crate::systems::{BulletPool, Scope};
+use crate::traits::{Active, BoundedTickable, Collidable, Drawable, GameEntity};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Ids are handed out in order and never reused, so a stale id can't point
+// at a newer entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntityId(u32);
+
+impl EntityId {
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+// Anything with the whole entity trait family can live in the world.
+pub trait Entity: GameEntity + Drawable + BoundedTickable + Active + Collidable + Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: GameEntity + Drawable + BoundedTickable + Active + Collidable + Any> Entity for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Everything on the board. The snakes, food, bullets and scope are kept by
+// type, since the game's rules ask for them by name; anything else is
+// spawned by id and handled through the entity traits alone. `iter`, `len`
+// and `tick` cover the spawned entities, in id order, which is also spawn
+// order.
+#[derive(Default)]
+pub struct World {
+    snakes: Vec<Snake>,
+    food: Food,
+    bullets: BulletPool,
+    scope: Scope,
+    entities: BTreeMap<EntityId, Box<dyn Entity>>,
+    next_id: u32,
+    // Whether a spawned entity may have appeared, moved or gone since
+    // `take_changed` last asked.
+    changed: bool,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_core(snakes: Vec<Snake>, bullets: BulletPool, scope: Scope) -> Self {
+        Self {
+            snakes,
+            bullets,
+            scope,
+            ..Self::default()
+        }
+    }
+
+    // Snake numbers are indices here, the player's first.
+    pub fn snakes(&self) -> &[Snake] {
+        &self.snakes
+    }
+
+    pub fn snake_mut(&mut self, id: usize) -> Option<&mut Snake> {
+        self.snakes.get_mut(id)
+    }
+
+    pub fn food(&self) -> &Food {
+        &self.food
+    }
+
+    pub fn food_mut(&mut self) -> &mut Food {
+        &mut self.food
+    }
+
+    pub fn bullets(&self) -> &BulletPool {
+        &self.bullets
+    }
+
+    pub fn bullets_mut(&mut self) -> &mut BulletPool {
+        &mut self.bullets
+    }
+
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    pub fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+
+    // Everything in draw order, back to front. `shown` picks which snakes
+    // are drawn, and `food` whether the food is.
+    pub fn drawables(&self, shown: impl Fn(usize) -> bool, food: bool) -> Vec<&dyn Drawable> {
+        let mut drawables: Vec<&dyn Drawable> = vec![&self.scope, &self.bullets];
+        drawables.extend(
+            self.snakes
+                .iter()
+                .enumerate()
+                .filter(|&(id, _)| shown(id))
+                .map(|(_, snake)| snake as &dyn Drawable),
+        );
+        if food {
+            drawables.push(&self.food);
+        }
+        drawables.extend(self.iter().map(|(_, e)| e as &dyn Drawable));
+        drawables.sort_by_key(|d| d.layer());
+        drawables
+    }
+
+    pub fn spawn(&mut self, entity: impl Entity) -> EntityId {
+        self.spawn_boxed(Box::new(entity))
+    }
+
+    pub fn spawn_boxed(&mut self, entity: Box<dyn Entity>) -> EntityId {
+        let id = EntityId(self.next_id);
+        self.next_id += 1;
+        self.entities.insert(id, entity);
+        self.changed = true;
+        id
+    }
+
+    pub fn despawn(&mut self, id: EntityId) -> Option<Box<dyn Entity>> {
+        self.changed = true;
+        self.entities.remove(&id)
+    }
+
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.entities.contains_key(&id)
+    }
+
+    pub fn get(&self, id: EntityId) -> Option<&dyn Entity> {
+        self.entities.get(&id).map(|e| e.as_ref())
+    }
+
+    // Handing out a mutable entity counts as a change, since the caller may
+    // move it.
+    pub fn get_mut(&mut self, id: EntityId) -> Option<&mut dyn Entity> {
+        self.changed = true;
+        match self.entities.get_mut(&id) {
+            Some(e) => Some(e.as_mut()),
+            None => None,
+        }
+    }
+
+    pub fn get_as<T: Entity>(&self, id: EntityId) -> Option<&T> {
+        self.get(id).and_then(|e| e.as_any().downcast_ref())
+    }
+
+    pub fn get_as_mut<T: Entity>(&mut self, id: EntityId) -> Option<&mut T> {
+        self.get_mut(id).and_then(|e| e.as_any_mut().downcast_mut())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, &dyn Entity)> {
+        self.entities.iter().map(|(&id, e)| (id, e.as_ref()))
+    }
+
+    pub fn of_type<T: Entity>(&self) -> impl Iterator<Item = (EntityId, &T)> {
+        self.entities
+            .iter()
+            .filter_map(|(&id, e)| e.as_any().downcast_ref().map(|t| (id, t)))
+    }
+
+    // The oldest active entity on the cell.
+    pub fn at(&self, pos: Vec2) -> Option<EntityId> {
+        self.iter()
+            .find(|(_, e)| e.is_active() && e.occupies(pos))
+            .map(|(id, _)| id)
+    }
+
+    // Ticks everything, then drops whatever went inactive and returns
+    // their ids.
+    pub fn tick(&mut self, bounds: Vec2) -> Vec<EntityId> {
+        self.changed |= !self.entities.is_empty();
+        for entity in self.entities.values_mut() {
+            entity.tick(bounds);
+        }
+        let expired: Vec<EntityId> = self
+            .entities
+            .iter()
+            .filter(|(_, e)| !e.is_active())
+            .map(|(&id, _)| id)
+            .collect();
+        for id in &expired {
+            self.entities.remove(id);
+        }
+        expired
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.changed |= !self.entities.is_empty();
+        self.entities.clear();
+    }
+
+    // Reports and resets whether the spawned entities changed, so their
+    // grid marks are only rebuilt when they could be stale.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+}
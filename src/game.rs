@@ -1,23 +1,68 @@
-use crate::core::{Bounds, Direction, Edge, GameState};
-use crate::entities::{Food, Snake, SnakeMoveResult};
-use crate::systems::{BulletPool, DeathCause, EventQueue, GameEvent, Scope};
-use crate::traits::{BoundedTickable, Damageable, EdgeSpawnable, Positioned, Segmented};
+use crate::core::{Bounds, Direction, Edge, GameState, Vec2};
+use crate::entities::{EntityId, Food, FoodConfig, Snake, SnakeMoveResult, Walls, World};
+use crate::rules::GameRule;
+use crate::systems::{
+    BulletPool, Collision, CollisionKind, CollisionWorld, DeathCause, Difficulty, EventQueue,
+    GameEvent, GameMode, LevelCurve, Objective, OccupancyGrid, RunTally, Scope, ScopeConfig,
+    ScoreOutcome, Scoring, ScoringConfig, SpawnCandidate, SpawnContext, SpawnError, SpawnRules,
+    Spawner, Sweep, SUBSTEPS,
+};
+use crate::traits::{Active, Drawable, Moveable, Positioned};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::time::Duration;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnakeStart {
+    pub position: Vec2,
+    pub direction: Direction,
+    pub length: usize,
+}
+
+#[derive(Clone, Debug)]
 pub struct GameConfig {
     pub bounds: Bounds,
+    pub mode: GameMode,
     pub bullet_pool_capacity: usize,
     pub event_queue_capacity: usize,
     pub bullet_cooldown_ticks: u8,
+    pub scope: ScopeConfig,
+    pub scoring: ScoringConfig,
+    pub levels: LevelCurve,
+    pub walls: Vec<Vec2>,
+    pub start: Option<SnakeStart>,
+    // Extra snakes for bot arenas. The player's snake is number 0 and the
+    // rivals follow in order.
+    pub rivals: Vec<SnakeStart>,
+    pub objective: Option<Objective>,
+    pub tick_duration: Duration,
+    pub turn_buffer: usize,
+    pub spawn: SpawnRules,
+    // Fixes every random choice the game makes; `None` seeds from the OS.
+    pub seed: Option<u64>,
 }
 
 impl GameConfig {
     pub fn new(width: i16, height: i16) -> Self {
         Self {
             bounds: Bounds::new(width, height),
+            mode: GameMode::default(),
             bullet_pool_capacity: 16,
             event_queue_capacity: 32,
             bullet_cooldown_ticks: 3,
+            scope: ScopeConfig::default(),
+            scoring: ScoringConfig::default(),
+            levels: LevelCurve::default(),
+            walls: Vec::new(),
+            start: None,
+            rivals: Vec::new(),
+            objective: None,
+            tick_duration: Duration::from_millis(70),
+            turn_buffer: Snake::DEFAULT_TURN_BUFFER,
+            spawn: SpawnRules::default(),
+            seed: None,
         }
     }
 }
@@ -28,6 +73,7 @@ pub struct Configured;
 
 pub struct GameBuilder<State> {
     config: GameConfig,
+    rules: Vec<Box<dyn GameRule>>,
     _state: PhantomData<State>,
 }
 
@@ -35,6 +81,7 @@ impl GameBuilder<Uninitialized> {
     pub fn new() -> Self {
         Self {
             config: GameConfig::new(0, 0),
+            rules: Vec::new(),
             _state: PhantomData,
         }
     }
@@ -42,33 +89,98 @@ impl GameBuilder<Uninitialized> {
     pub fn with_bounds(self, width: i16, height: i16) -> GameBuilder<WithBounds> {
         GameBuilder {
             config: GameConfig::new(width, height),
+            rules: self.rules,
             _state: PhantomData,
         }
     }
 }
 
 impl GameBuilder<WithBounds> {
-    #[allow(dead_code)]
     pub fn with_bullet_capacity(mut self, capacity: usize) -> Self {
         self.config.bullet_pool_capacity = capacity;
         self
     }
 
-    #[allow(dead_code)]
     pub fn with_event_capacity(mut self, capacity: usize) -> Self {
         self.config.event_queue_capacity = capacity;
         self
     }
 
-    #[allow(dead_code)]
     pub fn with_bullet_cooldown(mut self, ticks: u8) -> Self {
         self.config.bullet_cooldown_ticks = ticks;
         self
     }
 
+    pub fn with_scope(mut self, scope: ScopeConfig) -> Self {
+        self.config.scope = scope;
+        self
+    }
+
+    pub fn with_scoring(mut self, scoring: ScoringConfig) -> Self {
+        self.config.scoring = scoring;
+        self
+    }
+
+    pub fn with_levels(mut self, levels: LevelCurve) -> Self {
+        self.config.levels = levels;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: GameMode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    pub fn with_walls(mut self, walls: impl IntoIterator<Item = Vec2>) -> Self {
+        self.config.walls = walls.into_iter().collect();
+        self
+    }
+
+    pub fn with_snake_start(mut self, start: SnakeStart) -> Self {
+        self.config.start = Some(start);
+        self
+    }
+
+    pub fn with_rival(mut self, start: SnakeStart) -> Self {
+        self.config.rivals.push(start);
+        self
+    }
+
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.config.objective = Some(objective);
+        self
+    }
+
+    pub fn with_tick_duration(mut self, tick_duration: Duration) -> Self {
+        self.config.tick_duration = tick_duration;
+        self
+    }
+
+    pub fn with_turn_buffer(mut self, depth: usize) -> Self {
+        self.config.turn_buffer = depth;
+        self
+    }
+
+    pub fn with_spawn_rules(mut self, rules: SpawnRules) -> Self {
+        self.config.spawn = rules;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    // Rules run in the order they're added.
+    pub fn with_rule(mut self, rule: impl GameRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
     pub fn configure(self) -> GameBuilder<Configured> {
         GameBuilder {
             config: self.config,
+            rules: self.rules,
             _state: PhantomData,
         }
     }
@@ -80,7 +192,7 @@ impl GameBuilder<WithBounds> {
 
 impl GameBuilder<Configured> {
     pub fn build(self) -> Game {
-        Game::with_config(self.config)
+        Game::with_rules(self.config, self.rules)
     }
 }
 
@@ -90,17 +202,85 @@ impl Default for GameBuilder<Uninitialized> {
     }
 }
 
+// The player's snake: the one the single-snake API steers, the scope
+// follows and food spawns fairly for.
+pub const PLAYER: usize = 0;
+
+// Everything kept for one snake alone; the snake itself lives in the
+// world under the same number. Rivals play by the same rules as the player,
+// so they get the same bookkeeping.
+pub struct Player {
+    scoring: Scoring,
+    collected: u32,
+    bullet_kills: u32,
+    // When food was last collected, for survival's hunger.
+    last_meal: Duration,
+    bullet_cooldown: u8,
+    aim_target: Option<Vec2>,
+    // When it went out. Out snakes are off the board but keep their place
+    // in the list, so snake numbers never shift, and survival stops
+    // counting their time.
+    out_at: Option<Duration>,
+    // Why it's out; `None` for a snake that was withdrawn.
+    death: Option<DeathCause>,
+    // Steps asked for through `queue_step`, taken one per tick.
+    steps_owed: usize,
+}
+
+impl Player {
+    fn new(scoring: ScoringConfig) -> Self {
+        Self {
+            scoring: Scoring::new(scoring),
+            collected: 0,
+            bullet_kills: 0,
+            last_meal: Duration::ZERO,
+            bullet_cooldown: 0,
+            aim_target: None,
+            out_at: None,
+            death: None,
+            steps_owed: 0,
+        }
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    pub fn bullet_cooldown(&self) -> u8 {
+        self.bullet_cooldown
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.out_at.is_none()
+    }
+
+    pub fn death(&self) -> Option<DeathCause> {
+        self.death
+    }
+
+    fn aim_direction(&self, snake: &Snake) -> Direction {
+        self.aim_target
+            .and_then(|target| Direction::dominant(target - snake.head()))
+            .unwrap_or_else(|| snake.direction())
+    }
+}
+
 pub struct Game {
-    snake: Snake,
-    food: Food,
-    bullets: BulletPool,
-    scope: Scope,
+    players: Vec<Player>,
     events: EventQueue,
     state: GameState,
-    score: u32,
+    level: u32,
+    walls: Walls,
+    elapsed: Duration,
     bounds: Bounds,
-    bullet_cooldown: u8,
     bullet_cooldown_max: u8,
+    ticks: u64,
+    collisions: Vec<Collision>,
+    occupancy: OccupancyGrid,
+    world: World,
+    rules: Vec<Box<dyn GameRule>>,
+    rng: StdRng,
+    config: GameConfig,
 }
 
 impl Game {
@@ -109,21 +289,58 @@ impl Game {
     }
 
     pub fn with_config(config: GameConfig) -> Self {
-        let snake_pos = config.bounds.center();
+        Self::with_rules(config, Vec::new())
+    }
+
+    // Rules are in place before the first food spawns, so they see it.
+    fn with_rules(config: GameConfig, rules: Vec<Box<dyn GameRule>>) -> Self {
+        let snake = |start: Option<SnakeStart>| {
+            match start {
+                Some(start) => Snake::with_body(start.position, start.direction, start.length),
+                None => Snake::new(config.bounds.center()),
+            }
+            .with_turn_buffer(config.turn_buffer)
+        };
+        let snakes: Vec<Snake> = std::iter::once(config.start)
+            .chain(config.rivals.iter().copied().map(Some))
+            .map(snake)
+            .collect();
+        let players = snakes
+            .iter()
+            .map(|_| Player::new(config.scoring.clone()))
+            .collect();
+        let walls = Walls::new(config.walls.iter().copied());
+        let mut occupancy = OccupancyGrid::build(config.bounds.to_vec2(), &walls, &snakes[PLAYER]);
+        for (id, rival) in snakes.iter().enumerate().skip(1) {
+            occupancy.add_body(id, rival);
+        }
+        let world = World::with_core(
+            snakes,
+            BulletPool::new(config.bullet_pool_capacity),
+            Scope::with_config(config.scope),
+        );
 
         let mut game = Self {
-            snake: Snake::new(snake_pos),
-            food: Food::default(),
-            bullets: BulletPool::new(config.bullet_pool_capacity),
-            scope: Scope::new(),
+            players,
             events: EventQueue::new(config.event_queue_capacity),
             state: GameState::Playing,
-            score: 0,
+            level: 1,
+            walls,
+            elapsed: Duration::ZERO,
             bounds: config.bounds,
-            bullet_cooldown: 0,
             bullet_cooldown_max: config.bullet_cooldown_ticks,
+            ticks: 0,
+            collisions: Vec::new(),
+            occupancy,
+            world,
+            rules,
+            rng: config
+                .seed
+                .map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64),
+            config,
         };
 
+        game.apply_difficulty();
         game.spawn_food();
         game.update_scope();
         game
@@ -138,127 +355,644 @@ impl Game {
     }
 
     pub fn score(&self) -> u32 {
-        self.score
+        self.score_of(PLAYER)
+    }
+
+    pub fn score_of(&self, id: usize) -> u32 {
+        self.players.get(id).map_or(0, |player| {
+            let elapsed = player.out_at.unwrap_or(self.elapsed);
+            self.config.mode.score(player.scoring.score(), elapsed)
+        })
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.config.mode
+    }
+
+    pub fn time_left(&self) -> Option<Duration> {
+        self.config
+            .mode
+            .time_limit()
+            .map(|limit| limit.saturating_sub(self.elapsed))
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.players[PLAYER].scoring
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.config.levels.difficulty(self.level)
+    }
+
+    pub fn walls(&self) -> &Walls {
+        &self.walls
+    }
+
+    pub fn objective(&self) -> Option<Objective> {
+        self.config.objective
+    }
+
+    pub fn tally(&self) -> RunTally {
+        let player = &self.players[PLAYER];
+        RunTally {
+            collected: player.collected,
+            bullet_kills: player.bullet_kills,
+            elapsed: self.elapsed,
+            length: self.snake().length(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    // The wall-clock length of one tick at the current level.
+    pub fn tick_duration(&self) -> Duration {
+        self.config.tick_duration * self.difficulty().tick_percent as u32 / 100
+    }
+
+    pub fn set_tick_duration(&mut self, tick_duration: Duration) {
+        self.config.tick_duration = tick_duration;
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
     }
 
+    // The fairness rules are a preference: when no edge cell passes them
+    // they're dropped, and when every edge is taken the food appears on the
+    // free cell nearest the centre. Only a full board stops the spawn.
     fn spawn_food(&mut self) {
-        use rand::Rng;
-        loop {
-            let edge = Edge::ALL[rand::rng().random_range(0..4)];
-            let food = Food::spawn_at_edge(edge, self.bounds.to_vec2(), Default::default());
-            if !self.snake.contains_position(food.position()) {
-                self.food = food;
-                break;
+        let config = self.world.food().config();
+        self.occupancy.set_food(None);
+        let snake = &self.world.snakes()[PLAYER];
+        let ctx = SpawnContext {
+            grid: &self.occupancy,
+            head: snake.head(),
+            heading: snake.direction(),
+            food_speed: config.speed_multiplier,
+        };
+        let fallback_edge = Edge::ALL[self.rng.random_range(0..Edge::ALL.len())];
+        let chosen = Spawner::new(self.config.spawn)
+            .choose(&ctx, &mut self.rng)
+            .or_else(|_| Spawner::new(SpawnRules::none()).choose(&ctx, &mut self.rng))
+            .or_else(|err| match err {
+                SpawnError::NoFairCell => ctx
+                    .grid
+                    .nearest_free(self.bounds.center())
+                    .map(|position| SpawnCandidate {
+                        position,
+                        edge: fallback_edge,
+                    })
+                    .ok_or(SpawnError::BoardFull),
+                SpawnError::BoardFull => Err(err),
+            });
+
+        match chosen {
+            Ok(spot) => {
+                *self.world.food_mut() = Food::launch(spot.position, spot.edge, config);
+                self.occupancy.set_food(Some(spot.position));
+                self.run_rules(|rule, game| rule.on_spawn(game));
+            }
+            Err(_) => {
+                self.world.food_mut().deactivate();
+                if self.state.is_active() {
+                    self.state = GameState::Won;
+                    self.emit(GameEvent::BoardFilled);
+                }
             }
         }
     }
 
     fn update_scope(&mut self) {
-        self.scope.update(
-            self.snake.head(),
-            self.snake.direction(),
-            self.food.position(),
-            self.bounds.to_vec2(),
+        let (head, aim, food) = (
+            self.snake().head(),
+            self.aim_direction(),
+            self.world.food().position(),
         );
+        let bounds = self.bounds.to_vec2();
+        self.world.scope_mut().update(head, aim, food, bounds);
     }
 
     pub fn tick(&mut self) {
         if !self.state.is_active() {
             return;
         }
+        self.take_queued_steps();
+        if !self.state.is_active() {
+            return;
+        }
 
-        self.bullet_cooldown = self.bullet_cooldown.saturating_sub(1);
+        self.ticks += 1;
+        self.elapsed += self.tick_duration();
+        self.sync_world();
+        self.events.set_tick(self.ticks);
+        for player in &mut self.players {
+            player.bullet_cooldown = player.bullet_cooldown.saturating_sub(1);
+        }
 
-        self.food.tick(self.bounds.to_vec2());
-        self.check_food_snake_collision();
-        self.check_bullet_food_collisions();
-        self.bullets.tick(self.bounds.to_vec2());
+        // Every path is swept before anything moves, so outcomes don't depend
+        // on which entity happens to tick first.
+        let bounds = self.bounds.to_vec2();
+        let food = self.world.food();
+        let food = Sweep::new(food.position(), food.velocity());
+        let bullets: Vec<_> = self
+            .world
+            .bullets()
+            .indexed()
+            .map(|(index, b)| (index, Sweep::new(b.position(), b.velocity())))
+            .collect();
+        let collisions = CollisionWorld::new(&self.occupancy).sweep(Some(food), &bullets);
+
+        self.collisions.clear();
+        let mut food_taken = false;
+        // Whose shots missed; each of them loses their combo once.
+        let mut missed = Vec::new();
+        for collision in collisions {
+            food_taken |= collision.kind.consumes_food();
+            if collision.kind == CollisionKind::BulletWall {
+                missed.extend(self.bullet_owner(collision.bullet));
+            }
+            self.record_collision(collision);
+            self.apply_collision(collision);
+        }
+
+        if !food_taken {
+            let lifetime = self.difficulty().food_lifetime;
+            let food = self.world.food_mut();
+            if !food.advance(bounds) || (lifetime > 0 && food.age() >= lifetime) {
+                self.spawn_food();
+            } else {
+                self.occupancy.set_food(Some(food.position()));
+            }
+        }
+        missed.extend(self.world.bullets_mut().tick(bounds));
+        self.tick_world(bounds);
+        missed.sort_unstable();
+        missed.dedup();
+        for id in missed {
+            if let Some(player) = self.players.get_mut(id) {
+                let outcome = player.scoring.shot_missed();
+                self.push_score_events(outcome);
+            }
+        }
         self.update_scope();
+        self.check_level_up();
+        self.check_objective();
+        self.check_hunger();
+        self.check_time_limit();
+        self.run_rules(|rule, game| rule.on_tick(game));
     }
 
-    fn check_food_snake_collision(&mut self) {
-        let food_pos = self.food.position();
-        if food_pos == self.snake.head() {
-            self.collect_food(false);
-        } else if let Some(damage) = self.snake.damage_at_position(food_pos) {
-            if damage.is_significant() {
-                self.events.push(GameEvent::SnakeDamaged {
-                    position: food_pos,
-                    segments_lost: damage.segments_lost,
+    fn record_collision(&mut self, collision: Collision) {
+        self.collisions.push(collision);
+        self.emit(GameEvent::Collision {
+            kind: collision.kind,
+            position: collision.position,
+        });
+        self.run_rules(|rule, game| rule.on_collision(game, &collision));
+    }
+
+    // Food is moved to where it was caught so events and effects fire at
+    // the point of impact rather than where the tick started.
+    fn apply_collision(&mut self, collision: Collision) {
+        let at = collision.position;
+        match collision.kind {
+            CollisionKind::BulletFood => {
+                let distance = collision
+                    .bullet
+                    .and_then(|index| self.world.bullets().get(index))
+                    .map_or(0, |b| b.origin().manhattan_distance(at));
+                let shooter = self.bullet_owner(collision.bullet).unwrap_or(PLAYER);
+                if let Some(index) = collision.bullet {
+                    self.world.bullets_mut().deactivate(index);
+                }
+                self.world.food_mut().set_position(at);
+                self.collect_food(shooter, true, distance);
+            }
+            CollisionKind::FoodHead => {
+                self.world.food_mut().set_position(at);
+                self.collect_food(collision.snake.unwrap_or(PLAYER), false, 0);
+            }
+            CollisionKind::FoodBody => self.food_hit_body(at),
+            CollisionKind::FoodWall => self.spawn_food(),
+            CollisionKind::BulletWall => {
+                if let Some(index) = collision.bullet {
+                    self.world.bullets_mut().deactivate(index);
+                }
+            }
+            CollisionKind::SnakeEntity => {
+                if let Some(entity) = collision.entity.and_then(|id| self.world.get_mut(id)) {
+                    entity.on_collision(collision.kind);
+                }
+                self.sync_world();
+            }
+            CollisionKind::SnakeWall
+            | CollisionKind::SnakeSelf
+            | CollisionKind::SnakeSnake
+            | CollisionKind::HeadOn => {}
+        }
+    }
+
+    fn bullet_owner(&self, bullet: Option<usize>) -> Option<usize> {
+        bullet
+            .and_then(|index| self.world.bullets().get(index))
+            .map(|b| b.owner())
+    }
+
+    // World entities move themselves, so the grid is re-marked after they
+    // do. One that steps onto a snake collides with it; one that was
+    // already there doesn't collide again.
+    fn tick_world(&mut self, bounds: Vec2) {
+        let before: HashMap<EntityId, Vec2> = self
+            .world
+            .iter()
+            .map(|(id, e)| (id, e.position()))
+            .collect();
+        self.world.tick(bounds);
+        self.sync_world();
+        let hits: Vec<Collision> = self
+            .world
+            .iter()
+            .filter(|(_, e)| e.is_active() && self.occupancy.has_snake(e.position()))
+            .filter(|(id, e)| before.get(id) != Some(&e.position()))
+            .filter_map(|(id, e)| {
+                let (snake, _) = self.occupancy.segment_at(e.position())?;
+                let collision = Collision::new(CollisionKind::SnakeEntity, e.position(), SUBSTEPS);
+                Some(collision.with_entity(id).with_snake(snake))
+            })
+            .collect();
+        for collision in hits {
+            self.record_collision(collision);
+            self.apply_collision(collision);
+        }
+    }
+
+    // Entities can be added through `world_mut` at any time, so the grid
+    // catches up before anything relies on it. The marks are only rebuilt
+    // when the world says an entity may have changed.
+    fn sync_world(&mut self) {
+        if !self.world.take_changed() {
+            return;
+        }
+        self.occupancy.set_entities(
+            self.world
+                .iter()
+                .filter(|(_, e)| e.is_active())
+                .map(|(_, e)| e.position()),
+        );
+    }
+
+    fn food_hit_body(&mut self, at: Vec2) {
+        // The grid says which segment was hit, and the cut hands back the
+        // cells it frees, so neither step walks the body.
+        let hit = self.occupancy.segment_at(at).filter(|(_, i)| i.is_body());
+        if let Some((id, index)) = hit {
+            let extra = self.config.mode.extra_cut(self.elapsed);
+            let keep = (index.as_usize() + 1).saturating_sub(extra);
+            let segments_lost = match self.world.snake_mut(id) {
+                Some(snake) => snake
+                    .cut(keep)
+                    .inspect(|&pos| self.occupancy.remove_snake(pos))
+                    .count(),
+                None => 0,
+            };
+            if segments_lost > 0 {
+                self.emit(GameEvent::SnakeDamaged {
+                    position: at,
+                    segments_lost,
                 });
+                if let Some(player) = self.players.get_mut(id) {
+                    let outcome = player.scoring.damaged(segments_lost);
+                    self.push_score_events(outcome);
+                }
             }
-            self.spawn_food();
         }
+        self.spawn_food();
     }
 
     pub fn move_snake(&mut self) {
+        self.move_snake_for(PLAYER);
+    }
+
+    // Steps snake `id` one cell towards its next turn. Snakes that are out
+    // stay put.
+    pub fn move_snake_for(&mut self, id: usize) {
         if !self.state.is_active() {
             return;
         }
+        let alive = self.players.get(id).is_some_and(|p| p.is_alive());
+        let Some(snake) = self.world.snakes().get(id).filter(|_| alive) else {
+            return;
+        };
 
-        match self.snake.tick(self.bounds.to_vec2()) {
-            SnakeMoveResult::Moved(pos) => {
-                if pos == self.food.position() {
-                    self.collect_food(false);
+        let mode = self.config.mode;
+        let bounds = self.bounds.to_vec2();
+        let length_before = snake.length();
+        let mut ahead = snake.head() + snake.pending_direction().to_vec2();
+        if mode.wraps_walls() {
+            ahead = ahead.wrapped(bounds);
+        }
+        let tail = snake.segments().back().copied();
+        self.sync_world();
+        let hit = CollisionWorld::new(&self.occupancy)
+            .snake_step(id, ahead, mode.has_death(), self.world.food().position())
+            .map(|c| match self.world.at(c.position) {
+                Some(entity) if c.kind == CollisionKind::SnakeEntity => c.with_entity(entity),
+                _ => c,
+            });
+        // Head-on, the snake stepped onto is out as well.
+        let struck = hit
+            .filter(|c| c.kind == CollisionKind::HeadOn)
+            .and_then(|c| self.occupancy.segment_at(c.position))
+            .map(|(owner, _)| owner);
+        if let Some(collision) = hit {
+            self.record_collision(collision);
+        }
+        let result = match hit.map(|c| c.kind) {
+            Some(CollisionKind::SnakeWall) => Err(DeathCause::HitWall),
+            Some(CollisionKind::SnakeSelf) => Err(DeathCause::HitSelf),
+            Some(CollisionKind::SnakeSnake | CollisionKind::HeadOn) => Err(DeathCause::HitSnake),
+            // The grid has already ruled out a bite, so the snake needn't
+            // scan its own body again.
+            _ => match self
+                .world
+                .snake_mut(id)
+                .map(|snake| snake.tick_with(bounds, mode.wraps_walls(), false))
+            {
+                Some(SnakeMoveResult::Moved(head)) => Ok(head),
+                Some(SnakeMoveResult::HitWall) | None => Err(DeathCause::HitWall),
+                Some(SnakeMoveResult::HitSelf) => Err(DeathCause::HitSelf),
+            },
+        };
+
+        match result {
+            Ok(head) => {
+                let length = self.world.snakes()[id].length();
+                if let Some(tail) = tail.filter(|_| length == length_before) {
+                    self.occupancy.remove_snake(tail);
+                }
+                self.occupancy.add_snake(id, head);
+                if length > length_before {
+                    self.emit(GameEvent::SnakeGrew { length });
                 }
+                if let Some(collision) = hit {
+                    self.apply_collision(collision);
+                }
+                self.run_rules(|rule, game| rule.on_snake_move(game, head));
             }
-            SnakeMoveResult::HitWall => {
-                self.state = GameState::Dead;
-                self.events.push(GameEvent::SnakeDied {
-                    cause: DeathCause::HitWall,
-                });
+            // Zen never lets a snake's own body block it, so only walls and
+            // other snakes get here. The step is refused and its turn
+            // dropped, so the next press is read fresh rather than running
+            // into the same wall again.
+            Err(_) if !mode.has_death() => {
+                if let Some(snake) = self.world.snake_mut(id) {
+                    snake.discard_turn();
+                }
+                self.emit(GameEvent::MoveBlocked { position: ahead });
             }
-            SnakeMoveResult::HitSelf => {
-                self.state = GameState::Dead;
-                self.events.push(GameEvent::SnakeDied {
-                    cause: DeathCause::HitSelf,
-                });
+            Err(cause) => {
+                self.kill(id, cause);
+                if let Some(struck) = struck {
+                    self.kill(struck, DeathCause::HitSnake);
+                }
             }
         }
 
         self.update_scope();
+        self.check_objective();
+    }
+
+    fn kill(&mut self, id: usize, cause: DeathCause) {
+        self.retire(id, Some(cause));
+    }
+
+    // Takes snake `id` out of the game without a death, as when the bot
+    // steering it breaks the rules. Its body leaves the board.
+    pub fn withdraw(&mut self, id: usize) {
+        self.retire(id, None);
     }
 
-    fn check_bullet_food_collisions(&mut self) {
-        let food_pos = self.food.position();
-        if self
-            .bullets
-            .check_collision_before_tick(food_pos, self.bounds.to_vec2())
-        {
-            self.collect_food(true);
+    // An out snake is cleared off the board, and the run is over once every
+    // snake is out.
+    fn retire(&mut self, id: usize, death: Option<DeathCause>) {
+        let Some(player) = self.players.get_mut(id).filter(|p| p.is_alive()) else {
+            return;
+        };
+        player.out_at = Some(self.elapsed);
+        player.death = death;
+        for &pos in self.world.snakes()[id].segments() {
+            self.occupancy.remove_snake(pos);
+        }
+        if self.players.iter().all(|p| !p.is_alive()) {
+            self.state = GameState::Dead;
+        }
+        if let Some(cause) = death {
+            self.emit(GameEvent::SnakeDied { cause });
         }
     }
 
-    fn collect_food(&mut self, by_bullet: bool) {
-        self.score += 1;
-        self.snake.grow();
-        self.events.push(GameEvent::FoodCollected {
-            position: self.food.position(),
-            by_bullet,
-        });
+    // A hungry snake loses its tail a segment at a time and starves once
+    // only the head is left.
+    fn check_hunger(&mut self) {
+        let Some(hunger) = self.config.mode.hunger() else {
+            return;
+        };
+        let elapsed = self.elapsed;
+        for id in 0..self.players.len() {
+            if !self.state.is_active() {
+                return;
+            }
+            let Some(player) = self
+                .players
+                .get_mut(id)
+                .filter(|p| p.is_alive() && elapsed.saturating_sub(p.last_meal) >= hunger)
+            else {
+                continue;
+            };
+            player.last_meal = elapsed;
+            let Some(snake) = self.world.snake_mut(id) else {
+                continue;
+            };
+            let keep = snake.length().saturating_sub(1);
+            let Some(tail) = snake.cut(keep).next() else {
+                self.kill(id, DeathCause::Starved);
+                continue;
+            };
+            self.occupancy.remove_snake(tail);
+            self.emit(GameEvent::SnakeDamaged {
+                position: tail,
+                segments_lost: 1,
+            });
+        }
+    }
+
+    fn check_time_limit(&mut self) {
+        if self.state.is_active() && self.time_left() == Some(Duration::ZERO) {
+            self.state = GameState::Won;
+            self.emit(GameEvent::TimeUp);
+        }
+    }
+
+    fn check_objective(&mut self) {
+        let Some(objective) = self.config.objective else {
+            return;
+        };
+        if self.state.is_active() && objective.is_met(&self.tally()) {
+            self.state = GameState::Won;
+            self.emit(GameEvent::ObjectiveCompleted);
+        }
+    }
+
+    fn collect_food(&mut self, id: usize, by_bullet: bool, shot_distance: i32) {
+        if let Some(player) = self.players.get_mut(id) {
+            let outcome = player.scoring.food_collected(by_bullet, shot_distance);
+            player.collected += 1;
+            player.last_meal = self.elapsed;
+            if by_bullet {
+                player.bullet_kills += 1;
+            }
+            if let Some(snake) = self.world.snake_mut(id) {
+                snake.grow();
+            }
+            self.emit(GameEvent::FoodCollected {
+                position: self.world.food().position(),
+                by_bullet,
+            });
+            self.push_score_events(outcome);
+            self.check_level_up();
+        }
         self.spawn_food();
     }
 
+    // Levels only go up: losing points to damage doesn't hand back an
+    // easier curve. With rivals on the board the best score sets the pace.
+    fn check_level_up(&mut self) {
+        let best = (0..self.players.len())
+            .map(|id| self.score_of(id))
+            .max()
+            .unwrap_or(0);
+        let level = self.config.levels.level_for_score(best);
+        if level <= self.level {
+            return;
+        }
+        self.level = level;
+        self.apply_difficulty();
+        self.emit(GameEvent::LevelUp { level });
+    }
+
+    fn apply_difficulty(&mut self) {
+        let difficulty = self.difficulty();
+        self.world
+            .food_mut()
+            .set_config(FoodConfig::new().with_speed(difficulty.food_speed));
+        self.bullet_cooldown_max = difficulty.bullet_cooldown(self.config.bullet_cooldown_ticks);
+    }
+
+    fn push_score_events(&mut self, outcome: ScoreOutcome) {
+        if outcome.combo_started {
+            self.emit(GameEvent::ComboStarted {
+                multiplier: outcome.multiplier,
+            });
+        }
+        if let Some(streak) = outcome.combo_broken {
+            self.emit(GameEvent::ComboBroken { streak });
+        }
+    }
+
     pub fn set_direction(&mut self, dir: Direction) {
-        self.snake.set_direction(dir);
+        self.set_direction_for(PLAYER, dir);
+    }
+
+    pub fn set_direction_for(&mut self, id: usize, dir: Direction) {
+        if let Some(player) = self.players.get_mut(id) {
+            player.aim_target = None;
+        }
+        if let Some(snake) = self.world.snake_mut(id) {
+            snake.set_direction(dir);
+        }
+    }
+
+    // Steers and asks for a step, which the next tick takes. Presses that
+    // come quicker than the tick each queue a turn, up to the turn buffer.
+    pub fn queue_step(&mut self, dir: Direction) {
+        self.queue_step_for(PLAYER, dir);
+    }
+
+    pub fn queue_step_for(&mut self, id: usize, dir: Direction) {
+        self.set_direction_for(id, dir);
+        if !self.state.is_active() {
+            return;
+        }
+        let buffer = self.world.snakes().get(id).map_or(1, |s| s.turn_buffer());
+        if let Some(player) = self.players.get_mut(id) {
+            player.steps_owed = (player.steps_owed + 1).min(buffer);
+        }
+    }
+
+    fn take_queued_steps(&mut self) {
+        for id in 0..self.players.len() {
+            if self.players[id].steps_owed > 0 {
+                self.players[id].steps_owed -= 1;
+                self.move_snake_for(id);
+            }
+        }
+    }
+
+    // Points the gun and scope at a cell until the next steer; the aim
+    // follows the head as it moves, and a target on the head means "ahead".
+    pub fn aim_at(&mut self, target: Vec2) {
+        self.aim_at_for(PLAYER, target);
+    }
+
+    pub fn aim_at_for(&mut self, id: usize, target: Vec2) {
+        if let Some(player) = self.players.get_mut(id) {
+            player.aim_target = Some(target);
+        }
+        self.update_scope();
+    }
+
+    pub fn aim_target(&self) -> Option<Vec2> {
+        self.players[PLAYER].aim_target
+    }
+
+    pub fn aim_direction(&self) -> Direction {
+        self.players[PLAYER].aim_direction(self.snake())
     }
 
     pub fn fire(&mut self) -> bool {
-        if !self.state.is_active() || self.bullet_cooldown > 0 {
+        self.fire_for(PLAYER)
+    }
+
+    pub fn fire_for(&mut self, id: usize) -> bool {
+        let (Some(player), Some(snake)) = (self.players.get(id), self.world.snakes().get(id))
+        else {
+            return false;
+        };
+        if !self.state.is_active() || !player.is_alive() || player.bullet_cooldown > 0 {
             return false;
         }
 
-        let head = self.snake.head();
-        let dir = self.snake.direction();
-        let spawn_pos = head + dir.to_vec2();
+        let dir = player.aim_direction(snake);
+        let spawn_pos = snake.head() + dir.to_vec2();
 
         if !spawn_pos.in_bounds(self.bounds.to_vec2()) {
             return false;
         }
 
-        if self.bullets.spawn(spawn_pos, dir) {
-            self.bullet_cooldown = self.bullet_cooldown_max;
-            self.events.push(GameEvent::BulletFired {
+        if self.world.bullets_mut().spawn_for(id, spawn_pos, dir) {
+            if let Some(player) = self.players.get_mut(id) {
+                player.bullet_cooldown = self.bullet_cooldown_max;
+            }
+            self.emit(GameEvent::BulletFired {
                 position: spawn_pos,
                 direction: dir,
             });
@@ -268,59 +1002,140 @@ impl Game {
         false
     }
 
-    #[allow(dead_code)]
+    pub fn bullet_cooldown(&self) -> u8 {
+        self.players[PLAYER].bullet_cooldown
+    }
+
+    pub fn bullet_cooldown_max(&self) -> u8 {
+        self.bullet_cooldown_max
+    }
+
     pub fn can_fire(&self) -> bool {
-        self.state.is_active() && self.bullet_cooldown == 0
+        self.state.is_active() && self.bullet_cooldown() == 0
     }
 
     pub fn toggle_pause(&mut self) {
-        self.state = match self.state {
-            GameState::Playing => {
-                self.events.push(GameEvent::GamePaused);
-                GameState::Paused
-            }
-            GameState::Paused => {
-                self.events.push(GameEvent::GameResumed);
-                GameState::Playing
-            }
-            GameState::Dead => GameState::Dead,
+        let (state, event) = match self.state {
+            GameState::Playing => (GameState::Paused, GameEvent::GamePaused),
+            GameState::Paused => (GameState::Playing, GameEvent::GameResumed),
+            GameState::Dead | GameState::Won => return,
         };
+        self.state = state;
+        self.emit(event);
     }
 
     pub fn restart(&mut self) {
-        let bounds = self.bounds;
-        let bullet_cap = self.bullets.capacity();
-        *self = Self::with_config(GameConfig {
-            bounds,
-            bullet_pool_capacity: bullet_cap,
-            event_queue_capacity: 32,
-            bullet_cooldown_ticks: self.bullet_cooldown_max,
-        });
-        self.events.push(GameEvent::GameRestarted);
+        let mut events = std::mem::take(&mut self.events);
+        let rules = std::mem::take(&mut self.rules);
+        *self = Self::with_rules(self.config.clone(), rules);
+        events.set_tick(0);
+        self.events = events;
+        self.emit(GameEvent::GameRestarted);
+    }
+
+    // Queues an event and tells the rules about it.
+    pub fn emit(&mut self, event: GameEvent) {
+        self.events.push(event);
+        self.run_rules(|rule, game| rule.on_event(game, &event));
+    }
+
+    // The rules are lifted out for the call, so anything they trigger runs
+    // without them. Food is re-synced afterwards in case a rule moved it.
+    fn run_rules(&mut self, mut hook: impl FnMut(&mut dyn GameRule, &mut Game)) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let mut rules = std::mem::take(&mut self.rules);
+        for rule in &mut rules {
+            hook(rule.as_mut(), self);
+        }
+        self.rules = rules;
+        let food = self.world.food();
+        let food = food.is_active().then(|| food.position());
+        self.occupancy.set_food(food);
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    // What the last tick's sweep resolved, plus the snakes' own steps since.
+    pub fn collisions(&self) -> &[Collision] {
+        &self.collisions
+    }
+
+    // The snakes, food, bullets and scope, plus any extra entities. Extras
+    // ride along with the run: ticked after the core ones, drawn on their
+    // own layer, marked in the occupancy grid so they meet the snakes as
+    // `SnakeEntity` collisions, and cleared by a restart.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    // Everything on the arena in draw order, back to front. Food is hidden
+    // once the run is over; snakes that are out only come back for the
+    // final picture.
+    pub fn drawables(&self) -> Vec<&dyn Drawable> {
+        let over = self.state.is_over();
+        let shown = |id: usize| over || self.players.get(id).is_some_and(|p| p.is_alive());
+        let mut drawables: Vec<&dyn Drawable> = vec![&self.walls];
+        drawables.extend(self.world.drawables(shown, !over));
+        drawables.sort_by_key(|d| d.layer());
+        drawables
+    }
+
+    pub fn occupancy(&self) -> &OccupancyGrid {
+        &self.occupancy
     }
 
     pub fn snake(&self) -> &Snake {
-        &self.snake
+        &self.world.snakes()[PLAYER]
+    }
+
+    // Every snake on the board, the player's first.
+    pub fn snakes(&self) -> &[Snake] {
+        self.world.snakes()
+    }
+
+    // The bookkeeping for each snake, in the same order.
+    pub fn players(&self) -> &[Player] {
+        &self.players
     }
 
     pub fn food(&self) -> &Food {
-        &self.food
+        self.world.food()
+    }
+
+    pub fn food_mut(&mut self) -> &mut Food {
+        self.world.food_mut()
     }
 
     pub fn bullets(&self) -> &BulletPool {
-        &self.bullets
+        self.world.bullets()
     }
 
     pub fn scope(&self) -> &Scope {
-        &self.scope
+        self.world.scope()
     }
 
-    #[allow(dead_code)]
     pub fn events(&mut self) -> &mut EventQueue {
         &mut self.events
     }
 
+    pub fn event_queue(&self) -> &EventQueue {
+        &self.events
+    }
+
+    pub fn set_scope_config(&mut self, scope: ScopeConfig) {
+        self.config.scope = scope;
+        self.world.scope_mut().set_config(scope);
+    }
+
     pub fn is_scope_aligned(&self) -> bool {
-        self.scope.is_aligned()
+        self.world.scope().is_aligned()
     }
 }
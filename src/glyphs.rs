@@ -0,0 +1,203 @@
+use crate::core::Direction;
+use crate::systems::ParticleKind;
+use std::fmt;
+use std::str::FromStr;
+
+// Every arena cell is two terminal columns wide. Snake segments are drawn as
+// a junction column followed by a bridge column that links to the right-hand
+// neighbour.
+pub type CellGlyph = [&'static str; 2];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum GlyphSet {
+    #[default]
+    Blocks,
+    Ascii,
+    BoxDrawing,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Links {
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+}
+
+impl Links {
+    fn from_neighbours(a: Option<Direction>, b: Option<Direction>) -> Self {
+        let mut links = Self::default();
+        for dir in [a, b].into_iter().flatten() {
+            match dir {
+                Direction::Up => links.up = true,
+                Direction::Down => links.down = true,
+                Direction::Left => links.left = true,
+                Direction::Right => links.right = true,
+            }
+        }
+        links
+    }
+}
+
+impl GlyphSet {
+    pub const ALL: [GlyphSet; 3] = [GlyphSet::Blocks, GlyphSet::Ascii, GlyphSet::BoxDrawing];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            GlyphSet::Blocks => "blocks",
+            GlyphSet::Ascii => "ascii",
+            GlyphSet::BoxDrawing => "box",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&g| g == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn snake_head(self, facing: Direction, toward_tail: Option<Direction>) -> CellGlyph {
+        let bridge = self.bridge(toward_tail == Some(Direction::Right));
+        match self {
+            GlyphSet::Blocks => ["▓", "▓"],
+            GlyphSet::Ascii => [
+                match facing {
+                    Direction::Up => "^",
+                    Direction::Down => "v",
+                    Direction::Left => "<",
+                    Direction::Right => ">",
+                },
+                bridge,
+            ],
+            GlyphSet::BoxDrawing => [
+                match facing {
+                    Direction::Up => "▲",
+                    Direction::Down => "▼",
+                    Direction::Left => "◀",
+                    Direction::Right => "▶",
+                },
+                bridge,
+            ],
+        }
+    }
+
+    pub fn snake_segment(
+        self,
+        toward_head: Option<Direction>,
+        toward_tail: Option<Direction>,
+    ) -> CellGlyph {
+        let links = Links::from_neighbours(toward_head, toward_tail);
+        let bridge = self.bridge(links.right);
+
+        let junction = match self {
+            GlyphSet::Blocks => return ["▓", "▓"],
+            GlyphSet::Ascii => match (links.up, links.down, links.left, links.right) {
+                (false, false, _, _) => "-",
+                (_, _, false, false) => "|",
+                _ => "+",
+            },
+            GlyphSet::BoxDrawing => match (links.up, links.down, links.left, links.right) {
+                (false, false, true, true) => "━",
+                (true, true, false, false) => "┃",
+                (true, false, false, true) => "┗",
+                (true, false, true, false) => "┛",
+                (false, true, false, true) => "┏",
+                (false, true, true, false) => "┓",
+                (true, false, false, false) => "╹",
+                (false, true, false, false) => "╻",
+                (false, false, true, false) => "╸",
+                (false, false, false, true) => "╺",
+                _ => "■",
+            },
+        };
+
+        [junction, bridge]
+    }
+
+    fn bridge(self, links_right: bool) -> &'static str {
+        match (self, links_right) {
+            (_, false) => " ",
+            (GlyphSet::Blocks, true) => "▓",
+            (GlyphSet::Ascii, true) => "-",
+            (GlyphSet::BoxDrawing, true) => "━",
+        }
+    }
+
+    pub const fn food(self) -> &'static str {
+        match self {
+            GlyphSet::Blocks | GlyphSet::BoxDrawing => "▓▓",
+            GlyphSet::Ascii => "()",
+        }
+    }
+
+    pub const fn bullet(self) -> &'static str {
+        match self {
+            GlyphSet::Blocks | GlyphSet::BoxDrawing => "██",
+            GlyphSet::Ascii => "**",
+        }
+    }
+
+    pub const fn particle(self, kind: ParticleKind) -> &'static str {
+        match (self, kind) {
+            (GlyphSet::Ascii, ParticleKind::Spark) => "* ",
+            (GlyphSet::Ascii, ParticleKind::Debris) => ". ",
+            (GlyphSet::Ascii, ParticleKind::Flash) => "##",
+            (GlyphSet::Ascii, ParticleKind::Muzzle) => "==",
+            (_, ParticleKind::Spark) => "✧ ",
+            (_, ParticleKind::Debris) => "░░",
+            (_, ParticleKind::Flash) => "▓▓",
+            (_, ParticleKind::Muzzle) => "▒▒",
+        }
+    }
+
+    pub const fn meter(self, filled: bool) -> &'static str {
+        match (self, filled) {
+            (GlyphSet::Blocks, true) => "█",
+            (GlyphSet::Blocks, false) => "░",
+            (GlyphSet::Ascii, true) => "#",
+            (GlyphSet::Ascii, false) => "-",
+            (GlyphSet::BoxDrawing, true) => "━",
+            (GlyphSet::BoxDrawing, false) => "─",
+        }
+    }
+
+    pub const fn wall(self) -> &'static str {
+        match self {
+            GlyphSet::Blocks => "██",
+            GlyphSet::Ascii => "##",
+            GlyphSet::BoxDrawing => "▒▒",
+        }
+    }
+
+    pub const fn star(self, filled: bool) -> &'static str {
+        match (self, filled) {
+            (GlyphSet::Ascii, true) => "*",
+            (GlyphSet::Ascii, false) => ".",
+            (_, true) => "★",
+            (_, false) => "☆",
+        }
+    }
+
+    pub const fn scope_dot(self) -> &'static str {
+        match self {
+            GlyphSet::Blocks | GlyphSet::Ascii => "::",
+            GlyphSet::BoxDrawing => "··",
+        }
+    }
+}
+
+impl fmt::Display for GlyphSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GlyphSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown glyph set '{}', expected blocks, ascii or box", s))
+    }
+}
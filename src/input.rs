@@ -7,9 +7,22 @@ pub enum GameAction {
     Fire,
     Pause,
     Restart,
+    Stats,
+    CycleTheme,
+    CycleGlyphs,
+    Menu,
+    Settings,
+    ToggleDebug,
     Quit,
 }
 
+impl GameAction {
+    // Actions that keep happening every tick while their key is held down.
+    pub fn repeats_while_held(self) -> bool {
+        matches!(self, GameAction::Fire | GameAction::Move(_))
+    }
+}
+
 pub trait InputMapper {
     fn map(&self, code: KeyCode) -> Option<GameAction>;
 }
@@ -22,6 +35,12 @@ impl InputMapper for DefaultInputMapper {
             KeyCode::Char('q') | KeyCode::Esc => Some(GameAction::Quit),
             KeyCode::Char(' ') | KeyCode::Enter => Some(GameAction::Pause),
             KeyCode::Char('f') | KeyCode::Char('x') => Some(GameAction::Fire),
+            KeyCode::Tab => Some(GameAction::Stats),
+            KeyCode::Char('t') => Some(GameAction::CycleTheme),
+            KeyCode::Char('g') => Some(GameAction::CycleGlyphs),
+            KeyCode::Char('m') => Some(GameAction::Menu),
+            KeyCode::Char('o') => Some(GameAction::Settings),
+            KeyCode::F(3) => Some(GameAction::ToggleDebug),
             KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => {
                 Some(GameAction::Move(Direction::Up))
             }
@@ -38,3 +57,174 @@ impl InputMapper for DefaultInputMapper {
         }
     }
 }
+
+pub const REBINDABLE: [(GameAction, &str); 6] = [
+    (GameAction::Move(Direction::Up), "up"),
+    (GameAction::Move(Direction::Down), "down"),
+    (GameAction::Move(Direction::Left), "left"),
+    (GameAction::Move(Direction::Right), "right"),
+    (GameAction::Fire, "fire"),
+    (GameAction::Pause, "pause"),
+];
+
+pub fn action_id(action: GameAction) -> Option<&'static str> {
+    REBINDABLE
+        .iter()
+        .find(|(a, _)| *a == action)
+        .map(|(_, id)| *id)
+}
+
+pub fn action_from_id(id: &str) -> Option<GameAction> {
+    REBINDABLE
+        .iter()
+        .find(|(_, a)| *a == id)
+        .map(|(action, _)| *action)
+}
+
+pub fn key_name(code: KeyCode) -> Option<String> {
+    let name = match code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) if c.is_ascii_graphic() => c.to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        _ => return None,
+    };
+    Some(name)
+}
+
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    match name {
+        "space" => Some(KeyCode::Char(' ')),
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "enter" => Some(KeyCode::Enter),
+        "tab" => Some(KeyCode::Tab),
+        "backspace" => Some(KeyCode::Backspace),
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_graphic() => Some(KeyCode::Char(c)),
+                _ => None,
+            }
+        }
+    }
+}
+
+// Custom bindings sit on top of another mapper: a rebound key wins, and every
+// key that isn't rebound keeps its default meaning.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyBindings {
+    custom: Vec<(GameAction, KeyCode)>,
+}
+
+impl KeyBindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind(&mut self, action: GameAction, code: KeyCode) -> bool {
+        if action_id(action).is_none() || key_name(code).is_none() || is_reserved(code) {
+            return false;
+        }
+        self.custom.retain(|&(a, c)| a != action && c != code);
+        self.custom.push((action, code));
+        true
+    }
+
+    pub fn unbind(&mut self, action: GameAction) {
+        self.custom.retain(|&(a, _)| a != action);
+    }
+
+    pub fn clear(&mut self) {
+        self.custom.clear();
+    }
+
+    pub fn key_for(&self, action: GameAction) -> Option<KeyCode> {
+        self.custom
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, code)| *code)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (GameAction, KeyCode)> + '_ {
+        self.custom.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.custom.is_empty()
+    }
+}
+
+impl InputMapper for KeyBindings {
+    fn map(&self, code: KeyCode) -> Option<GameAction> {
+        self.custom
+            .iter()
+            .find(|(_, c)| *c == code)
+            .map(|(action, _)| *action)
+    }
+}
+
+// Only terminals that report key releases can say when a hold ends; elsewhere
+// nothing is tracked and the OS key repeat, which arrives as ordinary
+// presses, does the repeating instead.
+#[derive(Clone, Debug, Default)]
+pub struct HeldActions {
+    releases_reported: bool,
+    held: Vec<GameAction>,
+}
+
+impl HeldActions {
+    pub fn new(releases_reported: bool) -> Self {
+        Self {
+            releases_reported,
+            held: Vec::new(),
+        }
+    }
+
+    pub fn releases_reported(&self) -> bool {
+        self.releases_reported
+    }
+
+    pub fn press(&mut self, action: GameAction) {
+        if !self.releases_reported || !action.repeats_while_held() {
+            return;
+        }
+        // Holding a new direction takes over from the previous one.
+        if let GameAction::Move(_) = action {
+            self.held.retain(|a| !matches!(a, GameAction::Move(_)));
+        }
+        if !self.held.contains(&action) {
+            self.held.push(action);
+        }
+    }
+
+    pub fn release(&mut self, action: GameAction) {
+        self.held.retain(|&a| a != action);
+    }
+
+    pub fn is_held(&self, action: GameAction) -> bool {
+        self.held.contains(&action)
+    }
+
+    pub fn held(&self) -> &[GameAction] {
+        &self.held
+    }
+
+    pub fn clear(&mut self) {
+        self.held.clear();
+    }
+}
+
+fn is_reserved(code: KeyCode) -> bool {
+    matches!(
+        DefaultInputMapper.map(code),
+        Some(GameAction::Quit | GameAction::Menu | GameAction::Settings)
+    )
+}
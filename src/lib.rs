@@ -3,21 +3,34 @@
 #![deny(clippy::panic)]
 
 pub mod app;
+pub mod bot;
+pub mod campaign;
 pub mod core;
+pub mod daily;
 pub mod entities;
 pub mod game;
+pub mod glyphs;
 pub mod input;
 pub mod renderer;
+pub mod rules;
+pub mod storage;
 pub mod systems;
+pub mod theme;
 pub mod traits;
+pub mod ui;
 
 pub use app::{run, App, AppConfig};
 pub use core::{Bounds, Direction, GameState, Vec2};
 pub use entities::{Bullet, Food, Snake, SnakeMoveResult};
 pub use game::{Game, GameBuilder, GameConfig};
+pub use glyphs::GlyphSet;
 pub use input::{DefaultInputMapper, GameAction, InputMapper};
-pub use renderer::{GameRenderer, MinimalRenderer, RenderConfig};
-pub use systems::{BulletPool, EventQueue, GameEvent, Scope};
+pub use renderer::{GameRenderer, HudLayout, MinimalRenderer, RenderConfig, RenderContext};
+pub use rules::GameRule;
+pub use systems::{
+    BulletPool, EventBus, EventQueue, EventSubscriber, GameEvent, Scope, TimedEvent,
+};
+pub use theme::{ColorDepth, Theme, ThemeName};
 pub use traits::{
     Active, BoundedTickable, Collidable, GameEntity, Moveable, Positioned, Renderable, Targetable,
     Tickable,
@@ -1,27 +1,402 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use cool_snake::bot::{
+    run_match, ArenaConfig, Bot, BotProcess, Entrant, Tournament, TournamentConfig,
+    TournamentResult,
+};
+use cool_snake::daily::DailyChallenge;
+use cool_snake::storage::{DailyStore, ScoreStore, SettingsStore, StatsStore};
+use cool_snake::systems::{Achievement, GameMode, ScopeStyle};
+use cool_snake::{AppConfig, ColorDepth, GlyphSet, HudLayout, ThemeName};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "cool-snake")]
 #[command(about = "An over-engineered snake game with bullets and scope")]
 struct Cli {
-    #[arg(short, long, default_value = "2", value_parser = clap::value_parser!(u8).range(1..=3))]
-    speed: u8,
+    /// Game speed 1-3 [default: saved setting, else 2]
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=3))]
+    speed: Option<u8>,
+
+    /// Colour theme: default, monochrome, high-contrast, colorblind
+    #[arg(short, long)]
+    theme: Option<ThemeName>,
+
+    /// Colour depth: auto, truecolor, 256 or 16
+    #[arg(long, default_value = "auto")]
+    colors: ColorDepth,
+
+    /// Glyph set: blocks, ascii, box
+    #[arg(short, long)]
+    glyphs: Option<GlyphSet>,
+
+    /// Aiming line style: dotted, solid, sparse, off
+    #[arg(long)]
+    scope: Option<ScopeStyle>,
+
+    /// HUD layout: sidebar, compact, minimal
+    #[arg(long)]
+    hud: Option<HudLayout>,
+
+    /// Skip the menu and start in a mode: classic, time-attack, survival, zen
+    #[arg(short, long)]
+    mode: Option<GameMode>,
+
+    /// Leave the mouse to the terminal instead of using it to aim and click
+    #[arg(long)]
+    no_mouse: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the local high-score tables
+    Scores,
+    /// Show lifetime statistics and achievements
+    Stats,
+    /// Play today's daily challenge: one scored attempt, same game for everyone
+    Daily {
+        /// List past daily results instead of playing
+        #[arg(long)]
+        history: bool,
+    },
+    /// Play a headless game driven by external bots over stdin/stdout
+    Arena {
+        /// Bot command, split on whitespace, e.g. "./my_bot" or "python3 bot.py";
+        /// repeat to put one snake per bot in the same arena
+        #[arg(long = "bot", required = true)]
+        bots: Vec<String>,
+        /// Arena seed [default: random]
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Mode to play: classic, time-attack, survival, zen
+        #[arg(long, default_value = "classic")]
+        mode: GameMode,
+        #[arg(long, default_value_t = 30)]
+        width: i16,
+        #[arg(long, default_value_t = 20)]
+        height: i16,
+        /// Milliseconds the bot gets to answer each state
+        #[arg(long, default_value_t = 200)]
+        timeout_ms: u64,
+        /// Stop the match after this many ticks
+        #[arg(long, default_value_t = 5000)]
+        max_ticks: u64,
+    },
+    /// Run a round-robin between bots and rate them
+    Tournament {
+        /// Entrants: built-in strategies (idle, sniper, hunter) or bot commands
+        #[arg(required = true, num_args = 2..)]
+        bots: Vec<String>,
+        /// How many times each pair meets
+        #[arg(long, default_value_t = 1)]
+        rounds: u32,
+        /// Base seed the match seeds are derived from [default: random]
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Mode to play: classic, time-attack, survival, zen
+        #[arg(long, default_value = "classic")]
+        mode: GameMode,
+        #[arg(long, default_value_t = 30)]
+        width: i16,
+        #[arg(long, default_value_t = 20)]
+        height: i16,
+        /// Milliseconds a bot gets to answer each state
+        #[arg(long, default_value_t = 200)]
+        timeout_ms: u64,
+        /// Stop each game after this many ticks
+        #[arg(long, default_value_t = 2000)]
+        max_ticks: u64,
+        /// Write a replay of every match into this directory
+        #[arg(long)]
+        replays: Option<PathBuf>,
+    },
 }
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
     let cli = Cli::parse();
-    let tick_ms = match cli.speed {
-        1 => 120,
-        2 => 70,
-        3 => 40,
-        _ => 70,
-    };
 
+    match cli.command {
+        Some(Command::Scores) => print_scores(&ScoreStore::default_location()),
+        Some(Command::Stats) => print_stats(&StatsStore::default_location()),
+        Some(Command::Daily { history: true }) => {
+            print_daily_history(&DailyStore::default_location())
+        }
+        Some(Command::Daily { history: false }) => {
+            play_daily(&cli, &DailyStore::default_location())
+        }
+        Some(Command::Arena {
+            ref bots,
+            seed,
+            mode,
+            width,
+            height,
+            timeout_ms,
+            max_ticks,
+        }) => run_arena(
+            bots,
+            ArenaConfig {
+                width,
+                height,
+                mode,
+                max_ticks,
+                ..ArenaConfig::new(seed.unwrap_or_else(rand::random))
+            },
+            Duration::from_millis(timeout_ms),
+        ),
+        Some(Command::Tournament {
+            ref bots,
+            rounds,
+            seed,
+            mode,
+            width,
+            height,
+            timeout_ms,
+            max_ticks,
+            ref replays,
+        }) => run_tournament(
+            Tournament::new(
+                bots.iter().map(|spec| Entrant::parse(spec)).collect(),
+                TournamentConfig {
+                    arena: ArenaConfig {
+                        width,
+                        height,
+                        mode,
+                        max_ticks,
+                        ..ArenaConfig::new(seed.unwrap_or_else(rand::random))
+                    },
+                    rounds,
+                    timeout: Duration::from_millis(timeout_ms),
+                },
+            ),
+            replays.as_deref(),
+        ),
+        None => play(app_config(&cli)),
+    }
+}
+
+fn run_arena(
+    commands: &[String],
+    config: ArenaConfig,
+    timeout: Duration,
+) -> color_eyre::Result<()> {
+    let mut bots = commands
+        .iter()
+        .map(|command| BotProcess::spawn(command, timeout))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut seated: Vec<&mut dyn Bot> = bots.iter_mut().map(|b| b as &mut dyn Bot).collect();
+    let reports = run_match(&config, &mut seated);
+
+    println!("Seed      {}", config.seed);
+    println!("Mode      {}", config.mode.title());
+    for (bot, report) in bots.iter().zip(&reports) {
+        println!();
+        println!("Bot       {}", bot.command());
+        println!("Result    {}", report.end);
+        println!("Ticks     {}", report.ticks);
+        println!("Score     {}", report.score);
+        println!("Length    {}", report.length);
+    }
+
+    if reports.iter().any(|report| report.end.is_fault()) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_tournament(tournament: Tournament, replays: Option<&Path>) -> color_eyre::Result<()> {
+    if let Some(dir) = replays {
+        fs::create_dir_all(dir)?;
+    }
+    let names = tournament.names();
+    let mut write_error = None;
+
+    let TournamentResult { standings, .. } = tournament.run(|record| {
+        let name = |entrant: usize| names.get(entrant).map_or("?", String::as_str);
+        let verdict = match record.winner() {
+            Some(winner) => format!("{} wins", name(winner)),
+            None => "draw".to_string(),
+        };
+        let bots: Vec<&str> = record.sides.iter().map(|s| name(s.entrant)).collect();
+        let scores: Vec<String> = record
+            .sides
+            .iter()
+            .map(|s| s.report.score.to_string())
+            .collect();
+        println!(
+            "Match {:>3}  {}  {}  {}",
+            record.index + 1,
+            bots.join(" vs "),
+            scores.join(" - "),
+            verdict
+        );
+        for side in &record.sides {
+            if side.report.end.is_fault() {
+                println!("           {}: {}", name(side.entrant), side.report.end);
+            }
+        }
+        if let Some(dir) = replays {
+            let path = dir.join(format!("match-{:03}.jsonl", record.index + 1));
+            if let Err(e) = fs::write(&path, record.replay(&names)) {
+                write_error.get_or_insert(e);
+            }
+        }
+    });
+
+    println!();
+    println!(
+        "{:>4}  {:<20} {:>6} {:>4} {:>4} {:>4} {:>6} {:>9}",
+        "Rank", "Bot", "Rating", "W", "D", "L", "Faults", "Avg score"
+    );
+    for (rank, s) in standings.iter().enumerate() {
+        println!(
+            "{:>4}  {:<20} {:>6.0} {:>4} {:>4} {:>4} {:>6} {:>9.1}",
+            rank + 1,
+            s.name,
+            s.rating,
+            s.wins,
+            s.draws,
+            s.losses,
+            s.faults,
+            s.average_score()
+        );
+    }
+
+    match write_error {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
+
+fn app_config(cli: &Cli) -> AppConfig {
+    let mut settings = SettingsStore::default_location().load().unwrap_or_default();
+    settings.speed = cli.speed.unwrap_or(settings.speed);
+    settings.theme = cli.theme.unwrap_or(settings.theme);
+    settings.glyphs = cli.glyphs.unwrap_or(settings.glyphs);
+    settings.scope = cli.scope.unwrap_or(settings.scope);
+    settings.hud = cli.hud.unwrap_or(settings.hud);
+
+    AppConfig {
+        color_depth: cli.colors,
+        mode: cli.mode,
+        mouse: !cli.no_mouse,
+        ..AppConfig::from_settings(&settings)
+    }
+}
+
+fn play(config: AppConfig) -> color_eyre::Result<()> {
     let terminal = ratatui::init();
-    let result = cool_snake::run(terminal, Duration::from_millis(tick_ms));
+    let result = cool_snake::run(terminal, config);
     ratatui::restore();
     result
 }
+
+fn play_daily(cli: &Cli, store: &DailyStore) -> color_eyre::Result<()> {
+    let challenge = DailyChallenge::today();
+    if !store.begin(&challenge)? {
+        let score = store
+            .load()?
+            .get(challenge.date)
+            .map_or(0, |result| result.score);
+        println!(
+            "Already played the {} daily: scored {}. Come back tomorrow.",
+            challenge.date, score
+        );
+        return Ok(());
+    }
+
+    play(AppConfig {
+        daily: Some(challenge),
+        ..app_config(cli)
+    })
+}
+
+fn print_daily_history(store: &DailyStore) -> color_eyre::Result<()> {
+    let history = store.load()?;
+
+    if history.is_empty() {
+        println!(
+            "No daily challenges played yet ({})",
+            store.path().display()
+        );
+        return Ok(());
+    }
+
+    for result in history.results() {
+        println!(
+            "{}  {:<12} {:>6}",
+            result.date,
+            result.mode.title(),
+            result.score
+        );
+    }
+
+    Ok(())
+}
+
+fn print_scores(store: &ScoreStore) -> color_eyre::Result<()> {
+    let scores = store.load()?;
+
+    if scores.is_empty() {
+        println!("No high scores yet ({})", store.path().display());
+        return Ok(());
+    }
+
+    for (key, entries) in scores.tables() {
+        println!(
+            "{} | speed {} | {}x{}",
+            key.mode, key.speed, key.width, key.height
+        );
+        for (rank, entry) in entries.iter().enumerate() {
+            println!("  {:>2}. {:<12} {:>6}", rank + 1, entry.name, entry.score);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn print_stats(store: &StatsStore) -> color_eyre::Result<()> {
+    let snapshot = store.load()?;
+    let s = &snapshot.stats;
+    let accuracy = s
+        .accuracy()
+        .map(|a| format!("{:.0}%", a * 100.0))
+        .unwrap_or_else(|| "-".to_string());
+
+    println!("Shots fired       {}", s.shots_fired);
+    println!("Accuracy          {}", accuracy);
+    println!("Bullet pickups    {}", s.bullet_collections);
+    println!("Head pickups      {}", s.head_collections);
+    println!("Segments lost     {}", s.segments_lost);
+    println!("Deaths (wall)     {}", s.deaths_by_wall);
+    println!("Deaths (self)     {}", s.deaths_by_self);
+    println!("Deaths (hunger)   {}", s.deaths_by_starving);
+    println!("Longest snake     {}", s.longest_snake);
+    println!();
+    println!(
+        "Achievements ({}/{})",
+        snapshot.achievements.len(),
+        Achievement::ALL.len()
+    );
+    for achievement in Achievement::ALL {
+        let mark = if snapshot.achievements.contains(&achievement) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "  [{}] {:<14} {}",
+            mark,
+            achievement.title(),
+            achievement.description()
+        );
+    }
+
+    Ok(())
+}
@@ -1,50 +1,145 @@
-use crate::core::{GameState, Vec2};
+use crate::core::{Bounds, GameState, Vec2};
 use crate::game::Game;
+use crate::glyphs::GlyphSet;
+use crate::systems::{EffectLayer, Effects};
+use crate::theme::Theme;
 use crate::traits::Renderable;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Widget},
 };
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+// Draw order, back to front. Particle effects sit outside it: their under
+// layer goes down first and their over layer last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderLayer {
+    Terrain,
+    Guides,
+    Bodies,
+    Items,
+    Projectiles,
+}
+
+pub struct RenderContext<'a> {
+    pub offset: Vec2,
+    pub bounds: Vec2,
+    pub theme: &'a Theme,
+    pub glyphs: GlyphSet,
+}
+
+// Cells start inside the arena's one-cell border.
+pub fn arena_offset(area: Rect) -> Vec2 {
+    Vec2::new((area.x + 1) as i16, (area.y + 1) as i16)
+}
+
+// Maps a terminal position inside an arena drawn at `area` back to the
+// cell under it.
+pub fn cell_at(area: Rect, bounds: Vec2, column: u16, row: u16) -> Option<Vec2> {
+    Vec2::from_screen(column, row, arena_offset(area)).filter(|cell| cell.in_bounds(bounds))
+}
 
 pub struct GameRenderer<'a> {
     game: &'a Game,
     config: RenderConfig,
+    effects: Option<&'a Effects>,
 }
 
-pub struct RenderConfig {
-    pub border_color: Color,
-    pub text_color: Color,
-    pub hud_color: Color,
-    #[allow(dead_code)]
-    pub show_debug: bool,
+// `Sidebar` falls back to `Compact` when the terminal is too narrow for the
+// panel; that decision is made by whoever lays out the frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum HudLayout {
+    #[default]
+    Sidebar,
+    Compact,
+    Minimal,
 }
 
-impl Default for RenderConfig {
-    fn default() -> Self {
-        Self {
-            border_color: Color::DarkGray,
-            text_color: Color::White,
-            hud_color: Color::Cyan,
-            show_debug: false,
+impl HudLayout {
+    pub const ALL: [HudLayout; 3] = [HudLayout::Sidebar, HudLayout::Compact, HudLayout::Minimal];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            HudLayout::Sidebar => "sidebar",
+            HudLayout::Compact => "compact",
+            HudLayout::Minimal => "minimal",
         }
     }
 }
 
+impl fmt::Display for HudLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HudLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|h| h.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown HUD layout '{}', expected sidebar, compact or minimal",
+                    s
+                )
+            })
+    }
+}
+
+pub(crate) fn cooldown_meter(game: &Game, glyphs: GlyphSet, width: usize) -> String {
+    let max = game.bullet_cooldown_max().max(1) as usize;
+    let remaining = (game.bullet_cooldown() as usize).min(max);
+    let filled = (width * (max - remaining) + max / 2) / max;
+    (0..width).map(|i| glyphs.meter(i < filled)).collect()
+}
+
+#[derive(Default)]
+pub struct RenderConfig {
+    pub theme: Theme,
+    pub glyphs: GlyphSet,
+    pub hud: HudLayout,
+    pub show_debug: bool,
+}
+
 impl<'a> GameRenderer<'a> {
     pub fn new(game: &'a Game) -> Self {
         Self::with_config(game, RenderConfig::default())
     }
 
     pub fn with_config(game: &'a Game, config: RenderConfig) -> Self {
-        Self { game, config }
+        Self {
+            game,
+            config,
+            effects: None,
+        }
+    }
+
+    pub fn with_effects(mut self, effects: &'a Effects) -> Self {
+        self.effects = Some(effects);
+        self
+    }
+
+    fn render_effects(&self, layer: EffectLayer, ctx: &RenderContext, buf: &mut Buffer) {
+        if let Some(effects) = self.effects {
+            effects.layer(layer).render(ctx, buf);
+        }
+    }
+
+    fn death_animation_playing(&self) -> bool {
+        self.effects.is_some_and(|e| e.is_death_animation_playing())
     }
 
     fn render_border(&self, area: Rect, buf: &mut Buffer) {
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(self.config.border_color))
+            .border_style(Style::default().fg(self.config.theme.border()))
             .render(area, buf);
     }
 
@@ -55,28 +150,121 @@ impl<'a> GameRenderer<'a> {
         } else {
             "   "
         };
+        let scoring = self.game.scoring();
+        let combo = if scoring.in_combo() {
+            format!(" x{}", scoring.multiplier())
+        } else {
+            String::new()
+        };
+        let goal = match (self.game.objective(), self.game.time_left()) {
+            (Some(objective), _) => {
+                let (current, target) = objective.progress(&self.game.tally());
+                format!("GOAL {}/{} | ", current.min(target), target)
+            }
+            (None, Some(left)) => {
+                let secs = left.as_secs();
+                format!("T {}:{:02} | ", secs / 60, secs % 60)
+            }
+            (None, None) => String::new(),
+        };
 
-        let hud = format!(" {} | SCORE: {} ", aligned, score);
+        let hud = match self.config.hud {
+            HudLayout::Compact => format!(
+                " {} | {}LV {} | SCORE: {}{} | LEN {} | GUN {} | B {}/{} ",
+                aligned,
+                goal,
+                self.game.level(),
+                score,
+                combo,
+                self.game.snake().length(),
+                cooldown_meter(self.game, self.config.glyphs, 4),
+                self.game.bullets().active_count(),
+                self.game.bullets().capacity(),
+            ),
+            HudLayout::Sidebar | HudLayout::Minimal => {
+                format!(
+                    " {} | {}LV {} | SCORE: {}{} ",
+                    aligned,
+                    goal,
+                    self.game.level(),
+                    score,
+                    combo
+                )
+            }
+        };
 
-        buf.set_string(
+        buf.set_stringn(
             area.x + 2,
             area.y,
             &hud,
-            Style::default().fg(self.config.hud_color),
+            area.width.saturating_sub(4) as usize,
+            Style::default().fg(self.config.theme.hud()),
         );
     }
 
     fn render_game_over(&self, area: Rect, buf: &mut Buffer) {
+        let headline = (self.game.state() == GameState::Won).then(|| {
+            if self.game.time_left() == Some(Duration::ZERO) {
+                "TIME UP"
+            } else {
+                "BOARD FULL"
+            }
+        });
         let score_text = format!("SCORE: {}", self.game.score());
         let restart_text = "PRESS SPACE TO RESTART";
+        let menu_text = "M FOR MENU";
 
         let center_x = |s: &str| area.x + (area.width.saturating_sub(s.len() as u16)) / 2;
         let center_y = area.y + area.height / 2;
 
-        let style = Style::default().fg(self.config.text_color);
+        let style = Style::default().fg(self.config.theme.text());
 
+        if let Some(headline) = headline {
+            buf.set_string(
+                center_x(headline),
+                center_y.saturating_sub(3),
+                headline,
+                Style::default().fg(self.config.theme.accent()),
+            );
+        }
         buf.set_string(center_x(&score_text), center_y - 1, &score_text, style);
         buf.set_string(center_x(restart_text), center_y + 1, restart_text, style);
+        if center_y + 2 < area.bottom() {
+            buf.set_string(
+                center_x(menu_text),
+                center_y + 2,
+                menu_text,
+                Style::default().fg(self.config.theme.muted()),
+            );
+        }
+    }
+
+    // Column numbers every five cells along the bottom border and the last
+    // digit of each row down the left one.
+    fn render_grid_coordinates(&self, area: Rect, buf: &mut Buffer) {
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+        let style = Style::default().fg(self.config.theme.muted());
+        let (w, h) = self.game.bounds();
+        let bottom = area.bottom() - 1;
+
+        for x in (0..w).step_by(5) {
+            let col = area.x + 1 + (x as u16) * 2;
+            let label = x.to_string();
+            if col + label.len() as u16 >= area.right() {
+                break;
+            }
+            buf.set_string(col, bottom, &label, style);
+        }
+
+        for y in 0..h {
+            let row = area.y + 1 + y as u16;
+            if row >= bottom {
+                break;
+            }
+            buf.set_string(area.x, row, (y % 10).to_string(), style);
+        }
     }
 
     fn render_paused(&self, area: Rect, buf: &mut Buffer) {
@@ -84,18 +272,17 @@ impl<'a> GameRenderer<'a> {
         let x = area.x + (area.width.saturating_sub(text.len() as u16)) / 2;
         let y = area.y + area.height / 2;
 
-        buf.set_string(x, y, text, Style::default().fg(self.config.text_color));
+        buf.set_string(x, y, text, Style::default().fg(self.config.theme.text()));
     }
 
-    fn render_entities(&self, offset: Vec2, buf: &mut Buffer) {
-        self.game.scope().render(offset, buf);
-        self.game.snake().render(offset, buf);
+    fn render_entities(&self, ctx: &RenderContext, buf: &mut Buffer) {
+        self.render_effects(EffectLayer::Under, ctx, buf);
 
-        if !self.game.state().is_dead() {
-            self.game.food().render(offset, buf);
+        for drawable in self.game.drawables() {
+            drawable.render(ctx, buf);
         }
 
-        self.game.bullets().render(offset, buf);
+        self.render_effects(EffectLayer::Over, ctx, buf);
     }
 }
 
@@ -103,26 +290,44 @@ impl Widget for GameRenderer<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         self.render_border(area, buf);
 
-        let offset = Vec2::new((area.x + 1) as i16, (area.y + 1) as i16);
+        let ctx = RenderContext {
+            offset: arena_offset(area),
+            bounds: Bounds::from(self.game.bounds()).to_vec2(),
+            theme: &self.config.theme,
+            glyphs: self.config.glyphs,
+        };
+
+        self.render_entities(&ctx, buf);
 
-        self.render_entities(offset, buf);
+        self.render_hud(area, buf);
+        if self.config.show_debug {
+            self.render_grid_coordinates(area, buf);
+        }
 
         match self.game.state() {
             GameState::Paused => self.render_paused(area, buf),
+            GameState::Dead if self.death_animation_playing() => {}
             GameState::Dead => self.render_game_over(area, buf),
-            GameState::Playing => self.render_hud(area, buf),
+            // The campaign draws its own results panel over a won game.
+            GameState::Won if self.game.objective().is_some() => {}
+            GameState::Won => self.render_game_over(area, buf),
+            GameState::Playing => {}
         }
     }
 }
 
 pub struct MinimalRenderer<'a> {
     game: &'a Game,
+    theme: Theme,
 }
 
 impl<'a> MinimalRenderer<'a> {
-    #[allow(dead_code)]
     pub fn new(game: &'a Game) -> Self {
-        Self { game }
+        Self::with_theme(game, Theme::default())
+    }
+
+    pub fn with_theme(game: &'a Game, theme: Theme) -> Self {
+        Self { game, theme }
     }
 }
 
@@ -130,15 +335,20 @@ impl Widget for MinimalRenderer<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(self.theme.border()))
             .render(area, buf);
 
-        let offset = Vec2::new((area.x + 1) as i16, (area.y + 1) as i16);
+        let ctx = RenderContext {
+            offset: arena_offset(area),
+            bounds: Bounds::from(self.game.bounds()).to_vec2(),
+            theme: &self.theme,
+            glyphs: GlyphSet::default(),
+        };
 
-        self.game.snake().render(offset, buf);
+        self.game.snake().render(&ctx, buf);
 
         if !self.game.state().is_dead() {
-            self.game.food().render(offset, buf);
+            self.game.food().render(&ctx, buf);
         }
     }
 }
@@ -0,0 +1,28 @@
+use crate::core::Vec2;
+use crate::game::Game;
+use crate::systems::{Collision, GameEvent};
+
+// House rules plug into the game at fixed points, registered through
+// `GameBuilder::with_rule`. Each hook gets the whole game, so a rule can
+// read anything public and change what the game exposes for changing.
+//
+// While a hook runs the rules are out of the game, so events a rule emits
+// from inside one aren't fed back to the rules; a rule can't set itself off
+// in a loop. Rules outlive `Game::restart` and hear about it as a
+// `GameRestarted` event.
+pub trait GameRule {
+    // After each tick's movement, collisions and end-of-run checks.
+    fn on_tick(&mut self, _game: &mut Game) {}
+
+    // After a snake's head steps onto `head`, and after any food it ate.
+    fn on_snake_move(&mut self, _game: &mut Game, _head: Vec2) {}
+
+    // For every event the game emits, once it's queued.
+    fn on_event(&mut self, _game: &mut Game, _event: &GameEvent) {}
+
+    // Before the collision takes effect.
+    fn on_collision(&mut self, _game: &mut Game, _collision: &Collision) {}
+
+    // Once new food is in place; `Game::food_mut` reaches it.
+    fn on_spawn(&mut self, _game: &mut Game) {}
+}
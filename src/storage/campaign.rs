@@ -0,0 +1,103 @@
+use super::{data_dir, read_to_string_or_empty, update_locked};
+use crate::campaign::Campaign;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CAMPAIGN_FILE: &str = "campaign.txt";
+
+// Keyed by level id rather than position so reordering or inserting levels
+// doesn't shuffle anyone's stars.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CampaignProgress {
+    stars: BTreeMap<String, u8>,
+}
+
+impl CampaignProgress {
+    pub fn parse(contents: &str) -> Self {
+        let stars = contents
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(key, value)| {
+                let id = key.trim().strip_prefix("stars.")?;
+                let stars = value.trim().parse::<u8>().ok()?.min(3);
+                Some((id.to_string(), stars))
+            })
+            .collect();
+        Self { stars }
+    }
+
+    pub fn to_file_string(&self) -> String {
+        self.stars
+            .iter()
+            .map(|(id, stars)| format!("stars.{}={}\n", id, stars))
+            .collect()
+    }
+
+    pub fn stars(&self, id: &str) -> u8 {
+        self.stars.get(id).copied().unwrap_or(0)
+    }
+
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.stars(id) > 0
+    }
+
+    // Returns whether this beat the previous best.
+    pub fn record(&mut self, id: &str, stars: u8) -> bool {
+        let stars = stars.min(3);
+        if stars <= self.stars(id) {
+            return false;
+        }
+        self.stars.insert(id.to_string(), stars);
+        true
+    }
+
+    pub fn is_unlocked(&self, campaign: &Campaign, index: usize) -> bool {
+        match index.checked_sub(1) {
+            None => true,
+            Some(prev) => campaign
+                .get(prev)
+                .is_some_and(|level| self.is_completed(&level.id)),
+        }
+    }
+
+    pub fn total_stars(&self) -> u32 {
+        self.stars.values().map(|&s| s as u32).sum()
+    }
+}
+
+pub struct CampaignStore {
+    path: PathBuf,
+}
+
+impl CampaignStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn default_location() -> Self {
+        Self::new(data_dir().join(CAMPAIGN_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> io::Result<CampaignProgress> {
+        read_to_string_or_empty(&self.path).map(|contents| CampaignProgress::parse(&contents))
+    }
+
+    pub fn record(&self, id: &str, stars: u8) -> io::Result<CampaignProgress> {
+        update_locked(&self.path, |current| {
+            let mut progress = CampaignProgress::parse(current);
+            progress.record(id, stars);
+            (progress.to_file_string(), progress)
+        })
+    }
+}
+
+impl Default for CampaignStore {
+    fn default() -> Self {
+        Self::default_location()
+    }
+}
@@ -0,0 +1,133 @@
+use super::{data_dir, read_to_string_or_empty, update_locked};
+use crate::daily::{DailyChallenge, Date};
+use crate::systems::GameMode;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const DAILY_FILE: &str = "daily.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyResult {
+    pub date: Date,
+    pub mode: GameMode,
+    pub score: u32,
+}
+
+// One line per day: `2026-10-18 time-attack 42`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DailyHistory {
+    results: BTreeMap<Date, DailyResult>,
+}
+
+impl DailyHistory {
+    pub fn parse(contents: &str) -> Self {
+        let results = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let date = fields.next()?.parse::<Date>().ok()?;
+                let mode = fields.next()?.parse::<GameMode>().ok()?;
+                let score = fields.next()?.parse::<u32>().ok()?;
+                Some((date, DailyResult { date, mode, score }))
+            })
+            .collect();
+        Self { results }
+    }
+
+    pub fn to_file_string(&self) -> String {
+        self.results
+            .values()
+            .map(|r| format!("{} {} {}\n", r.date, r.mode, r.score))
+            .collect()
+    }
+
+    pub fn get(&self, date: Date) -> Option<&DailyResult> {
+        self.results.get(&date)
+    }
+
+    pub fn has_played(&self, date: Date) -> bool {
+        self.results.contains_key(&date)
+    }
+
+    // Oldest first.
+    pub fn results(&self) -> impl Iterator<Item = &DailyResult> {
+        self.results.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    // The attempt is claimed before the run starts, at zero points, so
+    // quitting halfway doesn't earn a second go. Returns false if the day
+    // was already played.
+    pub fn begin(&mut self, challenge: &DailyChallenge) -> bool {
+        if self.has_played(challenge.date) {
+            return false;
+        }
+        self.results.insert(
+            challenge.date,
+            DailyResult {
+                date: challenge.date,
+                mode: challenge.mode,
+                score: 0,
+            },
+        );
+        true
+    }
+
+    pub fn finish(&mut self, date: Date, score: u32) {
+        if let Some(result) = self.results.get_mut(&date) {
+            result.score = score;
+        }
+    }
+}
+
+pub struct DailyStore {
+    path: PathBuf,
+}
+
+impl DailyStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn default_location() -> Self {
+        Self::new(data_dir().join(DAILY_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> io::Result<DailyHistory> {
+        read_to_string_or_empty(&self.path).map(|contents| DailyHistory::parse(&contents))
+    }
+
+    pub fn begin(&self, challenge: &DailyChallenge) -> io::Result<bool> {
+        update_locked(&self.path, |current| {
+            let mut history = DailyHistory::parse(current);
+            let started = history.begin(challenge);
+            (history.to_file_string(), started)
+        })
+    }
+
+    pub fn finish(&self, date: Date, score: u32) -> io::Result<DailyHistory> {
+        update_locked(&self.path, |current| {
+            let mut history = DailyHistory::parse(current);
+            history.finish(date, score);
+            (history.to_file_string(), history)
+        })
+    }
+}
+
+impl Default for DailyStore {
+    fn default() -> Self {
+        Self::default_location()
+    }
+}
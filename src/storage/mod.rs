@@ -0,0 +1,95 @@
+mod campaign;
+mod daily;
+mod scores;
+mod settings;
+mod stats;
+
+pub use campaign::{CampaignProgress, CampaignStore};
+pub use daily::{DailyHistory, DailyResult, DailyStore};
+pub use scores::{
+    sanitize_name, HighScores, ScoreEntry, ScoreKey, ScoreStore, MAX_ENTRIES_PER_TABLE,
+    MAX_NAME_LEN,
+};
+pub use settings::{Settings, SettingsStore};
+pub use stats::{StatsSnapshot, StatsStore};
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DATA_DIR_ENV: &str = "COOL_SNAKE_DATA_DIR";
+const APP_DIR_NAME: &str = "cool-snake";
+
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        return PathBuf::from(dir).join(APP_DIR_NAME);
+    }
+
+    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        return PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join(APP_DIR_NAME);
+    }
+
+    PathBuf::from(".").join(APP_DIR_NAME)
+}
+
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub(crate) fn read_to_string_or_empty(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e),
+    }
+}
+
+// Writers serialize on a sibling `.lock` file; the rename keeps readers from
+// ever seeing a half-written file.
+pub(crate) fn update_locked<T>(
+    path: &Path,
+    update: impl FnOnce(&str) -> (String, T),
+) -> io::Result<T> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(with_suffix(path, "lock"))?;
+    lock.lock()?;
+
+    let current = read_to_string_or_empty(path)?;
+    let (contents, result) = update(&current);
+
+    let tmp_path = with_suffix(path, &format!("tmp.{}", std::process::id()));
+    {
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(contents.as_bytes())?;
+        tmp.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+
+    lock.unlock()?;
+    Ok(result)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
@@ -0,0 +1,199 @@
+use super::{data_dir, read_to_string_or_empty, unix_timestamp, update_locked};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const MAX_ENTRIES_PER_TABLE: usize = 10;
+pub const MAX_NAME_LEN: usize = 12;
+
+const SCORES_FILE: &str = "scores.tsv";
+const ANONYMOUS: &str = "anon";
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScoreKey {
+    pub mode: String,
+    pub speed: u8,
+    pub width: i16,
+    pub height: i16,
+}
+
+impl ScoreKey {
+    pub fn new(mode: impl Into<String>, speed: u8, width: i16, height: i16) -> Self {
+        Self {
+            mode: mode.into(),
+            speed,
+            width,
+            height,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub timestamp: u64,
+}
+
+impl ScoreEntry {
+    pub fn new(name: &str, score: u32) -> Self {
+        Self {
+            name: sanitize_name(name),
+            score,
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
+pub fn sanitize_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_NAME_LEN)
+        .collect();
+    let cleaned = cleaned.trim();
+
+    if cleaned.is_empty() {
+        ANONYMOUS.to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HighScores {
+    tables: BTreeMap<ScoreKey, Vec<ScoreEntry>>,
+}
+
+impl HighScores {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let mut scores = Self::new();
+        for line in contents.lines() {
+            if let Some((key, entry)) = parse_line(line) {
+                scores.insert(key, entry);
+            }
+        }
+        scores
+    }
+
+    pub fn to_file_string(&self) -> String {
+        let mut out = String::new();
+        for (key, entries) in &self.tables {
+            for entry in entries {
+                out.push_str(&format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                    key.mode,
+                    key.speed,
+                    key.width,
+                    key.height,
+                    entry.score,
+                    entry.timestamp,
+                    entry.name
+                ));
+            }
+        }
+        out
+    }
+
+    pub fn table(&self, key: &ScoreKey) -> &[ScoreEntry] {
+        self.tables.get(key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn tables(&self) -> impl Iterator<Item = (&ScoreKey, &[ScoreEntry])> {
+        self.tables.iter().map(|(k, v)| (k, v.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    pub fn qualifies(&self, key: &ScoreKey, score: u32) -> bool {
+        if score == 0 {
+            return false;
+        }
+        let table = self.table(key);
+        table.len() < MAX_ENTRIES_PER_TABLE || table.last().is_some_and(|e| score > e.score)
+    }
+
+    pub fn insert(&mut self, key: ScoreKey, entry: ScoreEntry) -> Option<usize> {
+        if !self.qualifies(&key, entry.score) {
+            return None;
+        }
+
+        let table = self.tables.entry(key).or_default();
+        let rank = table.partition_point(|e| e.score >= entry.score);
+        table.insert(rank, entry);
+        table.truncate(MAX_ENTRIES_PER_TABLE);
+        Some(rank)
+    }
+}
+
+fn parse_line(line: &str) -> Option<(ScoreKey, ScoreEntry)> {
+    let mut fields = line.splitn(7, '\t');
+    let mode = fields.next()?.to_string();
+    let speed = fields.next()?.parse().ok()?;
+    let width = fields.next()?.parse().ok()?;
+    let height = fields.next()?.parse().ok()?;
+    let score = fields.next()?.parse().ok()?;
+    let timestamp = fields.next()?.parse().ok()?;
+    let name = fields.next()?.to_string();
+
+    if mode.is_empty() {
+        return None;
+    }
+
+    Some((
+        ScoreKey {
+            mode,
+            speed,
+            width,
+            height,
+        },
+        ScoreEntry {
+            name,
+            score,
+            timestamp,
+        },
+    ))
+}
+
+pub struct ScoreStore {
+    path: PathBuf,
+}
+
+impl ScoreStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn default_location() -> Self {
+        Self::new(data_dir().join(SCORES_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> io::Result<HighScores> {
+        read_to_string_or_empty(&self.path).map(|contents| HighScores::parse(&contents))
+    }
+
+    pub fn submit(&self, key: ScoreKey, entry: ScoreEntry) -> io::Result<Option<usize>> {
+        update_locked(&self.path, |current| {
+            let mut scores = HighScores::parse(current);
+            let rank = scores.insert(key, entry);
+            (scores.to_file_string(), rank)
+        })
+    }
+}
+
+impl Default for ScoreStore {
+    fn default() -> Self {
+        Self::default_location()
+    }
+}
@@ -0,0 +1,130 @@
+use super::{data_dir, read_to_string_or_empty, update_locked};
+use crate::glyphs::GlyphSet;
+use crate::input::{action_from_id, action_id, key_name, parse_key, KeyBindings};
+use crate::renderer::HudLayout;
+use crate::systems::ScopeStyle;
+use crate::theme::ThemeName;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SETTINGS_FILE: &str = "settings.txt";
+const BIND_PREFIX: &str = "bind.";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Settings {
+    pub speed: u8,
+    pub theme: ThemeName,
+    pub glyphs: GlyphSet,
+    pub scope: ScopeStyle,
+    pub hud: HudLayout,
+    pub bindings: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            speed: 2,
+            theme: ThemeName::default(),
+            glyphs: GlyphSet::default(),
+            scope: ScopeStyle::default(),
+            hud: HudLayout::default(),
+            bindings: KeyBindings::new(),
+        }
+    }
+}
+
+impl Settings {
+    // Unknown keys and bad values are skipped so a hand-edited file never
+    // stops the game from starting.
+    pub fn parse(contents: &str) -> Self {
+        let mut settings = Self::default();
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if let Some(id) = key.strip_prefix(BIND_PREFIX) {
+                if let (Some(action), Some(code)) = (action_from_id(id), parse_key(value)) {
+                    settings.bindings.bind(action, code);
+                }
+                continue;
+            }
+
+            match key {
+                "speed" => {
+                    if let Ok(speed @ 1..=3) = value.parse::<u8>() {
+                        settings.speed = speed;
+                    }
+                }
+                "theme" => {
+                    if let Ok(theme) = value.parse() {
+                        settings.theme = theme;
+                    }
+                }
+                "glyphs" => {
+                    if let Ok(glyphs) = value.parse() {
+                        settings.glyphs = glyphs;
+                    }
+                }
+                "scope" => {
+                    if let Ok(scope) = value.parse() {
+                        settings.scope = scope;
+                    }
+                }
+                "hud" => {
+                    if let Ok(hud) = value.parse() {
+                        settings.hud = hud;
+                    }
+                }
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    pub fn to_file_string(&self) -> String {
+        let mut out = format!(
+            "speed={}\ntheme={}\nglyphs={}\nscope={}\nhud={}\n",
+            self.speed, self.theme, self.glyphs, self.scope, self.hud
+        );
+        for (action, code) in self.bindings.iter() {
+            if let (Some(id), Some(key)) = (action_id(action), key_name(code)) {
+                out.push_str(&format!("{}{}={}\n", BIND_PREFIX, id, key));
+            }
+        }
+        out
+    }
+}
+
+pub struct SettingsStore {
+    path: PathBuf,
+}
+
+impl SettingsStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn default_location() -> Self {
+        Self::new(data_dir().join(SETTINGS_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> io::Result<Settings> {
+        read_to_string_or_empty(&self.path).map(|contents| Settings::parse(&contents))
+    }
+
+    pub fn save(&self, settings: &Settings) -> io::Result<()> {
+        update_locked(&self.path, |_| (settings.to_file_string(), ()))
+    }
+}
+
+impl Default for SettingsStore {
+    fn default() -> Self {
+        Self::default_location()
+    }
+}
@@ -0,0 +1,121 @@
+use super::{data_dir, read_to_string_or_empty, update_locked};
+use crate::systems::{Achievement, LifetimeStats};
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const STATS_FILE: &str = "stats.txt";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    pub stats: LifetimeStats,
+    pub achievements: BTreeSet<Achievement>,
+}
+
+impl StatsSnapshot {
+    pub fn parse(contents: &str) -> Self {
+        let mut snapshot = Self::default();
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if key == "achievements" {
+                snapshot.achievements = value
+                    .split(',')
+                    .filter_map(|id| Achievement::from_id(id.trim()))
+                    .collect();
+                continue;
+            }
+
+            let Ok(n) = value.parse::<u64>() else {
+                continue;
+            };
+            let stats = &mut snapshot.stats;
+            match key {
+                "shots_fired" => stats.shots_fired = n,
+                "bullet_collections" => stats.bullet_collections = n,
+                "head_collections" => stats.head_collections = n,
+                "times_damaged" => stats.times_damaged = n,
+                "segments_lost" => stats.segments_lost = n,
+                "deaths_by_wall" => stats.deaths_by_wall = n,
+                "deaths_by_self" => stats.deaths_by_self = n,
+                "deaths_by_starving" => stats.deaths_by_starving = n,
+                "longest_snake" => stats.longest_snake = n,
+                _ => {}
+            }
+        }
+        snapshot
+    }
+
+    pub fn to_file_string(&self) -> String {
+        let s = &self.stats;
+        let achievements: Vec<_> = self.achievements.iter().map(|a| a.id()).collect();
+        format!(
+            "shots_fired={}\n\
+             bullet_collections={}\n\
+             head_collections={}\n\
+             times_damaged={}\n\
+             segments_lost={}\n\
+             deaths_by_wall={}\n\
+             deaths_by_self={}\n\
+             deaths_by_starving={}\n\
+             longest_snake={}\n\
+             achievements={}\n",
+            s.shots_fired,
+            s.bullet_collections,
+            s.head_collections,
+            s.times_damaged,
+            s.segments_lost,
+            s.deaths_by_wall,
+            s.deaths_by_self,
+            s.deaths_by_starving,
+            s.longest_snake,
+            achievements.join(","),
+        )
+    }
+}
+
+pub struct StatsStore {
+    path: PathBuf,
+}
+
+impl StatsStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn default_location() -> Self {
+        Self::new(data_dir().join(STATS_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> io::Result<StatsSnapshot> {
+        read_to_string_or_empty(&self.path).map(|contents| StatsSnapshot::parse(&contents))
+    }
+
+    // Sessions save deltas rather than totals so two terminals playing at
+    // once both contribute to the lifetime numbers.
+    pub fn merge(
+        &self,
+        delta: &LifetimeStats,
+        achievements: &BTreeSet<Achievement>,
+    ) -> io::Result<StatsSnapshot> {
+        update_locked(&self.path, |current| {
+            let mut snapshot = StatsSnapshot::parse(current);
+            snapshot.stats.merge(delta);
+            snapshot.achievements.extend(achievements.iter().copied());
+            (snapshot.to_file_string(), snapshot)
+        })
+    }
+}
+
+impl Default for StatsStore {
+    fn default() -> Self {
+        Self::default_location()
+    }
+}
@@ -0,0 +1,108 @@
+use super::stats::LifetimeStats;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Achievement {
+    FirstBite,
+    FirstShot,
+    Sharpshooter,
+    Marksman,
+    Deadeye,
+    Glutton,
+    Longcat,
+    Anaconda,
+    TriggerHappy,
+    Ouroboros,
+    Shredded,
+    Veteran,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 12] = [
+        Achievement::FirstBite,
+        Achievement::FirstShot,
+        Achievement::Sharpshooter,
+        Achievement::Marksman,
+        Achievement::Deadeye,
+        Achievement::Glutton,
+        Achievement::Longcat,
+        Achievement::Anaconda,
+        Achievement::TriggerHappy,
+        Achievement::Ouroboros,
+        Achievement::Shredded,
+        Achievement::Veteran,
+    ];
+
+    pub const fn id(self) -> &'static str {
+        match self {
+            Achievement::FirstBite => "first_bite",
+            Achievement::FirstShot => "first_shot",
+            Achievement::Sharpshooter => "sharpshooter",
+            Achievement::Marksman => "marksman",
+            Achievement::Deadeye => "deadeye",
+            Achievement::Glutton => "glutton",
+            Achievement::Longcat => "longcat",
+            Achievement::Anaconda => "anaconda",
+            Achievement::TriggerHappy => "trigger_happy",
+            Achievement::Ouroboros => "ouroboros",
+            Achievement::Shredded => "shredded",
+            Achievement::Veteran => "veteran",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Achievement::FirstBite => "First Bite",
+            Achievement::FirstShot => "Pew",
+            Achievement::Sharpshooter => "Sharpshooter",
+            Achievement::Marksman => "Marksman",
+            Achievement::Deadeye => "Deadeye",
+            Achievement::Glutton => "Glutton",
+            Achievement::Longcat => "Longcat",
+            Achievement::Anaconda => "Anaconda",
+            Achievement::TriggerHappy => "Trigger Happy",
+            Achievement::Ouroboros => "Ouroboros",
+            Achievement::Shredded => "Shredded",
+            Achievement::Veteran => "Veteran",
+        }
+    }
+
+    pub const fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstBite => "Collect your first food",
+            Achievement::FirstShot => "Fire your first bullet",
+            Achievement::Sharpshooter => "Collect 10 food with bullets",
+            Achievement::Marksman => "Collect 100 food with bullets",
+            Achievement::Deadeye => "50% accuracy over 50+ shots",
+            Achievement::Glutton => "Collect 250 food in total",
+            Achievement::Longcat => "Grow a snake to length 20",
+            Achievement::Anaconda => "Grow a snake to length 50",
+            Achievement::TriggerHappy => "Fire 1000 bullets",
+            Achievement::Ouroboros => "Bite your own tail",
+            Achievement::Shredded => "Lose 100 segments to food",
+            Achievement::Veteran => "Die 50 times",
+        }
+    }
+
+    pub fn is_met(self, stats: &LifetimeStats) -> bool {
+        match self {
+            Achievement::FirstBite => stats.total_collections() >= 1,
+            Achievement::FirstShot => stats.shots_fired >= 1,
+            Achievement::Sharpshooter => stats.bullet_collections >= 10,
+            Achievement::Marksman => stats.bullet_collections >= 100,
+            Achievement::Deadeye => {
+                stats.shots_fired >= 50 && stats.accuracy().is_some_and(|a| a >= 0.5)
+            }
+            Achievement::Glutton => stats.total_collections() >= 250,
+            Achievement::Longcat => stats.longest_snake >= 20,
+            Achievement::Anaconda => stats.longest_snake >= 50,
+            Achievement::TriggerHappy => stats.shots_fired >= 1000,
+            Achievement::Ouroboros => stats.deaths_by_self >= 1,
+            Achievement::Shredded => stats.segments_lost >= 100,
+            Achievement::Veteran => stats.total_deaths() >= 50,
+        }
+    }
+}
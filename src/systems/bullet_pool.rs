@@ -1,6 +1,7 @@
 use crate::core::{Direction, Vec2};
 use crate::entities::Bullet;
-use crate::traits::{Active, BoundedTickable, Moveable, Positioned, Renderable};
+use crate::renderer::{RenderContext, RenderLayer};
+use crate::traits::{Active, BoundedTickable, Layered, Renderable};
 use ratatui::buffer::Buffer;
 
 pub struct BulletPool {
@@ -21,52 +22,31 @@ impl BulletPool {
     }
 
     pub fn spawn(&mut self, position: Vec2, direction: Direction) -> bool {
-        self.cleanup();
-        self.bullets.push(Bullet::new(position, direction));
-        true
+        self.spawn_for(0, position, direction)
     }
 
-    pub fn tick(&mut self, bounds: Vec2) {
-        for bullet in &mut self.bullets {
-            bullet.tick(bounds);
-        }
+    // Fired by snake `owner`, who gets the credit if it hits.
+    pub fn spawn_for(&mut self, owner: usize, position: Vec2, direction: Direction) -> bool {
         self.cleanup();
+        self.bullets
+            .push(Bullet::new(position, direction).with_owner(owner));
+        true
     }
 
-    pub fn check_collision_before_tick(&mut self, target: Vec2, bounds: Vec2) -> bool {
+    // Returns the owner of every bullet that ran out of range or left the
+    // arena this tick; those are the shots that missed. Hits are settled by
+    // the collision sweep before this runs.
+    pub fn tick(&mut self, bounds: Vec2) -> Vec<usize> {
+        let mut expired = Vec::new();
         for bullet in &mut self.bullets {
-            if !bullet.is_active() {
-                continue;
-            }
-
-            let current = bullet.position();
-            let vel = bullet.velocity();
-
-            if current == target {
-                bullet.deactivate();
-                return true;
-            }
-
-            let speed = vel.x.abs().max(vel.y.abs());
-            if speed > 0 {
-                let dir_x = vel.x.signum();
-                let dir_y = vel.y.signum();
-
-                for i in 1..=speed {
-                    let check_pos = Vec2::new(current.x + dir_x * i, current.y + dir_y * i);
-
-                    if !check_pos.in_bounds(bounds) {
-                        break;
-                    }
-
-                    if check_pos == target {
-                        bullet.deactivate();
-                        return true;
-                    }
-                }
+            let was_active = bullet.is_active();
+            bullet.tick(bounds);
+            if was_active && !bullet.is_active() {
+                expired.push(bullet.owner());
             }
         }
-        false
+        self.cleanup();
+        expired
     }
 
     fn cleanup(&mut self) {
@@ -77,7 +57,25 @@ impl BulletPool {
         self.bullets.iter().filter(|b| b.is_active())
     }
 
-    #[allow(dead_code)]
+    // Active bullets with the index `get` and `deactivate` take; indices
+    // stay valid until the next spawn or tick.
+    pub fn indexed(&self) -> impl Iterator<Item = (usize, &Bullet)> {
+        self.bullets
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.is_active())
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Bullet> {
+        self.bullets.get(index).filter(|b| b.is_active())
+    }
+
+    pub fn deactivate(&mut self, index: usize) {
+        if let Some(bullet) = self.bullets.get_mut(index) {
+            bullet.deactivate();
+        }
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Bullet> {
         self.bullets.iter_mut().filter(|b| b.is_active())
     }
@@ -93,36 +91,6 @@ impl BulletPool {
     pub fn available(&self) -> usize {
         usize::MAX
     }
-
-    pub fn check_collision(&mut self, target: Vec2) -> bool {
-        for bullet in &mut self.bullets {
-            if !bullet.is_active() {
-                continue;
-            }
-
-            if bullet.position() == target {
-                bullet.deactivate();
-                return true;
-            }
-
-            let vel = bullet.velocity();
-            let speed = vel.x.abs().max(vel.y.abs());
-            if speed > 1 {
-                let dir_x = vel.x.signum();
-                let dir_y = vel.y.signum();
-                let prev_pos = bullet.position() - vel;
-
-                for i in 0..speed {
-                    let check_pos = Vec2::new(prev_pos.x + dir_x * i, prev_pos.y + dir_y * i);
-                    if check_pos == target {
-                        bullet.deactivate();
-                        return true;
-                    }
-                }
-            }
-        }
-        false
-    }
 }
 
 impl Default for BulletPool {
@@ -131,10 +99,16 @@ impl Default for BulletPool {
     }
 }
 
+impl Layered for BulletPool {
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Projectiles
+    }
+}
+
 impl Renderable for BulletPool {
-    fn render(&self, offset: Vec2, buf: &mut Buffer) {
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
         for bullet in self.iter() {
-            bullet.render(offset, buf);
+            bullet.render(ctx, buf);
         }
     }
 }
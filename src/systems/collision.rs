@@ -0,0 +1,301 @@
+use super::occupancy::OccupancyGrid;
+use crate::core::Vec2;
+use crate::entities::EntityId;
+
+// Every tick is split into this many slices so entities of different speeds
+// can be compared at the same instant. It divides evenly by every speed the
+// game uses (1-4 cells a tick), so each cell on a path gets its own slice.
+pub const SUBSTEPS: i16 = 12;
+
+// Order matters: when two collisions land on the same slice the earlier
+// kind wins, so a shot beats the food reaching the snake at the same moment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CollisionKind {
+    BulletFood,
+    FoodHead,
+    FoodBody,
+    BulletWall,
+    FoodWall,
+    SnakeWall,
+    SnakeSelf,
+    // A snake stepping into another snake's body.
+    SnakeSnake,
+    // A snake stepping onto another snake's head; both are out.
+    HeadOn,
+    // A world entity and the snake meeting, whichever of them moved.
+    SnakeEntity,
+}
+
+impl CollisionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BulletFood => "bullet/food",
+            Self::FoodHead => "food/head",
+            Self::FoodBody => "food/body",
+            Self::BulletWall => "bullet/wall",
+            Self::FoodWall => "food/wall",
+            Self::SnakeWall => "snake/wall",
+            Self::SnakeSelf => "snake/self",
+            Self::SnakeSnake => "snake/snake",
+            Self::HeadOn => "head/head",
+            Self::SnakeEntity => "snake/entity",
+        }
+    }
+
+    pub fn consumes_food(&self) -> bool {
+        matches!(
+            self,
+            Self::BulletFood | Self::FoodHead | Self::FoodBody | Self::FoodWall
+        )
+    }
+
+    pub fn consumes_bullet(&self) -> bool {
+        matches!(self, Self::BulletFood | Self::BulletWall)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Collision {
+    pub kind: CollisionKind,
+    pub position: Vec2,
+    // How far through the tick it happened, out of `SUBSTEPS`.
+    pub substep: i16,
+    // Index into the bullet pool for the bullet kinds.
+    pub bullet: Option<usize>,
+    // The world entity involved, for `SnakeEntity`.
+    pub entity: Option<EntityId>,
+    // The snake involved: the one stepping for the snake kinds, the one
+    // reached for the food kinds.
+    pub snake: Option<usize>,
+}
+
+impl Collision {
+    pub fn new(kind: CollisionKind, position: Vec2, substep: i16) -> Self {
+        Self {
+            kind,
+            position,
+            substep,
+            bullet: None,
+            entity: None,
+            snake: None,
+        }
+    }
+
+    pub fn with_snake(mut self, snake: usize) -> Self {
+        self.snake = Some(snake);
+        self
+    }
+
+    pub fn with_entity(mut self, id: EntityId) -> Self {
+        self.entity = Some(id);
+        self
+    }
+
+    fn with_bullet(mut self, index: usize) -> Self {
+        self.bullet = Some(index);
+        self
+    }
+}
+
+// The straight path an entity covers in one tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sweep {
+    pub from: Vec2,
+    pub velocity: Vec2,
+}
+
+impl Sweep {
+    pub fn new(from: Vec2, velocity: Vec2) -> Self {
+        Self { from, velocity }
+    }
+
+    pub fn stationary(at: Vec2) -> Self {
+        Self::new(at, Vec2::zero())
+    }
+
+    pub fn speed(&self) -> i16 {
+        self.velocity.x.abs().max(self.velocity.y.abs())
+    }
+
+    // The cell occupied `substep` slices into the tick.
+    pub fn at(&self, substep: i16) -> Vec2 {
+        let step = Vec2::new(self.velocity.x.signum(), self.velocity.y.signum());
+        let cells =
+            i32::from(self.speed()) * i32::from(substep.clamp(0, SUBSTEPS)) / i32::from(SUBSTEPS);
+        self.from + step * cells as i16
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Mover {
+    sweep: Sweep,
+    alive: bool,
+    previous: Vec2,
+}
+
+impl Mover {
+    fn new(sweep: Sweep) -> Self {
+        Self {
+            sweep,
+            alive: true,
+            previous: sweep.from,
+        }
+    }
+
+    // Entities that leave the arena drop out of the sweep without a
+    // collision; their own tick handles expiry and respawning.
+    fn advance(&mut self, substep: i16, bounds: Vec2) -> Option<Vec2> {
+        if !self.alive {
+            return None;
+        }
+        let at = self.sweep.at(substep);
+        if !at.in_bounds(bounds) {
+            self.alive = false;
+            return None;
+        }
+        Some(at)
+    }
+}
+
+// The static side of a tick: the arena, its walls and the snakes, which
+// only move between ticks.
+pub struct CollisionWorld<'a> {
+    bounds: Vec2,
+    grid: &'a OccupancyGrid,
+}
+
+impl<'a> CollisionWorld<'a> {
+    pub fn new(grid: &'a OccupancyGrid) -> Self {
+        Self {
+            bounds: grid.bounds(),
+            grid,
+        }
+    }
+
+    // Walks every path slice by slice and returns the collisions that take
+    // effect, earliest first. Each entity is stopped by its first collision,
+    // so anything it would have hit later in the tick is dropped.
+    pub fn sweep(&self, food: Option<Sweep>, bullets: &[(usize, Sweep)]) -> Vec<Collision> {
+        let mut food = food.map(Mover::new);
+        let mut bullets: Vec<(usize, Mover)> = bullets
+            .iter()
+            .map(|&(index, sweep)| (index, Mover::new(sweep)))
+            .collect();
+        let mut resolved = Vec::new();
+
+        for substep in 0..=SUBSTEPS {
+            let food_at = food
+                .as_mut()
+                .and_then(|mover| mover.advance(substep, self.bounds));
+            let mut found = Vec::new();
+
+            if let (Some(at), Some(mover)) = (food_at, food.as_ref()) {
+                if let Some(collision) = self.food_hit(at, substep) {
+                    found.push(collision);
+                }
+                for (index, bullet) in &mut bullets {
+                    let Some(bullet_at) = bullet.advance(substep, self.bounds) else {
+                        continue;
+                    };
+                    // Crossing counts: food and bullet swapping cells within
+                    // a slice meet halfway.
+                    let crossed = bullet_at == mover.previous && at == bullet.previous;
+                    if bullet_at == at || crossed {
+                        found.push(
+                            Collision::new(CollisionKind::BulletFood, bullet_at, substep)
+                                .with_bullet(*index),
+                        );
+                    }
+                }
+            }
+
+            for (index, bullet) in &mut bullets {
+                let Some(at) = bullet.advance(substep, self.bounds) else {
+                    continue;
+                };
+                if self.grid.has_wall(at) {
+                    found.push(
+                        Collision::new(CollisionKind::BulletWall, at, substep).with_bullet(*index),
+                    );
+                }
+                bullet.previous = at;
+            }
+            if let (Some(at), Some(mover)) = (food_at, food.as_mut()) {
+                mover.previous = at;
+            }
+
+            found.sort_by_key(|c| (c.kind, c.bullet));
+            for collision in found {
+                let bullet = collision
+                    .bullet
+                    .and_then(|index| bullets.iter_mut().find(|(i, _)| *i == index));
+                if collision.kind.consumes_food() && !food.is_some_and(|f| f.alive) {
+                    continue;
+                }
+                if collision.kind.consumes_bullet() && !bullet.as_ref().is_some_and(|b| b.1.alive) {
+                    continue;
+                }
+                if collision.kind.consumes_food() {
+                    if let Some(mover) = food.as_mut() {
+                        mover.alive = false;
+                    }
+                }
+                if let Some((_, mover)) = bullet {
+                    mover.alive = false;
+                }
+                resolved.push(collision);
+            }
+        }
+        resolved
+    }
+
+    fn food_hit(&self, at: Vec2, substep: i16) -> Option<Collision> {
+        if let Some((snake, index)) = self.grid.segment_at(at) {
+            let kind = if index.is_head() {
+                CollisionKind::FoodHead
+            } else {
+                CollisionKind::FoodBody
+            };
+            Some(Collision::new(kind, at, substep).with_snake(snake))
+        } else if self.grid.has_wall(at) {
+            Some(Collision::new(CollisionKind::FoodWall, at, substep))
+        } else {
+            None
+        }
+    }
+
+    // A snake moves one whole cell between ticks, so its step is checked
+    // on its own. `next` is the head's next cell after any wrapping. Other
+    // snakes' bodies are solid even when `solid_body` lets a snake cross
+    // its own.
+    pub fn snake_step(
+        &self,
+        snake: usize,
+        next: Vec2,
+        solid_body: bool,
+        food: Vec2,
+    ) -> Option<Collision> {
+        let other = self
+            .grid
+            .segment_at(next)
+            .filter(|&(owner, _)| owner != snake);
+        let kind = if !next.in_bounds(self.bounds) || self.grid.has_wall(next) {
+            CollisionKind::SnakeWall
+        } else if let Some((_, index)) = other {
+            if index.is_head() {
+                CollisionKind::HeadOn
+            } else {
+                CollisionKind::SnakeSnake
+            }
+        } else if solid_body && self.grid.has_snake(next) {
+            CollisionKind::SnakeSelf
+        } else if next == food {
+            CollisionKind::FoodHead
+        } else if self.grid.has_entity(next) {
+            CollisionKind::SnakeEntity
+        } else {
+            return None;
+        };
+        Some(Collision::new(kind, next, SUBSTEPS).with_snake(snake))
+    }
+}
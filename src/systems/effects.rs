@@ -0,0 +1,297 @@
+use super::collision::CollisionKind;
+use super::event_bus::EventSubscriber;
+use super::events::{GameEvent, TimedEvent};
+use crate::core::Vec2;
+use crate::renderer::RenderContext;
+use crate::traits::Renderable;
+use ratatui::{buffer::Buffer, style::Style};
+
+const RADIAL: [Vec2; 8] = [
+    Vec2::new(0, -1),
+    Vec2::new(1, -1),
+    Vec2::new(1, 0),
+    Vec2::new(1, 1),
+    Vec2::new(0, 1),
+    Vec2::new(-1, 1),
+    Vec2::new(-1, 0),
+    Vec2::new(-1, -1),
+];
+
+const DEATH_MAX_DELAY: u8 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EffectLayer {
+    Under,
+    Over,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleKind {
+    Spark,
+    Debris,
+    Flash,
+    Muzzle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tint {
+    Food,
+    Accent,
+    Danger,
+    Body,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Particle {
+    kind: ParticleKind,
+    position: Vec2,
+    velocity: Vec2,
+    tint: Tint,
+    layer: EffectLayer,
+    delay: u8,
+    age: u8,
+    lifetime: u8,
+    death: bool,
+}
+
+impl Particle {
+    fn new(kind: ParticleKind, position: Vec2, tint: Tint, layer: EffectLayer) -> Self {
+        Self {
+            kind,
+            position,
+            velocity: Vec2::zero(),
+            tint,
+            layer,
+            delay: 0,
+            age: 0,
+            lifetime: 3,
+            death: false,
+        }
+    }
+
+    fn moving(mut self, velocity: Vec2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    fn lasting(mut self, lifetime: u8) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    fn delayed(mut self, delay: u8) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn visible(&self) -> bool {
+        self.delay == 0 && self.age < self.lifetime
+    }
+
+    fn tick(&mut self) {
+        if self.delay > 0 {
+            self.delay -= 1;
+            return;
+        }
+        self.age = self.age.saturating_add(1);
+        self.position = self.position + self.velocity;
+    }
+}
+
+#[derive(Default)]
+pub struct Effects {
+    particles: Vec<Particle>,
+    snake_snapshot: Vec<Vec2>,
+}
+
+impl Effects {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Events don't carry the lost segments or the dying body, so effects work
+    // from a copy of the snake taken just before each simulation step.
+    pub fn observe_snake(&mut self, segments: impl IntoIterator<Item = Vec2>) {
+        self.snake_snapshot.clear();
+        self.snake_snapshot.extend(segments);
+    }
+
+    pub fn tick(&mut self) {
+        for particle in &mut self.particles {
+            particle.tick();
+        }
+        self.particles.retain(|p| p.delay > 0 || p.age < p.lifetime);
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_death_animation_playing(&self) -> bool {
+        self.particles.iter().any(|p| p.death)
+    }
+
+    pub fn layer(&self, layer: EffectLayer) -> EffectLayerView<'_> {
+        EffectLayerView {
+            effects: self,
+            layer,
+        }
+    }
+
+    fn burst(&mut self, origin: Vec2, by_bullet: bool) {
+        let (tint, lifetime, dirs): (_, _, &[Vec2]) = if by_bullet {
+            (Tint::Accent, 5, &RADIAL)
+        } else {
+            (Tint::Food, 3, &[RADIAL[0], RADIAL[2], RADIAL[4], RADIAL[6]])
+        };
+
+        self.particles
+            .push(Particle::new(ParticleKind::Flash, origin, tint, EffectLayer::Over).lasting(2));
+        for &dir in dirs {
+            self.particles.push(
+                Particle::new(ParticleKind::Spark, origin, tint, EffectLayer::Over)
+                    .moving(dir)
+                    .lasting(lifetime),
+            );
+        }
+    }
+
+    fn damage(&mut self, cut: Vec2, segments_lost: usize) {
+        let lost: Vec<Vec2> = match self.snake_snapshot.iter().position(|&s| s == cut) {
+            Some(idx) => self.snake_snapshot.iter().skip(idx + 1).copied().collect(),
+            None => vec![cut],
+        };
+
+        for (i, &pos) in lost.iter().take(segments_lost.max(1)).enumerate() {
+            self.particles.push(
+                Particle::new(ParticleKind::Flash, pos, Tint::Danger, EffectLayer::Over).lasting(3),
+            );
+            self.particles.push(
+                Particle::new(ParticleKind::Debris, pos, Tint::Body, EffectLayer::Under)
+                    .moving(RADIAL[i % RADIAL.len()])
+                    .delayed(1)
+                    .lasting(4),
+            );
+        }
+    }
+
+    fn death(&mut self) {
+        for (i, &pos) in self.snake_snapshot.iter().enumerate() {
+            let delay = (i / 2).min(DEATH_MAX_DELAY as usize) as u8;
+            let mut flash =
+                Particle::new(ParticleKind::Flash, pos, Tint::Danger, EffectLayer::Over)
+                    .delayed(delay)
+                    .lasting(3);
+            flash.death = true;
+            self.particles.push(flash);
+        }
+
+        if let Some(&head) = self.snake_snapshot.first() {
+            for &dir in &RADIAL {
+                let mut spark =
+                    Particle::new(ParticleKind::Spark, head, Tint::Danger, EffectLayer::Over)
+                        .moving(dir)
+                        .lasting(6);
+                spark.death = true;
+                self.particles.push(spark);
+            }
+        }
+    }
+}
+
+impl EventSubscriber for Effects {
+    fn on_event(&mut self, event: &TimedEvent) {
+        match event.event {
+            GameEvent::FoodCollected {
+                position,
+                by_bullet,
+            } => self.burst(position, by_bullet),
+            GameEvent::SnakeDamaged {
+                position,
+                segments_lost,
+            } => self.damage(position, segments_lost),
+            GameEvent::BulletFired { position, .. } => self.particles.push(
+                Particle::new(
+                    ParticleKind::Muzzle,
+                    position,
+                    Tint::Accent,
+                    EffectLayer::Over,
+                )
+                .lasting(2),
+            ),
+            GameEvent::Collision {
+                kind: CollisionKind::BulletWall,
+                position,
+            } => self.particles.push(
+                Particle::new(
+                    ParticleKind::Spark,
+                    position,
+                    Tint::Accent,
+                    EffectLayer::Over,
+                )
+                .lasting(2),
+            ),
+            GameEvent::MoveBlocked { position } => self.particles.push(
+                Particle::new(
+                    ParticleKind::Spark,
+                    position,
+                    Tint::Danger,
+                    EffectLayer::Over,
+                )
+                .lasting(2),
+            ),
+            GameEvent::SnakeDied { .. } => self.death(),
+            GameEvent::GameRestarted => self.clear(),
+            GameEvent::SnakeGrew { .. }
+            | GameEvent::Collision { .. }
+            | GameEvent::ComboStarted { .. }
+            | GameEvent::ComboBroken { .. }
+            | GameEvent::LevelUp { .. }
+            | GameEvent::ObjectiveCompleted
+            | GameEvent::BoardFilled
+            | GameEvent::TimeUp
+            | GameEvent::GamePaused
+            | GameEvent::GameResumed
+            | GameEvent::EventsDropped { .. } => {}
+        }
+    }
+}
+
+pub struct EffectLayerView<'a> {
+    effects: &'a Effects,
+    layer: EffectLayer,
+}
+
+impl Renderable for EffectLayerView<'_> {
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
+        let particles = self
+            .effects
+            .particles
+            .iter()
+            .filter(|p| p.layer == self.layer && p.visible());
+
+        for particle in particles {
+            if !particle.position.in_bounds(ctx.bounds) {
+                continue;
+            }
+
+            let color = match particle.tint {
+                Tint::Food => ctx.theme.food(),
+                Tint::Accent => ctx.theme.accent(),
+                Tint::Danger => ctx.theme.danger(),
+                Tint::Body => ctx.theme.snake_body(),
+            };
+            let (x, y) = particle.position.to_screen(ctx.offset);
+            buf.set_string(
+                x,
+                y,
+                ctx.glyphs.particle(particle.kind),
+                Style::default().fg(color),
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use super::events::{EventQueue, GameEvent, TimedEvent};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub trait EventSubscriber {
+    fn on_event(&mut self, event: &TimedEvent);
+}
+
+pub type SharedSubscriber = Rc<RefCell<dyn EventSubscriber>>;
+
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<SharedSubscriber>,
+    dispatched: u64,
+    dropped: u64,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe<S: EventSubscriber + 'static>(&mut self, subscriber: Rc<RefCell<S>>) {
+        self.subscribers.push(subscriber);
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    pub fn dispatch(&mut self, queue: &mut EventQueue) -> usize {
+        let mut count = 0;
+
+        for event in queue.drain() {
+            self.publish(&event);
+            count += 1;
+        }
+
+        let dropped = queue.take_overflowed();
+        if dropped > 0 {
+            self.dropped += dropped as u64;
+            self.publish(&TimedEvent {
+                tick: queue.current_tick(),
+                event: GameEvent::EventsDropped { count: dropped },
+            });
+        }
+
+        self.dispatched += count as u64;
+        count
+    }
+
+    fn publish(&self, event: &TimedEvent) {
+        for subscriber in &self.subscribers {
+            subscriber.borrow_mut().on_event(event);
+        }
+    }
+
+    pub fn dispatched(&self) -> u64 {
+        self.dispatched
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
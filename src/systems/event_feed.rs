@@ -0,0 +1,56 @@
+use super::event_bus::EventSubscriber;
+use super::events::{GameEvent, TimedEvent};
+use std::collections::VecDeque;
+
+pub struct EventFeed {
+    entries: VecDeque<TimedEvent>,
+    capacity: usize,
+}
+
+impl EventFeed {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, event: TimedEvent) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(event);
+    }
+
+    pub fn recent(&self) -> impl Iterator<Item = &TimedEvent> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for EventFeed {
+    fn default() -> Self {
+        Self::new(32)
+    }
+}
+
+// Collisions are left out: each one is followed by the gameplay event it
+// caused, and listing both would halve what the feed can show.
+impl EventSubscriber for EventFeed {
+    fn on_event(&mut self, event: &TimedEvent) {
+        if !matches!(event.event, GameEvent::Collision { .. }) {
+            self.push(*event);
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use super::collision::CollisionKind;
 use crate::core::{Direction, Vec2};
+use std::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GameEvent {
@@ -17,31 +19,110 @@ pub enum GameEvent {
         position: Vec2,
         segments_lost: usize,
     },
+    SnakeGrew {
+        length: usize,
+    },
+    // A step refused because something solid was in the way, in modes
+    // where that isn't fatal.
+    MoveBlocked {
+        position: Vec2,
+    },
+    Collision {
+        kind: CollisionKind,
+        position: Vec2,
+    },
+    ComboStarted {
+        multiplier: u32,
+    },
+    ComboBroken {
+        streak: u32,
+    },
+    LevelUp {
+        level: u32,
+    },
+    ObjectiveCompleted,
+    BoardFilled,
+    TimeUp,
     GamePaused,
     GameResumed,
     GameRestarted,
+    EventsDropped {
+        count: usize,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeathCause {
     HitWall,
     HitSelf,
+    Starved,
+    // Ran into another snake, head-on or into its body.
+    HitSnake,
+}
+
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            GameEvent::FoodCollected {
+                by_bullet: true, ..
+            } => f.write_str("food shot"),
+            GameEvent::FoodCollected { .. } => f.write_str("food eaten"),
+            GameEvent::BulletFired { .. } => f.write_str("fired"),
+            GameEvent::SnakeDied {
+                cause: DeathCause::HitWall,
+            } => f.write_str("hit the wall"),
+            GameEvent::SnakeDied {
+                cause: DeathCause::HitSelf,
+            } => f.write_str("bit itself"),
+            GameEvent::SnakeDied {
+                cause: DeathCause::Starved,
+            } => f.write_str("starved"),
+            GameEvent::SnakeDied {
+                cause: DeathCause::HitSnake,
+            } => f.write_str("hit a snake"),
+            GameEvent::SnakeDamaged { segments_lost, .. } => write!(f, "cut -{}", segments_lost),
+            GameEvent::SnakeGrew { length } => write!(f, "grew to {}", length),
+            GameEvent::MoveBlocked { .. } => f.write_str("blocked"),
+            GameEvent::Collision { kind, .. } => write!(f, "hit {}", kind.as_str()),
+            GameEvent::ComboStarted { multiplier } => write!(f, "combo x{}", multiplier),
+            GameEvent::ComboBroken { streak } => write!(f, "combo lost ({} hits)", streak),
+            GameEvent::LevelUp { level } => write!(f, "level {}", level),
+            GameEvent::ObjectiveCompleted => f.write_str("objective complete"),
+            GameEvent::BoardFilled => f.write_str("board filled"),
+            GameEvent::TimeUp => f.write_str("time up"),
+            GameEvent::GamePaused => f.write_str("paused"),
+            GameEvent::GameResumed => f.write_str("resumed"),
+            GameEvent::GameRestarted => f.write_str("restarted"),
+            GameEvent::EventsDropped { count } => write!(f, "{} events dropped", count),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedEvent {
+    pub tick: u64,
+    pub event: GameEvent,
 }
 
 pub struct EventQueue {
-    events: Vec<Option<GameEvent>>,
-    write_idx: usize,
+    events: Vec<Option<TimedEvent>>,
     read_idx: usize,
+    len: usize,
     capacity: usize,
+    current_tick: u64,
+    overflowed: usize,
 }
 
 impl EventQueue {
     pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
         Self {
             events: (0..capacity).map(|_| None).collect(),
-            write_idx: 0,
             read_idx: 0,
+            len: 0,
             capacity,
+            current_tick: 0,
+            overflowed: 0,
         }
     }
 
@@ -49,39 +130,75 @@ impl EventQueue {
         Self::new(32)
     }
 
-    pub fn push(&mut self, event: GameEvent) {
-        self.events[self.write_idx] = Some(event);
-        self.write_idx = (self.write_idx + 1) % self.capacity;
+    pub fn set_tick(&mut self, tick: u64) {
+        self.current_tick = tick;
     }
 
-    pub fn pop(&mut self) -> Option<GameEvent> {
-        if self.read_idx == self.write_idx && self.events[self.read_idx].is_none() {
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    // Unread events are never overwritten; when full the new event is
+    // dropped and counted so the bus can report it.
+    pub fn push(&mut self, event: GameEvent) -> bool {
+        if self.len == self.capacity {
+            self.overflowed += 1;
+            return false;
+        }
+
+        let write_idx = (self.read_idx + self.len) % self.capacity;
+        self.events[write_idx] = Some(TimedEvent {
+            tick: self.current_tick,
+            event,
+        });
+        self.len += 1;
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<TimedEvent> {
+        if self.len == 0 {
             return None;
         }
 
         let event = self.events[self.read_idx].take();
-        if event.is_some() {
-            self.read_idx = (self.read_idx + 1) % self.capacity;
-        }
+        self.read_idx = (self.read_idx + 1) % self.capacity;
+        self.len -= 1;
         event
     }
 
-    #[allow(dead_code)]
-    pub fn peek(&self) -> Option<&GameEvent> {
+    pub fn peek(&self) -> Option<&TimedEvent> {
+        if self.len == 0 {
+            return None;
+        }
         self.events[self.read_idx].as_ref()
     }
 
-    #[allow(dead_code)]
     pub fn clear(&mut self) {
         for event in &mut self.events {
             *event = None;
         }
-        self.write_idx = 0;
         self.read_idx = 0;
+        self.len = 0;
     }
 
     pub fn is_empty(&self) -> bool {
-        self.read_idx == self.write_idx && self.events[self.read_idx].is_none()
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn overflowed(&self) -> usize {
+        self.overflowed
+    }
+
+    pub fn take_overflowed(&mut self) -> usize {
+        std::mem::take(&mut self.overflowed)
     }
 
     pub fn drain(&mut self) -> EventDrainIterator<'_> {
@@ -100,7 +217,7 @@ pub struct EventDrainIterator<'a> {
 }
 
 impl Iterator for EventDrainIterator<'_> {
-    type Item = GameEvent;
+    type Item = TimedEvent;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.queue.pop()
@@ -0,0 +1,90 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Difficulty {
+    pub food_speed: i16,
+    // Ticks a piece of food stays on the field before it jumps to a new
+    // edge; 0 leaves it in place until it is eaten or drifts out.
+    pub food_lifetime: u32,
+    pub tick_percent: u16,
+    pub cooldown_percent: u16,
+}
+
+impl Difficulty {
+    pub const fn new(
+        food_speed: i16,
+        food_lifetime: u32,
+        tick_percent: u16,
+        cooldown_percent: u16,
+    ) -> Self {
+        Self {
+            food_speed,
+            food_lifetime,
+            tick_percent,
+            cooldown_percent,
+        }
+    }
+
+    pub fn bullet_cooldown(&self, base: u8) -> u8 {
+        let scaled = (base as u32 * self.cooldown_percent as u32).div_ceil(100);
+        scaled.min(u8::MAX as u32) as u8
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::new(1, 0, 100, 100)
+    }
+}
+
+const DEFAULT_CURVE: [Difficulty; 8] = [
+    Difficulty::new(1, 0, 100, 100),
+    Difficulty::new(1, 0, 92, 100),
+    Difficulty::new(1, 60, 85, 100),
+    Difficulty::new(1, 50, 80, 67),
+    Difficulty::new(2, 45, 80, 67),
+    Difficulty::new(2, 40, 75, 67),
+    Difficulty::new(2, 35, 70, 34),
+    Difficulty::new(2, 30, 65, 34),
+];
+
+// Level N uses `steps[N - 1]`; levels past the end of the table keep the
+// last entry, so the curve flattens out instead of running away.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LevelCurve {
+    pub points_per_level: u32,
+    pub max_level: u32,
+    pub steps: Vec<Difficulty>,
+}
+
+impl LevelCurve {
+    pub fn flat() -> Self {
+        Self {
+            points_per_level: 1,
+            max_level: 1,
+            steps: vec![Difficulty::default()],
+        }
+    }
+
+    pub fn level_for_score(&self, score: u32) -> u32 {
+        let level = 1 + score / self.points_per_level.max(1);
+        level.min(self.max_level.max(1))
+    }
+
+    pub fn difficulty(&self, level: u32) -> Difficulty {
+        let idx = level.saturating_sub(1) as usize;
+        self.steps
+            .get(idx)
+            .or(self.steps.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for LevelCurve {
+    fn default() -> Self {
+        Self {
+            points_per_level: 10,
+            max_level: 10,
+            steps: DEFAULT_CURVE.to_vec(),
+        }
+    }
+}
@@ -1,7 +1,33 @@
+mod achievements;
 mod bullet_pool;
+mod collision;
+mod effects;
+mod event_bus;
+mod event_feed;
 mod events;
+mod levels;
+mod modes;
+mod objectives;
+mod occupancy;
 mod scope;
+mod scoring;
+mod spawner;
+mod stats;
+mod timings;
 
+pub use achievements::Achievement;
 pub use bullet_pool::BulletPool;
-pub use events::{DeathCause, EventQueue, GameEvent};
-pub use scope::Scope;
+pub use collision::{Collision, CollisionKind, CollisionWorld, Sweep, SUBSTEPS};
+pub use effects::{EffectLayer, EffectLayerView, Effects, ParticleKind};
+pub use event_bus::{EventBus, EventSubscriber, SharedSubscriber};
+pub use event_feed::EventFeed;
+pub use events::{DeathCause, EventQueue, GameEvent, TimedEvent};
+pub use levels::{Difficulty, LevelCurve};
+pub use modes::GameMode;
+pub use objectives::{Objective, RunTally};
+pub use occupancy::{OccupancyGrid, Occupant};
+pub use scope::{Scope, ScopeConfig, ScopeStyle};
+pub use scoring::{ScoreOutcome, Scoring, ScoringConfig};
+pub use spawner::{SpawnCandidate, SpawnContext, SpawnError, SpawnRules, Spawner};
+pub use stats::{LifetimeStats, StatsTracker};
+pub use timings::TimingWindow;
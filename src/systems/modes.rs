@@ -0,0 +1,114 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+const TIME_ATTACK_LIMIT: Duration = Duration::from_secs(120);
+const SURVIVAL_CUT_STEP: Duration = Duration::from_secs(30);
+const SURVIVAL_HUNGER: Duration = Duration::from_secs(10);
+
+// The rules a mode changes, asked for by `Game` at fixed points in its loop
+// so every mode shares the same simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    TimeAttack,
+    Survival,
+    Zen,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Classic,
+        GameMode::TimeAttack,
+        GameMode::Survival,
+        GameMode::Zen,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::TimeAttack => "time-attack",
+            GameMode::Survival => "survival",
+            GameMode::Zen => "zen",
+        }
+    }
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::TimeAttack => "Time Attack",
+            GameMode::Survival => "Survival",
+            GameMode::Zen => "Zen",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn time_limit(self) -> Option<Duration> {
+        match self {
+            GameMode::TimeAttack => Some(TIME_ATTACK_LIMIT),
+            GameMode::Classic | GameMode::Survival | GameMode::Zen => None,
+        }
+    }
+
+    pub fn has_death(self) -> bool {
+        self != GameMode::Zen
+    }
+
+    pub fn wraps_walls(self) -> bool {
+        self == GameMode::Zen
+    }
+
+    // How long the snake can go without eating before it loses a tail
+    // segment. Going hungry with only the head left is fatal, so a run that
+    // stops playing still ends.
+    pub fn hunger(self) -> Option<Duration> {
+        match self {
+            GameMode::Survival => Some(SURVIVAL_HUNGER),
+            GameMode::Classic | GameMode::TimeAttack | GameMode::Zen => None,
+        }
+    }
+
+    // Extra tail segments lost on top of a food cut.
+    pub fn extra_cut(self, elapsed: Duration) -> usize {
+        match self {
+            GameMode::Survival => (elapsed.as_secs() / SURVIVAL_CUT_STEP.as_secs()) as usize,
+            GameMode::Classic | GameMode::TimeAttack | GameMode::Zen => 0,
+        }
+    }
+
+    pub fn score(self, points: u32, elapsed: Duration) -> u32 {
+        match self {
+            GameMode::Survival => elapsed.as_secs().min(u32::MAX as u64) as u32,
+            GameMode::Classic | GameMode::TimeAttack | GameMode::Zen => points,
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|m| m.as_str()).collect();
+                format!(
+                    "unknown mode '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    Collect(u32),
+    BulletKills(u32),
+    Survive { seconds: u32 },
+    ReachLength(usize),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunTally {
+    pub collected: u32,
+    pub bullet_kills: u32,
+    pub elapsed: Duration,
+    pub length: usize,
+}
+
+impl Objective {
+    pub fn progress(&self, tally: &RunTally) -> (u64, u64) {
+        match *self {
+            Objective::Collect(n) => (tally.collected as u64, n as u64),
+            Objective::BulletKills(n) => (tally.bullet_kills as u64, n as u64),
+            Objective::Survive { seconds } => (tally.elapsed.as_secs(), seconds as u64),
+            Objective::ReachLength(n) => (tally.length as u64, n as u64),
+        }
+    }
+
+    pub fn is_met(&self, tally: &RunTally) -> bool {
+        let (current, target) = self.progress(tally);
+        current >= target
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Objective::Collect(n) => write!(f, "collect {}", n),
+            Objective::BulletKills(n) => write!(f, "shoot {}", n),
+            Objective::Survive { seconds } => write!(f, "survive {}s", seconds),
+            Objective::ReachLength(n) => write!(f, "reach length {}", n),
+        }
+    }
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let (Some(kind), Some(count), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!("expected '<kind> <count>', got '{}'", s));
+        };
+        let count: u32 = count
+            .parse()
+            .map_err(|_| format!("'{}' is not a valid count", count))?;
+        if count == 0 {
+            return Err("objective count must be at least 1".to_string());
+        }
+
+        match kind.to_ascii_lowercase().as_str() {
+            "collect" => Ok(Objective::Collect(count)),
+            "kills" | "shoot" => Ok(Objective::BulletKills(count)),
+            "survive" => Ok(Objective::Survive { seconds: count }),
+            "length" => Ok(Objective::ReachLength(count as usize)),
+            _ => Err(format!(
+                "unknown objective '{}', expected collect, kills, survive or length",
+                kind
+            )),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::core::{Edge, SegmentIndex, Vec2};
+use crate::entities::{Snake, Walls};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Occupant {
+    Wall,
+    Snake,
+    Entity,
+    Food,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Cell {
+    wall: bool,
+    // A count rather than a flag: in zen mode the body can cross itself.
+    snake: u16,
+    // Whose body it is. Snakes never share a cell, since another snake's
+    // body always blocks.
+    owner: usize,
+    // Which head step put the newest segment here. Segments leave from the
+    // tail end, so while the count is non-zero this one is still there.
+    stamp: u32,
+    // Active world entities standing here.
+    entities: u16,
+}
+
+// Which cells are taken, kept up to date as the snake and food move so
+// lookups never walk the snake. World entities are re-marked whenever they
+// may have moved. Bullets aren't tracked; they pass over everything and
+// never block a spawn.
+#[derive(Clone, Debug)]
+pub struct OccupancyGrid {
+    bounds: Vec2,
+    cells: Vec<Cell>,
+    food: Option<Vec2>,
+    snake_cells: usize,
+    // Bumped for every new head, one counter per snake; a segment's index
+    // is how many heads its snake has added since its own.
+    head_stamps: Vec<u32>,
+    // Where entities were last marked, so they can be cleared again.
+    entity_cells: Vec<Vec2>,
+}
+
+impl OccupancyGrid {
+    pub fn new(bounds: Vec2) -> Self {
+        let len = bounds.x.max(0) as usize * bounds.y.max(0) as usize;
+        Self {
+            bounds,
+            cells: vec![Cell::default(); len],
+            food: None,
+            snake_cells: 0,
+            head_stamps: Vec::new(),
+            entity_cells: Vec::new(),
+        }
+    }
+
+    pub fn build(bounds: Vec2, walls: &Walls, snake: &Snake) -> Self {
+        let mut grid = Self::new(bounds);
+        for &wall in walls.cells() {
+            grid.add_wall(wall);
+        }
+        grid.add_body(0, snake);
+        grid
+    }
+
+    // Marks a whole snake, tail first, as snake number `owner`.
+    pub fn add_body(&mut self, owner: usize, snake: &Snake) {
+        for &segment in snake.segments().iter().rev() {
+            self.add_snake(owner, segment);
+        }
+    }
+
+    pub fn bounds(&self) -> Vec2 {
+        self.bounds
+    }
+
+    fn index(&self, pos: Vec2) -> Option<usize> {
+        pos.in_bounds(self.bounds)
+            .then(|| pos.y as usize * self.bounds.x as usize + pos.x as usize)
+    }
+
+    fn cell(&self, pos: Vec2) -> Option<&Cell> {
+        self.index(pos).and_then(|i| self.cells.get(i))
+    }
+
+    fn cell_mut(&mut self, pos: Vec2) -> Option<&mut Cell> {
+        self.index(pos).and_then(|i| self.cells.get_mut(i))
+    }
+
+    pub fn add_wall(&mut self, pos: Vec2) {
+        if let Some(cell) = self.cell_mut(pos) {
+            cell.wall = true;
+        }
+    }
+
+    // Adds a new head for snake `owner`; the rest of its body shifts one
+    // index further from it.
+    pub fn add_snake(&mut self, owner: usize, pos: Vec2) {
+        if self.head_stamps.len() <= owner {
+            self.head_stamps.resize(owner + 1, 0);
+        }
+        let Some(head_stamp) = self.head_stamps.get_mut(owner) else {
+            return;
+        };
+        *head_stamp = head_stamp.wrapping_add(1);
+        let stamp = *head_stamp;
+        if let Some(cell) = self.cell_mut(pos) {
+            cell.snake = cell.snake.saturating_add(1);
+            cell.owner = owner;
+            cell.stamp = stamp;
+            self.snake_cells += 1;
+        }
+    }
+
+    pub fn remove_snake(&mut self, pos: Vec2) {
+        if let Some(cell) = self.cell_mut(pos) {
+            if cell.snake > 0 {
+                cell.snake -= 1;
+                self.snake_cells -= 1;
+            }
+        }
+    }
+
+    // Replaces every entity mark with the given cells.
+    pub fn set_entities(&mut self, cells: impl IntoIterator<Item = Vec2>) {
+        for pos in std::mem::take(&mut self.entity_cells) {
+            if let Some(cell) = self.cell_mut(pos) {
+                cell.entities = 0;
+            }
+        }
+        for pos in cells {
+            if let Some(cell) = self.cell_mut(pos) {
+                cell.entities = cell.entities.saturating_add(1);
+                self.entity_cells.push(pos);
+            }
+        }
+    }
+
+    pub fn set_food(&mut self, food: Option<Vec2>) {
+        self.food = food.filter(|pos| pos.in_bounds(self.bounds));
+    }
+
+    // Walls outrank the snake, then entities, then food, when cells are
+    // shared.
+    pub fn occupant(&self, pos: Vec2) -> Option<Occupant> {
+        let cell = self.cell(pos)?;
+        if cell.wall {
+            Some(Occupant::Wall)
+        } else if cell.snake > 0 {
+            Some(Occupant::Snake)
+        } else if cell.entities > 0 {
+            Some(Occupant::Entity)
+        } else if self.food == Some(pos) {
+            Some(Occupant::Food)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_wall(&self, pos: Vec2) -> bool {
+        self.cell(pos).is_some_and(|cell| cell.wall)
+    }
+
+    pub fn has_snake(&self, pos: Vec2) -> bool {
+        self.cell(pos).is_some_and(|cell| cell.snake > 0)
+    }
+
+    pub fn has_entity(&self, pos: Vec2) -> bool {
+        self.cell(pos).is_some_and(|cell| cell.entities > 0)
+    }
+
+    // The segment on `pos`, counted from its snake's head. Where a body
+    // crosses itself, the one nearest the head.
+    pub fn segment_index(&self, pos: Vec2) -> Option<SegmentIndex> {
+        self.segment_at(pos).map(|(_, index)| index)
+    }
+
+    // Whose segment is on `pos`, and which one.
+    pub fn segment_at(&self, pos: Vec2) -> Option<(usize, SegmentIndex)> {
+        let cell = self.cell(pos).filter(|cell| cell.snake > 0)?;
+        let head_stamp = self.head_stamps.get(cell.owner).copied().unwrap_or(0);
+        Some((
+            cell.owner,
+            SegmentIndex::new(head_stamp.wrapping_sub(cell.stamp) as usize),
+        ))
+    }
+
+    // Cells a snake can't enter: outside the arena, walls and bodies.
+    pub fn is_blocked(&self, pos: Vec2) -> bool {
+        self.cell(pos)
+            .is_none_or(|cell| cell.wall || cell.snake > 0)
+    }
+
+    // Entities don't block the snake, but nothing spawns on them.
+    pub fn is_free(&self, pos: Vec2) -> bool {
+        !self.is_blocked(pos) && self.food != Some(pos) && !self.has_entity(pos)
+    }
+
+    // Counts segments, so a body crossing itself is counted twice.
+    pub fn snake_cells(&self) -> usize {
+        self.snake_cells
+    }
+
+    // In order along the edge, corners included.
+    pub fn free_on_edge(&self, edge: Edge) -> Vec<Vec2> {
+        let Vec2 { x: w, y: h } = self.bounds;
+        let (start, step, len) = match edge {
+            Edge::Top => (Vec2::new(0, 0), Vec2::new(1, 0), w),
+            Edge::Bottom => (Vec2::new(0, h - 1), Vec2::new(1, 0), w),
+            Edge::Left => (Vec2::new(0, 0), Vec2::new(0, 1), h),
+            Edge::Right => (Vec2::new(w - 1, 0), Vec2::new(0, 1), h),
+        };
+        (0..len)
+            .map(|i| start + step * i)
+            .filter(|&pos| self.is_free(pos))
+            .collect()
+    }
+
+    // Searches outward in square rings, so "nearest" is by king moves; ties
+    // go to the first cell clockwise from the top-left of the ring.
+    pub fn nearest_free(&self, from: Vec2) -> Option<Vec2> {
+        let reach = self.bounds.x.max(self.bounds.y);
+        (0..=reach).find_map(|radius| {
+            ring(from, radius)
+                .into_iter()
+                .find(|&pos| self.is_free(pos))
+        })
+    }
+}
+
+fn ring(center: Vec2, radius: i16) -> Vec<Vec2> {
+    if radius == 0 {
+        return vec![center];
+    }
+    let (left, right) = (center.x - radius, center.x + radius);
+    let (top, bottom) = (center.y - radius, center.y + radius);
+    let mut cells = Vec::with_capacity(radius as usize * 8);
+    cells.extend((left..right).map(|x| Vec2::new(x, top)));
+    cells.extend((top..bottom).map(|y| Vec2::new(right, y)));
+    cells.extend((left + 1..=right).rev().map(|x| Vec2::new(x, bottom)));
+    cells.extend((top + 1..=bottom).rev().map(|y| Vec2::new(left, y)));
+    cells
+}
@@ -1,23 +1,89 @@
 use crate::core::{Direction, Vec2};
-use crate::traits::Renderable;
-use ratatui::{buffer::Buffer, style::Color, style::Style};
+use crate::renderer::{RenderContext, RenderLayer};
+use crate::traits::{Layered, Renderable};
+use ratatui::{buffer::Buffer, style::Style};
+use std::fmt;
+use std::str::FromStr;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ScopeConfig {
-    pub aligned_color: Color,
-    pub unaligned_color: Color,
     pub dot_spacing: usize,
+    pub visible: bool,
 }
 
 impl Default for ScopeConfig {
     fn default() -> Self {
-        Self {
-            aligned_color: Color::Green,
-            unaligned_color: Color::White,
-            dot_spacing: 2,
+        ScopeStyle::default().config()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ScopeStyle {
+    #[default]
+    Dotted,
+    Solid,
+    Sparse,
+    Off,
+}
+
+impl ScopeStyle {
+    pub const ALL: [ScopeStyle; 4] = [
+        ScopeStyle::Dotted,
+        ScopeStyle::Solid,
+        ScopeStyle::Sparse,
+        ScopeStyle::Off,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ScopeStyle::Dotted => "dotted",
+            ScopeStyle::Solid => "solid",
+            ScopeStyle::Sparse => "sparse",
+            ScopeStyle::Off => "off",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn config(self) -> ScopeConfig {
+        let (dot_spacing, visible) = match self {
+            ScopeStyle::Dotted => (2, true),
+            ScopeStyle::Solid => (1, true),
+            ScopeStyle::Sparse => (4, true),
+            ScopeStyle::Off => (2, false),
+        };
+        ScopeConfig {
+            dot_spacing,
+            visible,
         }
     }
 }
 
+impl fmt::Display for ScopeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ScopeStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|style| style.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown scope style '{}', expected dotted, solid, sparse or off",
+                    s
+                )
+            })
+    }
+}
+
 pub struct Scope {
     origin: Vec2,
     direction: Direction,
@@ -68,6 +134,14 @@ impl Scope {
         self.is_aligned
     }
 
+    pub fn config(&self) -> ScopeConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: ScopeConfig) {
+        self.config = config;
+    }
+
     pub fn ray_cast(&self) -> RayCastIterator {
         RayCastIterator {
             current: self.origin,
@@ -77,7 +151,6 @@ impl Scope {
         }
     }
 
-    #[allow(dead_code)]
     pub fn distance_to_target(&self) -> Option<i32> {
         self.target.map(|t| {
             let diff = t - self.origin;
@@ -92,20 +165,24 @@ impl Default for Scope {
     }
 }
 
+impl Layered for Scope {
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Guides
+    }
+}
+
 impl Renderable for Scope {
-    fn render(&self, offset: Vec2, buf: &mut Buffer) {
-        let color = if self.is_aligned {
-            self.config.aligned_color
-        } else {
-            self.config.unaligned_color
-        };
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
+        if !self.config.visible {
+            return;
+        }
 
-        let style = Style::default().fg(color);
-        let glyph = "::";
+        let style = Style::default().fg(ctx.theme.scope(self.is_aligned));
+        let glyph = ctx.glyphs.scope_dot();
 
         for (i, point) in self.ray_cast().enumerate() {
-            if i % self.config.dot_spacing == 0 {
-                let (x, y) = point.to_screen(offset);
+            if i % self.config.dot_spacing.max(1) == 0 {
+                let (x, y) = point.to_screen(ctx.offset);
                 buf.set_string(x, y, glyph, style);
             }
         }
@@ -137,7 +214,6 @@ impl Iterator for RayCastIterator {
 }
 
 impl RayCastIterator {
-    #[allow(dead_code)]
     pub fn hits_target(&mut self, target: Vec2) -> bool {
         self.any(|pos| pos == target)
     }
@@ -0,0 +1,129 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoringConfig {
+    pub head_pickup: u32,
+    pub bullet_kill: u32,
+    pub cells_per_distance_bonus: i32,
+    pub max_distance_bonus: u32,
+    pub hits_per_multiplier: u32,
+    pub max_multiplier: u32,
+    pub penalty_per_segment: u32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            head_pickup: 1,
+            bullet_kill: 2,
+            cells_per_distance_bonus: 5,
+            max_distance_bonus: 4,
+            hits_per_multiplier: 2,
+            max_multiplier: 5,
+            penalty_per_segment: 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScoreOutcome {
+    pub points: u32,
+    pub multiplier: u32,
+    pub combo_started: bool,
+    pub combo_broken: Option<u32>,
+}
+
+// A streak counts consecutive pickups; any missed shot or damage ends it.
+// The multiplier climbs one step every `hits_per_multiplier` hits, and a
+// combo is "on" while it is above 1.
+#[derive(Clone, Debug, Default)]
+pub struct Scoring {
+    config: ScoringConfig,
+    score: u32,
+    streak: u32,
+    best_streak: u32,
+}
+
+impl Scoring {
+    pub fn new(config: ScoringConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
+
+    pub fn best_streak(&self) -> u32 {
+        self.best_streak
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier_for(self.streak)
+    }
+
+    pub fn in_combo(&self) -> bool {
+        self.multiplier() > 1
+    }
+
+    fn multiplier_for(&self, streak: u32) -> u32 {
+        let step = self.config.hits_per_multiplier.max(1);
+        (1 + streak.saturating_sub(1) / step).min(self.config.max_multiplier.max(1))
+    }
+
+    pub fn food_collected(&mut self, by_bullet: bool, shot_distance: i32) -> ScoreOutcome {
+        let was_in_combo = self.in_combo();
+        self.streak += 1;
+        self.best_streak = self.best_streak.max(self.streak);
+
+        let base = if by_bullet {
+            let step = self.config.cells_per_distance_bonus.max(1);
+            let bonus = (shot_distance.max(0) / step) as u32;
+            self.config.bullet_kill + bonus.min(self.config.max_distance_bonus)
+        } else {
+            self.config.head_pickup
+        };
+
+        let multiplier = self.multiplier();
+        let points = base * multiplier;
+        self.score = self.score.saturating_add(points);
+
+        ScoreOutcome {
+            points,
+            multiplier,
+            combo_started: !was_in_combo && self.in_combo(),
+            combo_broken: None,
+        }
+    }
+
+    pub fn shot_missed(&mut self) -> ScoreOutcome {
+        ScoreOutcome {
+            multiplier: 1,
+            combo_broken: self.break_streak(),
+            ..ScoreOutcome::default()
+        }
+    }
+
+    pub fn damaged(&mut self, segments_lost: usize) -> ScoreOutcome {
+        let penalty = self
+            .config
+            .penalty_per_segment
+            .saturating_mul(segments_lost as u32);
+        self.score = self.score.saturating_sub(penalty);
+        ScoreOutcome {
+            multiplier: 1,
+            combo_broken: self.break_streak(),
+            ..ScoreOutcome::default()
+        }
+    }
+
+    fn break_streak(&mut self) -> Option<u32> {
+        let broken = self.in_combo().then_some(self.streak);
+        self.streak = 0;
+        broken
+    }
+}
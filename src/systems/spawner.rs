@@ -0,0 +1,142 @@
+use super::occupancy::OccupancyGrid;
+use crate::core::{Direction, Edge, Vec2};
+use rand::Rng;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpawnRules {
+    // Manhattan distance food must keep from the head.
+    pub min_head_distance: i32,
+    // Cells straight ahead of the head that food may not land on.
+    pub head_lookahead: i16,
+    // Ticks of the food's own flight that must stay clear of the snake.
+    pub clear_ticks: i16,
+}
+
+impl SpawnRules {
+    pub const fn new() -> Self {
+        Self {
+            min_head_distance: 4,
+            head_lookahead: 3,
+            clear_ticks: 2,
+        }
+    }
+
+    // Anything free will do.
+    pub const fn none() -> Self {
+        Self {
+            min_head_distance: 0,
+            head_lookahead: 0,
+            clear_ticks: 0,
+        }
+    }
+}
+
+impl Default for SpawnRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpawnCandidate {
+    pub position: Vec2,
+    pub edge: Edge,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpawnError {
+    // Free cells exist, but none on an edge passes the rules.
+    NoFairCell,
+    // Nothing is free anywhere; the player has filled the arena.
+    BoardFull,
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::NoFairCell => f.write_str("no fair spawn cell"),
+            SpawnError::BoardFull => f.write_str("board is full"),
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
+
+// What the spawner needs to know about the snake at the moment of spawning.
+#[derive(Clone, Copy, Debug)]
+pub struct SpawnContext<'a> {
+    pub grid: &'a OccupancyGrid,
+    pub head: Vec2,
+    pub heading: Direction,
+    pub food_speed: i16,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Spawner {
+    rules: SpawnRules,
+}
+
+impl Spawner {
+    pub fn new(rules: SpawnRules) -> Self {
+        Self { rules }
+    }
+
+    pub fn rules(&self) -> SpawnRules {
+        self.rules
+    }
+
+    // Every edge cell that's free and passes the rules, edge by edge.
+    pub fn candidates(&self, ctx: &SpawnContext) -> Vec<SpawnCandidate> {
+        Edge::ALL
+            .iter()
+            .flat_map(|&edge| {
+                ctx.grid
+                    .free_on_edge(edge)
+                    .into_iter()
+                    .map(move |position| SpawnCandidate { position, edge })
+            })
+            .filter(|candidate| self.is_fair(ctx, candidate))
+            .collect()
+    }
+
+    pub fn is_fair(&self, ctx: &SpawnContext, candidate: &SpawnCandidate) -> bool {
+        let rules = self.rules;
+        let pos = candidate.position;
+        if ctx.head.manhattan_distance(pos) < rules.min_head_distance {
+            return false;
+        }
+        let ahead = ctx.heading.to_vec2();
+        if (1..=rules.head_lookahead).any(|i| ctx.head + ahead * i == pos) {
+            return false;
+        }
+        let flight = candidate.edge.to_direction().to_vec2();
+        let cells = ctx.food_speed.max(0) * rules.clear_ticks;
+        !(1..=cells).any(|i| ctx.grid.has_snake(pos + flight * i))
+    }
+
+    // Picks an edge at random among those with a fair cell, then a cell on
+    // it, so short edges get as much food as long ones.
+    pub fn choose(
+        &self,
+        ctx: &SpawnContext,
+        rng: &mut impl Rng,
+    ) -> Result<SpawnCandidate, SpawnError> {
+        let candidates = self.candidates(ctx);
+        let edges: Vec<Edge> = Edge::ALL
+            .into_iter()
+            .filter(|&edge| candidates.iter().any(|c| c.edge == edge))
+            .collect();
+        if edges.is_empty() {
+            let center = Vec2::new(ctx.grid.bounds().x / 2, ctx.grid.bounds().y / 2);
+            return Err(match ctx.grid.nearest_free(center) {
+                Some(_) => SpawnError::NoFairCell,
+                None => SpawnError::BoardFull,
+            });
+        }
+        let edge = edges[rng.random_range(0..edges.len())];
+        let on_edge: Vec<SpawnCandidate> =
+            candidates.into_iter().filter(|c| c.edge == edge).collect();
+        Ok(on_edge[rng.random_range(0..on_edge.len())])
+    }
+}
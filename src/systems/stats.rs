@@ -0,0 +1,156 @@
+use super::achievements::Achievement;
+use super::event_bus::EventSubscriber;
+use super::events::{DeathCause, GameEvent, TimedEvent};
+use std::collections::BTreeSet;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LifetimeStats {
+    pub shots_fired: u64,
+    pub bullet_collections: u64,
+    pub head_collections: u64,
+    pub times_damaged: u64,
+    pub segments_lost: u64,
+    pub deaths_by_wall: u64,
+    pub deaths_by_self: u64,
+    pub deaths_by_starving: u64,
+    pub longest_snake: u64,
+}
+
+impl LifetimeStats {
+    pub fn record(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::FoodCollected { by_bullet, .. } => {
+                if by_bullet {
+                    self.bullet_collections += 1;
+                } else {
+                    self.head_collections += 1;
+                }
+            }
+            GameEvent::BulletFired { .. } => self.shots_fired += 1,
+            GameEvent::SnakeDamaged { segments_lost, .. } => {
+                self.times_damaged += 1;
+                self.segments_lost += segments_lost as u64;
+            }
+            GameEvent::SnakeGrew { length } => {
+                self.longest_snake = self.longest_snake.max(length as u64);
+            }
+            GameEvent::SnakeDied { cause } => match cause {
+                DeathCause::HitWall => self.deaths_by_wall += 1,
+                DeathCause::HitSelf => self.deaths_by_self += 1,
+                DeathCause::Starved => self.deaths_by_starving += 1,
+                // Only bot arenas have more than one snake, and they keep
+                // no stats.
+                DeathCause::HitSnake => {}
+            },
+            GameEvent::Collision { .. }
+            | GameEvent::ComboStarted { .. }
+            | GameEvent::ComboBroken { .. }
+            | GameEvent::LevelUp { .. }
+            | GameEvent::ObjectiveCompleted
+            | GameEvent::BoardFilled
+            | GameEvent::MoveBlocked { .. }
+            | GameEvent::TimeUp
+            | GameEvent::GamePaused
+            | GameEvent::GameResumed
+            | GameEvent::GameRestarted
+            | GameEvent::EventsDropped { .. } => {}
+        }
+    }
+
+    pub fn merge(&mut self, other: &LifetimeStats) {
+        self.shots_fired += other.shots_fired;
+        self.bullet_collections += other.bullet_collections;
+        self.head_collections += other.head_collections;
+        self.times_damaged += other.times_damaged;
+        self.segments_lost += other.segments_lost;
+        self.deaths_by_wall += other.deaths_by_wall;
+        self.deaths_by_self += other.deaths_by_self;
+        self.deaths_by_starving += other.deaths_by_starving;
+        self.longest_snake = self.longest_snake.max(other.longest_snake);
+    }
+
+    pub fn total_collections(&self) -> u64 {
+        self.bullet_collections + self.head_collections
+    }
+
+    pub fn total_deaths(&self) -> u64 {
+        self.deaths_by_wall + self.deaths_by_self + self.deaths_by_starving
+    }
+
+    pub fn accuracy(&self) -> Option<f32> {
+        if self.shots_fired == 0 {
+            return None;
+        }
+        Some(self.bullet_collections as f32 / self.shots_fired as f32)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Default)]
+pub struct StatsTracker {
+    lifetime: LifetimeStats,
+    unsaved: LifetimeStats,
+    unlocked: BTreeSet<Achievement>,
+    newly_unlocked: Vec<Achievement>,
+}
+
+impl StatsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_lifetime(lifetime: LifetimeStats, unlocked: BTreeSet<Achievement>) -> Self {
+        Self {
+            lifetime,
+            unlocked,
+            ..Self::default()
+        }
+    }
+
+    pub fn record(&mut self, event: &GameEvent) {
+        self.lifetime.record(event);
+        self.unsaved.record(event);
+
+        for achievement in Achievement::ALL {
+            if !self.unlocked.contains(&achievement) && achievement.is_met(&self.lifetime) {
+                self.unlocked.insert(achievement);
+                self.newly_unlocked.push(achievement);
+            }
+        }
+    }
+
+    pub fn lifetime(&self) -> &LifetimeStats {
+        &self.lifetime
+    }
+
+    pub fn unsaved(&self) -> &LifetimeStats {
+        &self.unsaved
+    }
+
+    pub fn unlocked(&self) -> &BTreeSet<Achievement> {
+        &self.unlocked
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    pub fn take_newly_unlocked(&mut self) -> Vec<Achievement> {
+        std::mem::take(&mut self.newly_unlocked)
+    }
+
+    pub fn mark_saved(&mut self, lifetime: LifetimeStats, unlocked: BTreeSet<Achievement>) {
+        self.lifetime = lifetime;
+        self.unlocked.extend(unlocked);
+        self.unsaved = LifetimeStats::default();
+    }
+}
+
+impl EventSubscriber for StatsTracker {
+    fn on_event(&mut self, event: &TimedEvent) {
+        self.record(&event.event);
+    }
+}
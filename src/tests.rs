@@ -456,30 +456,26 @@ mod bullet_pool_tests {
     }
 
     #[test]
-    fn bullet_pool_check_collision_direct_hit() {
+    fn bullet_pool_deactivates_by_index() {
         let mut pool = BulletPool::new(10);
         pool.spawn(Vec2::new(5, 5), Direction::Right);
-        pool.tick(Vec2::new(20, 20));
-        let hit = pool.check_collision(Vec2::new(7, 5));
-        assert!(hit);
-    }
+        pool.spawn(Vec2::new(5, 7), Direction::Right);
+        let indices: Vec<usize> = pool.indexed().map(|(i, _)| i).collect();
+        assert_eq!(indices, vec![0, 1]);
 
-    #[test]
-    fn bullet_pool_check_collision_misses() {
-        let mut pool = BulletPool::new(10);
-        pool.spawn(Vec2::new(5, 5), Direction::Right);
-        pool.tick(Vec2::new(20, 20));
-        let hit = pool.check_collision(Vec2::new(10, 10));
-        assert!(!hit);
+        pool.deactivate(0);
+        assert!(pool.get(0).is_none());
+        assert!(pool.get(1).is_some());
+        assert_eq!(pool.active_count(), 1);
     }
 
     #[test]
-    fn bullet_pool_check_collision_line_intersect() {
+    fn bullet_pool_tick_counts_expired_bullets() {
         let mut pool = BulletPool::new(10);
+        pool.spawn(Vec2::new(18, 5), Direction::Right);
         pool.spawn(Vec2::new(5, 5), Direction::Right);
-        pool.tick(Vec2::new(20, 20));
-        let hit = pool.check_collision(Vec2::new(6, 5));
-        assert!(hit);
+        assert_eq!(pool.tick(Vec2::new(20, 20)), vec![0]);
+        assert!(pool.tick(Vec2::new(20, 20)).is_empty());
     }
 }
 
@@ -639,7 +635,7 @@ mod event_queue_tests {
     fn event_queue_pop_returns_pushed() {
         let mut queue = EventQueue::new(10);
         queue.push(GameEvent::GamePaused);
-        let event = queue.pop();
+        let event = queue.pop().map(|e| e.event);
         assert_eq!(event, Some(GameEvent::GamePaused));
     }
 
@@ -648,8 +644,8 @@ mod event_queue_tests {
         let mut queue = EventQueue::new(10);
         queue.push(GameEvent::GamePaused);
         queue.push(GameEvent::GameResumed);
-        assert_eq!(queue.pop(), Some(GameEvent::GamePaused));
-        assert_eq!(queue.pop(), Some(GameEvent::GameResumed));
+        assert_eq!(queue.pop().map(|e| e.event), Some(GameEvent::GamePaused));
+        assert_eq!(queue.pop().map(|e| e.event), Some(GameEvent::GameResumed));
     }
 
     #[test]
@@ -678,7 +674,119 @@ mod event_queue_tests {
             position: Vec2::new(0, 0),
             by_bullet: false,
         });
-        assert_eq!(queue.pop(), Some(GameEvent::GameResumed));
+        assert_eq!(queue.pop().map(|e| e.event), Some(GameEvent::GameResumed));
+    }
+
+    #[test]
+    fn event_queue_full_drops_newest_and_counts() {
+        let mut queue = EventQueue::new(2);
+        assert!(queue.push(GameEvent::GamePaused));
+        assert!(queue.push(GameEvent::GameResumed));
+        assert!(!queue.push(GameEvent::GameRestarted));
+        assert_eq!(queue.overflowed(), 1);
+        assert_eq!(queue.pop().map(|e| e.event), Some(GameEvent::GamePaused));
+        assert_eq!(queue.pop().map(|e| e.event), Some(GameEvent::GameResumed));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn event_queue_stamps_current_tick() {
+        let mut queue = EventQueue::new(4);
+        queue.set_tick(7);
+        queue.push(GameEvent::GamePaused);
+        assert_eq!(queue.pop().map(|e| e.tick), Some(7));
+    }
+
+    #[test]
+    fn event_queue_len_tracks_occupancy() {
+        let mut queue = EventQueue::new(4);
+        queue.push(GameEvent::GamePaused);
+        queue.push(GameEvent::GamePaused);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.capacity(), 4);
+    }
+}
+
+mod event_bus_tests {
+    use crate::game::{Game, GameBuilder};
+    use crate::systems::{EventBus, EventQueue, EventSubscriber, GameEvent, TimedEvent};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<TimedEvent>,
+    }
+
+    impl EventSubscriber for Recorder {
+        fn on_event(&mut self, event: &TimedEvent) {
+            self.events.push(*event);
+        }
+    }
+
+    #[test]
+    fn bus_delivers_to_every_subscriber() {
+        let a = Rc::new(RefCell::new(Recorder::default()));
+        let b = Rc::new(RefCell::new(Recorder::default()));
+        let mut bus = EventBus::new();
+        bus.subscribe(a.clone());
+        bus.subscribe(b.clone());
+
+        let mut queue = EventQueue::new(4);
+        queue.push(GameEvent::GamePaused);
+        queue.push(GameEvent::GameResumed);
+
+        assert_eq!(bus.dispatch(&mut queue), 2);
+        assert_eq!(a.borrow().events.len(), 2);
+        assert_eq!(b.borrow().events.len(), 2);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn bus_reports_dropped_events() {
+        let rec = Rc::new(RefCell::new(Recorder::default()));
+        let mut bus = EventBus::new();
+        bus.subscribe(rec.clone());
+
+        let mut queue = EventQueue::new(1);
+        queue.push(GameEvent::GamePaused);
+        queue.push(GameEvent::GameResumed);
+        queue.push(GameEvent::GameResumed);
+        bus.dispatch(&mut queue);
+
+        let last = rec.borrow().events.last().map(|e| e.event);
+        assert_eq!(last, Some(GameEvent::EventsDropped { count: 2 }));
+        assert_eq!(bus.dropped(), 2);
+        assert_eq!(queue.overflowed(), 0);
+    }
+
+    #[test]
+    fn game_events_carry_tick_number() {
+        let mut game = Game::new(40, 40);
+        game.tick();
+        game.tick();
+        game.toggle_pause();
+        let event = game.events().pop();
+        assert_eq!(
+            event,
+            Some(TimedEvent {
+                tick: 2,
+                event: GameEvent::GamePaused
+            })
+        );
+    }
+
+    #[test]
+    fn restart_keeps_configured_capacity_and_pending_events() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 15)
+            .with_event_capacity(5)
+            .build();
+        game.toggle_pause();
+        game.restart();
+        assert_eq!(game.event_queue().capacity(), 5);
+        let events: Vec<_> = game.events().drain().map(|e| e.event).collect();
+        assert_eq!(events, [GameEvent::GamePaused, GameEvent::GameRestarted]);
     }
 }
 
@@ -914,9 +1022,3584 @@ mod input_tests {
         );
     }
 
+    #[test]
+    fn input_mapper_stats_tab() {
+        let mapper = DefaultInputMapper;
+        assert_eq!(mapper.map(KeyCode::Tab), Some(GameAction::Stats));
+    }
+
+    #[test]
+    fn input_mapper_cycle_theme_t() {
+        let mapper = DefaultInputMapper;
+        assert_eq!(mapper.map(KeyCode::Char('t')), Some(GameAction::CycleTheme));
+    }
+
+    #[test]
+    fn input_mapper_cycle_glyphs_g() {
+        let mapper = DefaultInputMapper;
+        assert_eq!(
+            mapper.map(KeyCode::Char('g')),
+            Some(GameAction::CycleGlyphs)
+        );
+    }
+
+    #[test]
+    fn input_mapper_menu_m() {
+        let mapper = DefaultInputMapper;
+        assert_eq!(mapper.map(KeyCode::Char('m')), Some(GameAction::Menu));
+    }
+
+    #[test]
+    fn input_mapper_settings_o() {
+        let mapper = DefaultInputMapper;
+        assert_eq!(mapper.map(KeyCode::Char('o')), Some(GameAction::Settings));
+    }
+
+    #[test]
+    fn input_mapper_debug_f3() {
+        let mapper = DefaultInputMapper;
+        assert_eq!(mapper.map(KeyCode::F(3)), Some(GameAction::ToggleDebug));
+    }
+
     #[test]
     fn input_mapper_unknown_returns_none() {
         let mapper = DefaultInputMapper;
         assert_eq!(mapper.map(KeyCode::Char('z')), None);
     }
 }
+
+mod high_score_tests {
+    use crate::storage::{
+        sanitize_name, HighScores, ScoreEntry, ScoreKey, ScoreStore, MAX_ENTRIES_PER_TABLE,
+    };
+    use std::path::PathBuf;
+
+    fn key() -> ScoreKey {
+        ScoreKey::new("classic", 2, 40, 20)
+    }
+
+    fn entry(name: &str, score: u32) -> ScoreEntry {
+        ScoreEntry {
+            name: name.to_string(),
+            score,
+            timestamp: 0,
+        }
+    }
+
+    fn temp_store(name: &str) -> (ScoreStore, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("cool-snake-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        (ScoreStore::new(dir.join("scores.tsv")), dir)
+    }
+
+    #[test]
+    fn zero_score_never_qualifies() {
+        let scores = HighScores::new();
+        assert!(!scores.qualifies(&key(), 0));
+    }
+
+    #[test]
+    fn any_score_qualifies_for_empty_table() {
+        let scores = HighScores::new();
+        assert!(scores.qualifies(&key(), 1));
+    }
+
+    #[test]
+    fn insert_orders_by_score_descending() {
+        let mut scores = HighScores::new();
+        scores.insert(key(), entry("a", 5));
+        scores.insert(key(), entry("b", 9));
+        let rank = scores.insert(key(), entry("c", 7));
+        assert_eq!(rank, Some(1));
+        let names: Vec<_> = scores
+            .table(&key())
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(names, ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn ties_rank_after_existing_entries() {
+        let mut scores = HighScores::new();
+        scores.insert(key(), entry("first", 5));
+        assert_eq!(scores.insert(key(), entry("second", 5)), Some(1));
+    }
+
+    #[test]
+    fn full_table_rejects_low_scores() {
+        let mut scores = HighScores::new();
+        for i in 0..MAX_ENTRIES_PER_TABLE as u32 {
+            scores.insert(key(), entry("p", 10 + i));
+        }
+        assert!(!scores.qualifies(&key(), 10));
+        assert_eq!(scores.insert(key(), entry("late", 10)), None);
+        assert_eq!(scores.insert(key(), entry("high", 100)), Some(0));
+        assert_eq!(scores.table(&key()).len(), MAX_ENTRIES_PER_TABLE);
+    }
+
+    #[test]
+    fn tables_are_keyed_separately() {
+        let mut scores = HighScores::new();
+        scores.insert(key(), entry("a", 5));
+        let other = ScoreKey::new("classic", 3, 40, 20);
+        assert!(scores.table(&other).is_empty());
+    }
+
+    #[test]
+    fn file_format_round_trips() {
+        let mut scores = HighScores::new();
+        scores.insert(key(), entry("with space", 12));
+        scores.insert(ScoreKey::new("classic", 1, 10, 10), entry("z", 3));
+        let parsed = HighScores::parse(&scores.to_file_string());
+        assert_eq!(parsed, scores);
+    }
+
+    #[test]
+    fn parse_skips_malformed_lines() {
+        let scores = HighScores::parse("garbage\nclassic\t2\t40\t20\t7\t0\tok\n");
+        assert_eq!(scores.table(&key()).len(), 1);
+    }
+
+    #[test]
+    fn sanitize_name_strips_control_and_truncates() {
+        assert_eq!(sanitize_name("  a\tb  "), "a b");
+        assert_eq!(sanitize_name(""), "anon");
+        assert_eq!(sanitize_name("abcdefghijklmnopq").len(), 12);
+    }
+
+    #[test]
+    fn store_submit_persists_entries() {
+        let (store, dir) = temp_store("submit");
+        assert_eq!(store.submit(key(), entry("a", 4)).ok(), Some(Some(0)));
+        assert_eq!(store.submit(key(), entry("b", 8)).ok(), Some(Some(0)));
+        let loaded = store.load().ok().unwrap_or_default();
+        assert_eq!(loaded.table(&key()).len(), 2);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn store_concurrent_submits_keep_every_entry() {
+        let (store, dir) = temp_store("concurrent");
+        let path = store.path().to_path_buf();
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    ScoreStore::new(path)
+                        .submit(key(), entry("t", i + 1))
+                        .is_ok()
+                })
+            })
+            .collect();
+        let all_ok = handles.into_iter().all(|h| h.join().unwrap_or(false));
+        assert!(all_ok);
+        let loaded = store.load().ok().unwrap_or_default();
+        assert_eq!(loaded.table(&key()).len(), 8);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+mod name_entry_tests {
+    use crate::ui::{NameEntry, NameEntryOutcome};
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn typing_and_backspace_edit_name() {
+        let mut entry = NameEntry::new(5, "");
+        entry.handle_key(KeyCode::Char('a'));
+        entry.handle_key(KeyCode::Char('b'));
+        entry.handle_key(KeyCode::Backspace);
+        assert_eq!(entry.name(), "a");
+    }
+
+    #[test]
+    fn enter_submits_and_esc_cancels() {
+        let mut entry = NameEntry::new(5, "x");
+        assert_eq!(
+            entry.handle_key(KeyCode::Enter),
+            NameEntryOutcome::Submitted
+        );
+        assert_eq!(entry.handle_key(KeyCode::Esc), NameEntryOutcome::Cancelled);
+    }
+
+    #[test]
+    fn name_length_is_capped() {
+        let mut entry = NameEntry::new(5, "");
+        for _ in 0..30 {
+            entry.handle_key(KeyCode::Char('z'));
+        }
+        assert_eq!(entry.name().len(), 12);
+    }
+}
+
+mod stats_tests {
+    use crate::core::{Direction, Vec2};
+    use crate::storage::{StatsSnapshot, StatsStore};
+    use crate::systems::{Achievement, DeathCause, GameEvent, LifetimeStats, StatsTracker};
+    use std::collections::BTreeSet;
+
+    fn fired() -> GameEvent {
+        GameEvent::BulletFired {
+            position: Vec2::zero(),
+            direction: Direction::Right,
+        }
+    }
+
+    fn collected(by_bullet: bool) -> GameEvent {
+        GameEvent::FoodCollected {
+            position: Vec2::zero(),
+            by_bullet,
+        }
+    }
+
+    #[test]
+    fn records_collections_by_source() {
+        let mut stats = LifetimeStats::default();
+        stats.record(&collected(true));
+        stats.record(&collected(false));
+        stats.record(&collected(false));
+        assert_eq!(stats.bullet_collections, 1);
+        assert_eq!(stats.head_collections, 2);
+        assert_eq!(stats.total_collections(), 3);
+    }
+
+    #[test]
+    fn accuracy_is_bullet_pickups_per_shot() {
+        let mut stats = LifetimeStats::default();
+        assert_eq!(stats.accuracy(), None);
+        for _ in 0..4 {
+            stats.record(&fired());
+        }
+        stats.record(&collected(true));
+        assert_eq!(stats.accuracy(), Some(0.25));
+    }
+
+    #[test]
+    fn records_damage_and_deaths() {
+        let mut stats = LifetimeStats::default();
+        stats.record(&GameEvent::SnakeDamaged {
+            position: Vec2::zero(),
+            segments_lost: 3,
+        });
+        stats.record(&GameEvent::SnakeDied {
+            cause: DeathCause::HitWall,
+        });
+        stats.record(&GameEvent::SnakeDied {
+            cause: DeathCause::HitSelf,
+        });
+        assert_eq!(stats.segments_lost, 3);
+        assert_eq!(stats.times_damaged, 1);
+        assert_eq!(stats.total_deaths(), 2);
+    }
+
+    #[test]
+    fn longest_snake_keeps_maximum() {
+        let mut stats = LifetimeStats::default();
+        stats.record(&GameEvent::SnakeGrew { length: 7 });
+        stats.record(&GameEvent::SnakeGrew { length: 3 });
+        assert_eq!(stats.longest_snake, 7);
+    }
+
+    #[test]
+    fn merge_sums_totals_and_maxes_length() {
+        let mut a = LifetimeStats {
+            shots_fired: 2,
+            longest_snake: 9,
+            ..Default::default()
+        };
+        let b = LifetimeStats {
+            shots_fired: 3,
+            longest_snake: 4,
+            ..Default::default()
+        };
+        a.merge(&b);
+        assert_eq!(a.shots_fired, 5);
+        assert_eq!(a.longest_snake, 9);
+    }
+
+    #[test]
+    fn tracker_unlocks_achievement_once() {
+        let mut tracker = StatsTracker::new();
+        tracker.record(&fired());
+        assert_eq!(tracker.take_newly_unlocked(), vec![Achievement::FirstShot]);
+        tracker.record(&fired());
+        assert!(tracker.take_newly_unlocked().is_empty());
+        assert!(tracker.is_unlocked(Achievement::FirstShot));
+    }
+
+    #[test]
+    fn tracker_threshold_achievement() {
+        let mut tracker = StatsTracker::new();
+        for _ in 0..9 {
+            tracker.record(&collected(true));
+        }
+        assert!(!tracker.is_unlocked(Achievement::Sharpshooter));
+        tracker.record(&collected(true));
+        assert!(tracker.is_unlocked(Achievement::Sharpshooter));
+    }
+
+    #[test]
+    fn achievement_ids_round_trip() {
+        for achievement in Achievement::ALL {
+            assert_eq!(Achievement::from_id(achievement.id()), Some(achievement));
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trips() {
+        let snapshot = StatsSnapshot {
+            stats: LifetimeStats {
+                shots_fired: 10,
+                deaths_by_self: 2,
+                longest_snake: 14,
+                ..Default::default()
+            },
+            achievements: BTreeSet::from([Achievement::FirstShot, Achievement::Ouroboros]),
+        };
+        assert_eq!(StatsSnapshot::parse(&snapshot.to_file_string()), snapshot);
+    }
+
+    #[test]
+    fn store_merge_accumulates_deltas() {
+        let dir = std::env::temp_dir().join(format!("cool-snake-stats-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = StatsStore::new(dir.join("stats.txt"));
+        let delta = LifetimeStats {
+            shots_fired: 5,
+            ..Default::default()
+        };
+        let _ = store.merge(&delta, &BTreeSet::new());
+        let merged = store.merge(&delta, &BTreeSet::from([Achievement::FirstShot]));
+        let merged = merged.ok().unwrap_or_default();
+        assert_eq!(merged.stats.shots_fired, 10);
+        assert!(merged.achievements.contains(&Achievement::FirstShot));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+mod toast_tests {
+    use crate::ui::Toasts;
+
+    #[test]
+    fn toasts_expire_after_ttl() {
+        let mut toasts = Toasts::with_ttl(2);
+        toasts.push("a", "b");
+        toasts.tick();
+        assert_eq!(toasts.len(), 1);
+        toasts.tick();
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn queued_toasts_wait_their_turn() {
+        let mut toasts = Toasts::with_ttl(1);
+        for _ in 0..5 {
+            toasts.push("a", "b");
+        }
+        toasts.tick();
+        assert_eq!(toasts.len(), 2);
+    }
+}
+
+mod theme_tests {
+    use crate::game::Game;
+    use crate::renderer::{GameRenderer, RenderConfig};
+    use crate::theme::{rgb_to_ansi256, ColorDepth, Theme, ThemeName};
+    use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+    #[test]
+    fn detects_truecolor_from_colorterm() {
+        let depth = ColorDepth::from_env(Some("truecolor"), Some("xterm"));
+        assert_eq!(depth, ColorDepth::TrueColor);
+    }
+
+    #[test]
+    fn detects_256_from_term() {
+        let depth = ColorDepth::from_env(None, Some("screen-256color"));
+        assert_eq!(depth, ColorDepth::Ansi256);
+    }
+
+    #[test]
+    fn linux_console_falls_back_to_16() {
+        assert_eq!(
+            ColorDepth::from_env(None, Some("linux")),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(ColorDepth::from_env(None, None), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn truecolor_keeps_rgb() {
+        let c = Color::Rgb(1, 2, 3);
+        assert_eq!(ColorDepth::TrueColor.adapt(c), c);
+    }
+
+    #[test]
+    fn ansi256_maps_rgb_to_cube_and_grays() {
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+    }
+
+    #[test]
+    fn ansi16_maps_rgb_to_named_colors() {
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Rgb(250, 10, 10)),
+            Color::LightRed
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Indexed(196)),
+            Color::LightRed
+        );
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Cyan), Color::Cyan);
+    }
+
+    #[test]
+    fn theme_never_emits_rgb_below_truecolor() {
+        for name in ThemeName::ALL {
+            for depth in [ColorDepth::Ansi256, ColorDepth::Ansi16] {
+                let theme = Theme::new(name, depth);
+                for color in [
+                    theme.snake_head(),
+                    theme.food(),
+                    theme.bullet(0.3),
+                    theme.scope(true),
+                    theme.hud(),
+                ] {
+                    assert!(!matches!(color, Color::Rgb(..)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bullet_color_fades_between_endpoints() {
+        let theme = Theme::default();
+        assert_eq!(theme.bullet(1.0), Color::Rgb(255, 255, 0));
+        assert_eq!(theme.bullet(0.0), Color::Rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn theme_names_parse_and_cycle() {
+        for name in ThemeName::ALL {
+            assert_eq!(name.as_str().parse::<ThemeName>(), Ok(name));
+        }
+        assert!("nope".parse::<ThemeName>().is_err());
+        assert_eq!(ThemeName::ColorBlind.next(), ThemeName::Default);
+    }
+
+    #[test]
+    fn renderer_uses_theme_colors() {
+        let game = Game::new(10, 5);
+        let theme = Theme::new(ThemeName::Monochrome, ColorDepth::Ansi16);
+        let area = Rect::new(0, 0, 22, 7);
+        let mut buf = Buffer::empty(area);
+        let config = RenderConfig {
+            theme,
+            ..RenderConfig::default()
+        };
+        GameRenderer::with_config(&game, config).render(area, &mut buf);
+
+        let head = game.snake().head();
+        let (x, y) = (1 + head.x as u16 * 2, 1 + head.y as u16);
+        assert_eq!(buf[(x, y)].fg, theme.snake_head());
+        assert_eq!(buf[(0, 0)].fg, theme.border());
+    }
+}
+
+mod glyph_tests {
+    use crate::core::{Direction, Vec2};
+    use crate::entities::Snake;
+    use crate::glyphs::GlyphSet;
+    use crate::renderer::RenderContext;
+    use crate::theme::Theme;
+    use crate::traits::Renderable;
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    fn render_row(snake: &Snake, glyphs: GlyphSet, width: u16, row: u16) -> String {
+        let theme = Theme::default();
+        let ctx = RenderContext {
+            offset: Vec2::zero(),
+            bounds: Vec2::new(width as i16 / 2, 4),
+            theme: &theme,
+            glyphs,
+        };
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, 4));
+        snake.render(&ctx, &mut buf);
+        (0..width)
+            .map(|x| buf[(x, row)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn ascii_set_is_pure_ascii() {
+        for facing in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            assert!(GlyphSet::Ascii.snake_head(facing, None).concat().is_ascii());
+        }
+        assert!(GlyphSet::Ascii.food().is_ascii());
+        assert!(GlyphSet::Ascii.bullet().is_ascii());
+        assert!(GlyphSet::Ascii.scope_dot().is_ascii());
+        let corner = GlyphSet::Ascii.snake_segment(Some(Direction::Up), Some(Direction::Right));
+        assert!(corner.concat().is_ascii());
+    }
+
+    #[test]
+    fn box_segment_follows_neighbours() {
+        let set = GlyphSet::BoxDrawing;
+        assert_eq!(
+            set.snake_segment(Some(Direction::Left), Some(Direction::Right)),
+            ["━", "━"]
+        );
+        assert_eq!(
+            set.snake_segment(Some(Direction::Up), Some(Direction::Down)),
+            ["┃", " "]
+        );
+        assert_eq!(
+            set.snake_segment(Some(Direction::Down), Some(Direction::Right)),
+            ["┏", "━"]
+        );
+        assert_eq!(
+            set.snake_segment(Some(Direction::Up), Some(Direction::Left)),
+            ["┛", " "]
+        );
+    }
+
+    #[test]
+    fn box_head_shows_facing() {
+        let set = GlyphSet::BoxDrawing;
+        assert_eq!(
+            set.snake_head(Direction::Right, Some(Direction::Left))[0],
+            "▶"
+        );
+        assert_eq!(
+            set.snake_head(Direction::Left, Some(Direction::Right)),
+            ["◀", "━"]
+        );
+    }
+
+    #[test]
+    fn box_snake_renders_connected_line() {
+        let mut snake = Snake::new(Vec2::new(0, 0));
+        snake.grow();
+        snake.grow();
+        snake.tick(Vec2::new(10, 4));
+        snake.tick(Vec2::new(10, 4));
+        assert_eq!(render_row(&snake, GlyphSet::BoxDrawing, 6, 0), "╺━━━▶ ");
+    }
+
+    #[test]
+    fn blocks_snake_matches_classic_look() {
+        let snake = Snake::new(Vec2::new(1, 0));
+        assert_eq!(render_row(&snake, GlyphSet::Blocks, 6, 0), "  ▓▓  ");
+    }
+
+    #[test]
+    fn direction_from_vec2_uses_sign() {
+        assert_eq!(Direction::from_vec2(Vec2::new(0, -3)), Some(Direction::Up));
+        assert_eq!(Direction::from_vec2(Vec2::new(1, 1)), None);
+        assert_eq!(Direction::from_vec2(Vec2::zero()), None);
+    }
+
+    #[test]
+    fn glyph_set_names_parse() {
+        for set in GlyphSet::ALL {
+            assert_eq!(set.as_str().parse::<GlyphSet>(), Ok(set));
+        }
+    }
+}
+
+mod effects_tests {
+    use crate::core::{Direction, Vec2};
+    use crate::glyphs::GlyphSet;
+    use crate::renderer::RenderContext;
+    use crate::systems::{
+        DeathCause, EffectLayer, Effects, EventSubscriber, GameEvent, TimedEvent,
+    };
+    use crate::theme::Theme;
+    use crate::traits::Renderable;
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    fn send(effects: &mut Effects, event: GameEvent) {
+        effects.on_event(&TimedEvent { tick: 0, event });
+    }
+
+    fn tick_n(effects: &mut Effects, n: usize) {
+        for _ in 0..n {
+            effects.tick();
+        }
+    }
+
+    #[test]
+    fn bullet_kills_burst_bigger_than_head_collections() {
+        let mut head = Effects::new();
+        send(
+            &mut head,
+            GameEvent::FoodCollected {
+                position: Vec2::new(3, 3),
+                by_bullet: false,
+            },
+        );
+
+        let mut bullet = Effects::new();
+        send(
+            &mut bullet,
+            GameEvent::FoodCollected {
+                position: Vec2::new(3, 3),
+                by_bullet: true,
+            },
+        );
+
+        assert!(head.active_count() > 0);
+        assert!(bullet.active_count() > head.active_count());
+    }
+
+    #[test]
+    fn firing_adds_muzzle_flash() {
+        let mut effects = Effects::new();
+        send(
+            &mut effects,
+            GameEvent::BulletFired {
+                position: Vec2::new(1, 1),
+                direction: Direction::Right,
+            },
+        );
+        assert_eq!(effects.active_count(), 1);
+    }
+
+    #[test]
+    fn damage_marks_each_lost_segment() {
+        let mut effects = Effects::new();
+        effects.observe_snake([
+            Vec2::new(5, 2),
+            Vec2::new(4, 2),
+            Vec2::new(3, 2),
+            Vec2::new(2, 2),
+        ]);
+        send(
+            &mut effects,
+            GameEvent::SnakeDamaged {
+                position: Vec2::new(4, 2),
+                segments_lost: 2,
+            },
+        );
+        // A flash and a piece of debris per lost segment.
+        assert_eq!(effects.active_count(), 4);
+    }
+
+    #[test]
+    fn death_animation_plays_then_finishes() {
+        let mut effects = Effects::new();
+        effects.observe_snake((0..6).map(|x| Vec2::new(x, 0)));
+        send(
+            &mut effects,
+            GameEvent::SnakeDied {
+                cause: DeathCause::HitWall,
+            },
+        );
+
+        assert!(effects.is_death_animation_playing());
+        tick_n(&mut effects, 2);
+        assert!(effects.is_death_animation_playing());
+        tick_n(&mut effects, 20);
+        assert!(!effects.is_death_animation_playing());
+        assert_eq!(effects.active_count(), 0);
+    }
+
+    #[test]
+    fn restart_clears_everything() {
+        let mut effects = Effects::new();
+        effects.observe_snake([Vec2::new(1, 1)]);
+        send(
+            &mut effects,
+            GameEvent::SnakeDied {
+                cause: DeathCause::HitSelf,
+            },
+        );
+        send(&mut effects, GameEvent::GameRestarted);
+        assert_eq!(effects.active_count(), 0);
+        assert!(!effects.is_death_animation_playing());
+    }
+
+    #[test]
+    fn particles_expire() {
+        let mut effects = Effects::new();
+        send(
+            &mut effects,
+            GameEvent::FoodCollected {
+                position: Vec2::new(3, 3),
+                by_bullet: true,
+            },
+        );
+        tick_n(&mut effects, 10);
+        assert_eq!(effects.active_count(), 0);
+    }
+
+    #[test]
+    fn sparks_leaving_the_board_are_not_drawn() {
+        let mut effects = Effects::new();
+        send(
+            &mut effects,
+            GameEvent::FoodCollected {
+                position: Vec2::new(0, 0),
+                by_bullet: true,
+            },
+        );
+        effects.tick();
+
+        let theme = Theme::default();
+        let ctx = RenderContext {
+            offset: Vec2::zero(),
+            bounds: Vec2::new(2, 2),
+            theme: &theme,
+            glyphs: GlyphSet::Ascii,
+        };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+        effects.layer(EffectLayer::Over).render(&ctx, &mut buf);
+        effects.layer(EffectLayer::Under).render(&ctx, &mut buf);
+
+        let drawn = (0..4)
+            .flat_map(|x| (0..2).map(move |y| (x, y)))
+            .filter(|&(x, y)| buf[(x, y)].symbol() != " ")
+            .count();
+        assert!(drawn > 0);
+    }
+}
+
+mod menu_tests {
+    use crate::theme::Theme;
+    use crate::ui::{Menu, MenuOutcome, Screen, ScreenStack, Themed};
+    use crossterm::event::KeyCode;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    fn menu() -> Menu {
+        Menu::new("TEST", ["One", "Two", "Three"])
+    }
+
+    #[test]
+    fn navigation_wraps_both_ways() {
+        let mut menu = menu();
+        assert_eq!(menu.handle_key(KeyCode::Up), MenuOutcome::None);
+        assert_eq!(menu.selected(), 2);
+        menu.handle_key(KeyCode::Char('j'));
+        assert_eq!(menu.selected(), 0);
+    }
+
+    #[test]
+    fn enter_selects_and_arrows_adjust() {
+        let mut menu = menu();
+        menu.handle_key(KeyCode::Down);
+        assert_eq!(menu.handle_key(KeyCode::Enter), MenuOutcome::Selected(1));
+        assert_eq!(menu.handle_key(KeyCode::Left), MenuOutcome::Adjust(1, -1));
+        assert_eq!(menu.handle_key(KeyCode::Right), MenuOutcome::Adjust(1, 1));
+        assert_eq!(menu.handle_key(KeyCode::Esc), MenuOutcome::Back);
+    }
+
+    #[test]
+    fn shrinking_items_clamps_selection() {
+        let mut menu = menu();
+        menu.select(2);
+        menu.set_items(["Only"]);
+        assert_eq!(menu.selected(), 0);
+        assert_eq!(menu.handle_key(KeyCode::Enter), MenuOutcome::Selected(0));
+    }
+
+    #[test]
+    fn renders_selected_marker() {
+        let mut menu = menu();
+        menu.select(1);
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 30, 10);
+        let mut buf = Buffer::empty(area);
+        Themed::new(&menu, &theme).render(area, &mut buf);
+
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(rows.iter().any(|r| r.contains("> Two")));
+        assert!(rows.iter().any(|r| r.contains("  One")));
+    }
+
+    #[test]
+    fn screen_stack_never_pops_title() {
+        let mut screens = ScreenStack::new();
+        assert_eq!(screens.current(), Screen::Title);
+        assert_eq!(screens.pop(), None);
+        assert_eq!(screens.current(), Screen::Title);
+    }
+
+    #[test]
+    fn screen_stack_navigates_and_returns() {
+        let mut screens = ScreenStack::new();
+        screens.push(Screen::ModeSelect);
+        screens.push(Screen::Help);
+        screens.push(Screen::Help);
+        assert_eq!(screens.depth(), 3);
+        assert_eq!(screens.pop(), Some(Screen::Help));
+        assert_eq!(screens.current(), Screen::ModeSelect);
+
+        screens.push(Screen::Game);
+        screens.return_to_title();
+        assert_eq!(screens.current(), Screen::Title);
+        assert_eq!(screens.depth(), 1);
+    }
+}
+
+mod settings_tests {
+    use crate::core::Direction;
+    use crate::game::GameBuilder;
+    use crate::glyphs::GlyphSet;
+    use crate::input::{key_name, parse_key, GameAction, InputMapper, KeyBindings};
+    use crate::renderer::HudLayout;
+    use crate::storage::{Settings, SettingsStore};
+    use crate::systems::ScopeStyle;
+    use crate::theme::ThemeName;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn custom_binding_overrides_and_replaces() {
+        let mut bindings = KeyBindings::new();
+        assert!(bindings.bind(GameAction::Fire, KeyCode::Char('e')));
+        assert_eq!(bindings.map(KeyCode::Char('e')), Some(GameAction::Fire));
+
+        assert!(bindings.bind(GameAction::Fire, KeyCode::Char('r')));
+        assert_eq!(bindings.map(KeyCode::Char('e')), None);
+        assert_eq!(bindings.key_for(GameAction::Fire), Some(KeyCode::Char('r')));
+
+        // Taking a key from another action moves it.
+        assert!(bindings.bind(GameAction::Pause, KeyCode::Char('r')));
+        assert_eq!(bindings.key_for(GameAction::Fire), None);
+        assert_eq!(bindings.map(KeyCode::Char('r')), Some(GameAction::Pause));
+    }
+
+    #[test]
+    fn reserved_keys_and_actions_cannot_be_bound() {
+        let mut bindings = KeyBindings::new();
+        assert!(!bindings.bind(GameAction::Fire, KeyCode::Char('q')));
+        assert!(!bindings.bind(GameAction::Fire, KeyCode::Esc));
+        assert!(!bindings.bind(GameAction::Fire, KeyCode::Char('o')));
+        assert!(!bindings.bind(GameAction::Quit, KeyCode::Char('z')));
+        assert!(bindings.is_empty());
+    }
+
+    #[test]
+    fn key_names_round_trip() {
+        for code in [
+            KeyCode::Char(' '),
+            KeyCode::Char('e'),
+            KeyCode::Char(';'),
+            KeyCode::Up,
+            KeyCode::Enter,
+        ] {
+            let name = key_name(code);
+            assert_eq!(name.as_deref().and_then(parse_key), Some(code));
+        }
+        assert_eq!(parse_key("ab"), None);
+    }
+
+    #[test]
+    fn settings_file_round_trips() {
+        let mut settings = Settings {
+            speed: 3,
+            theme: ThemeName::ColorBlind,
+            glyphs: GlyphSet::BoxDrawing,
+            scope: ScopeStyle::Sparse,
+            hud: HudLayout::Minimal,
+            ..Settings::default()
+        };
+        settings
+            .bindings
+            .bind(GameAction::Move(Direction::Up), KeyCode::Char('i'));
+
+        assert_eq!(Settings::parse(&settings.to_file_string()), settings);
+    }
+
+    #[test]
+    fn bad_settings_values_fall_back_to_defaults() {
+        let settings = Settings::parse("speed=9\ntheme=neon\nscope=off\nbind.fire=q\njunk\n");
+        assert_eq!(settings.speed, 2);
+        assert_eq!(settings.theme, ThemeName::Default);
+        assert_eq!(settings.scope, ScopeStyle::Off);
+        assert!(settings.bindings.is_empty());
+    }
+
+    #[test]
+    fn settings_store_saves_and_loads() {
+        let dir = std::env::temp_dir().join(format!("cool-snake-settings-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = SettingsStore::new(dir.join("settings.txt"));
+
+        assert_eq!(store.load().ok(), Some(Settings::default()));
+        let settings = Settings {
+            speed: 1,
+            ..Settings::default()
+        };
+        assert!(store.save(&settings).is_ok());
+        assert_eq!(store.load().ok(), Some(settings));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn scope_style_changes_survive_restart() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_scope(ScopeStyle::Solid.config())
+            .build();
+        assert_eq!(game.scope().config(), ScopeStyle::Solid.config());
+
+        game.set_scope_config(ScopeStyle::Off.config());
+        game.restart();
+        assert!(!game.scope().config().visible);
+    }
+}
+
+mod hud_tests {
+    use crate::core::Vec2;
+    use crate::game::Game;
+    use crate::renderer::{GameRenderer, HudLayout, RenderConfig};
+    use crate::systems::{DeathCause, EventFeed, GameEvent, TimedEvent};
+    use crate::theme::Theme;
+    use crate::ui::Sidebar;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    use std::time::Duration;
+
+    fn text(buf: &Buffer) -> String {
+        let area = buf.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn timed(tick: u64, event: GameEvent) -> TimedEvent {
+        TimedEvent { tick, event }
+    }
+
+    #[test]
+    fn events_describe_themselves() {
+        let shot = GameEvent::FoodCollected {
+            position: Vec2::zero(),
+            by_bullet: true,
+        };
+        assert_eq!(shot.to_string(), "food shot");
+        assert_eq!(
+            GameEvent::SnakeDamaged {
+                position: Vec2::zero(),
+                segments_lost: 3
+            }
+            .to_string(),
+            "cut -3"
+        );
+        assert_eq!(
+            GameEvent::SnakeDied {
+                cause: DeathCause::HitSelf
+            }
+            .to_string(),
+            "bit itself"
+        );
+    }
+
+    #[test]
+    fn feed_keeps_most_recent_first() {
+        let mut feed = EventFeed::new(2);
+        feed.push(timed(1, GameEvent::GamePaused));
+        feed.push(timed(2, GameEvent::GameResumed));
+        feed.push(timed(3, GameEvent::SnakeGrew { length: 4 }));
+
+        let ticks: Vec<u64> = feed.recent().map(|e| e.tick).collect();
+        assert_eq!(ticks, vec![3, 2]);
+    }
+
+    #[test]
+    fn sidebar_shows_stats_and_feed() {
+        let game = Game::new(20, 10);
+        let mut feed = EventFeed::default();
+        feed.push(timed(7, GameEvent::SnakeGrew { length: 4 }));
+        let theme = Theme::default();
+
+        let area = Rect::new(0, 0, 28, 20);
+        let mut buf = Buffer::empty(area);
+        Sidebar::new(&game, &feed, &theme)
+            .with_mode("classic")
+            .with_timing(Duration::from_secs(83), Duration::from_millis(70))
+            .render(area, &mut buf);
+
+        let out = text(&buf);
+        assert!(out.contains("LENGTH"));
+        assert!(out.contains("01:23"));
+        assert!(out.contains("classic"));
+        assert!(out.contains("grew to 4"));
+    }
+
+    #[test]
+    fn sidebar_survives_tiny_areas() {
+        let game = Game::new(20, 10);
+        let feed = EventFeed::default();
+        let theme = Theme::default();
+
+        for (w, h) in [(28, 3), (6, 2), (1, 1), (0, 0)] {
+            let area = Rect::new(0, 0, w, h);
+            let mut buf = Buffer::empty(area);
+            Sidebar::new(&game, &feed, &theme).render(area, &mut buf);
+        }
+    }
+
+    #[test]
+    fn compact_hud_shows_length_even_when_paused() {
+        let mut game = Game::new(30, 10);
+        game.toggle_pause();
+        let config = RenderConfig {
+            hud: HudLayout::Compact,
+            ..RenderConfig::default()
+        };
+
+        let area = Rect::new(0, 0, 62, 12);
+        let mut buf = Buffer::empty(area);
+        GameRenderer::with_config(&game, config).render(area, &mut buf);
+
+        let out = text(&buf);
+        assert!(out.contains("LEN 1"));
+        assert!(out.contains("PAUSED"));
+    }
+
+    #[test]
+    fn hud_layout_parses() {
+        assert_eq!("Compact".parse::<HudLayout>(), Ok(HudLayout::Compact));
+        assert!("wide".parse::<HudLayout>().is_err());
+    }
+}
+
+mod debug_tests {
+    use crate::core::Direction;
+    use crate::game::Game;
+    use crate::renderer::{GameRenderer, RenderConfig};
+    use crate::systems::TimingWindow;
+    use crate::theme::Theme;
+    use crate::ui::{DebugOverlay, DebugTimings};
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    use std::time::Duration;
+
+    fn text(buf: &Buffer) -> String {
+        let area = buf.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn timing_window_keeps_recent_samples() {
+        let mut window = TimingWindow::new(2);
+        assert_eq!(window.average(), None);
+
+        for ms in [100, 10, 30] {
+            window.record(Duration::from_millis(ms));
+        }
+        assert_eq!(window.len(), 2);
+        assert_eq!(window.average(), Some(Duration::from_millis(20)));
+        assert_eq!(window.max(), Some(Duration::from_millis(30)));
+        assert_eq!(window.last(), Some(Duration::from_millis(30)));
+    }
+
+    #[test]
+    fn overlay_shows_pending_direction_and_bullets() {
+        let mut game = Game::new(30, 12);
+        game.fire();
+        game.set_direction(Direction::Up);
+
+        let timings = DebugTimings::default();
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 62, 20);
+        let mut buf = Buffer::empty(area);
+        DebugOverlay::new(&game, &timings, &theme).render(area, &mut buf);
+
+        let out = text(&buf);
+        assert!(out.contains("Right -> Up"));
+        assert!(out.contains("life"));
+        assert!(out.contains("30x12"));
+    }
+
+    #[test]
+    fn grid_coordinates_only_with_show_debug() {
+        let game = Game::new(12, 6);
+        let area = Rect::new(0, 0, 26, 8);
+
+        let mut plain = Buffer::empty(area);
+        GameRenderer::new(&game).render(area, &mut plain);
+        assert_ne!(plain[(21, 7)].symbol(), "1");
+
+        let config = RenderConfig {
+            show_debug: true,
+            ..RenderConfig::default()
+        };
+        let mut debug = Buffer::empty(area);
+        GameRenderer::with_config(&game, config).render(area, &mut debug);
+        // Cell 10 starts at column 1 + 10 * 2 on the bottom border.
+        assert_eq!(debug[(21, 7)].symbol(), "1");
+        assert_eq!(debug[(0, 1)].symbol(), "0");
+    }
+
+    #[test]
+    fn overlay_handles_tiny_areas() {
+        let game = Game::new(10, 5);
+        let timings = DebugTimings::default();
+        let theme = Theme::default();
+        for (w, h) in [(3, 2), (10, 4), (0, 0)] {
+            let area = Rect::new(0, 0, w, h);
+            let mut buf = Buffer::empty(area);
+            DebugOverlay::new(&game, &timings, &theme).render(area, &mut buf);
+        }
+    }
+}
+
+mod scoring_tests {
+    use crate::systems::{GameEvent, Scoring, ScoringConfig};
+
+    #[test]
+    fn head_pickups_score_base_points() {
+        let mut scoring = Scoring::default();
+        let outcome = scoring.food_collected(false, 0);
+        assert_eq!(outcome.points, 1);
+        assert_eq!(scoring.score(), 1);
+        assert!(!scoring.in_combo());
+    }
+
+    #[test]
+    fn bullet_kills_scale_with_distance() {
+        let mut near = Scoring::default();
+        let mut far = Scoring::default();
+        near.food_collected(true, 1);
+        far.food_collected(true, 12);
+        assert_eq!(near.score(), 2);
+        assert_eq!(far.score(), 4);
+
+        let mut very_far = Scoring::default();
+        very_far.food_collected(true, 500);
+        assert_eq!(very_far.score(), 6);
+    }
+
+    #[test]
+    fn consecutive_hits_raise_the_multiplier() {
+        let mut scoring = Scoring::default();
+        assert!(!scoring.food_collected(false, 0).combo_started);
+        assert!(!scoring.food_collected(false, 0).combo_started);
+        let third = scoring.food_collected(false, 0);
+        assert!(third.combo_started);
+        assert_eq!(third.multiplier, 2);
+        assert_eq!(third.points, 2);
+        assert!(!scoring.food_collected(false, 0).combo_started);
+        assert_eq!(scoring.score(), 6);
+    }
+
+    #[test]
+    fn multiplier_is_capped() {
+        let config = ScoringConfig {
+            hits_per_multiplier: 1,
+            max_multiplier: 3,
+            ..ScoringConfig::default()
+        };
+        let mut scoring = Scoring::new(config);
+        for _ in 0..10 {
+            scoring.food_collected(false, 0);
+        }
+        assert_eq!(scoring.multiplier(), 3);
+        assert_eq!(scoring.best_streak(), 10);
+    }
+
+    #[test]
+    fn miss_breaks_combo() {
+        let mut scoring = Scoring::default();
+        for _ in 0..3 {
+            scoring.food_collected(false, 0);
+        }
+        assert_eq!(scoring.shot_missed().combo_broken, Some(3));
+        assert_eq!(scoring.streak(), 0);
+        assert_eq!(scoring.multiplier(), 1);
+    }
+
+    #[test]
+    fn losing_a_streak_below_combo_is_silent() {
+        let mut scoring = Scoring::default();
+        scoring.food_collected(false, 0);
+        assert_eq!(scoring.shot_missed().combo_broken, None);
+        assert_eq!(scoring.streak(), 0);
+    }
+
+    #[test]
+    fn damage_costs_points_without_underflow() {
+        let mut scoring = Scoring::default();
+        for _ in 0..3 {
+            scoring.food_collected(false, 0);
+        }
+        assert_eq!(scoring.score(), 4);
+        let outcome = scoring.damaged(3);
+        assert_eq!(outcome.combo_broken, Some(3));
+        assert_eq!(scoring.score(), 1);
+        scoring.damaged(10);
+        assert_eq!(scoring.score(), 0);
+    }
+
+    #[test]
+    fn combo_events_describe_themselves() {
+        assert_eq!(
+            GameEvent::ComboStarted { multiplier: 2 }.to_string(),
+            "combo x2"
+        );
+        assert_eq!(
+            GameEvent::ComboBroken { streak: 4 }.to_string(),
+            "combo lost (4 hits)"
+        );
+    }
+}
+
+mod level_tests {
+    use crate::game::GameBuilder;
+    use crate::systems::{Difficulty, GameEvent, LevelCurve};
+
+    #[test]
+    fn level_follows_points_per_level() {
+        let curve = LevelCurve::default();
+        assert_eq!(curve.level_for_score(0), 1);
+        assert_eq!(curve.level_for_score(9), 1);
+        assert_eq!(curve.level_for_score(10), 2);
+        assert_eq!(curve.level_for_score(10_000), curve.max_level);
+    }
+
+    #[test]
+    fn difficulty_holds_last_step() {
+        let curve = LevelCurve::default();
+        let last = *curve.steps.last().unwrap_or(&Difficulty::default());
+        assert_eq!(curve.difficulty(1), curve.steps[0]);
+        assert_eq!(curve.difficulty(50), last);
+        assert!(curve.difficulty(8).tick_percent < curve.difficulty(1).tick_percent);
+    }
+
+    #[test]
+    fn empty_curve_falls_back_to_defaults() {
+        let curve = LevelCurve {
+            steps: Vec::new(),
+            ..LevelCurve::default()
+        };
+        assert_eq!(curve.difficulty(3), Difficulty::default());
+    }
+
+    #[test]
+    fn cooldown_scales_and_rounds_up() {
+        assert_eq!(Difficulty::new(1, 0, 100, 100).bullet_cooldown(3), 3);
+        assert_eq!(Difficulty::new(1, 0, 100, 67).bullet_cooldown(3), 3);
+        assert_eq!(Difficulty::new(1, 0, 100, 34).bullet_cooldown(3), 2);
+        assert_eq!(Difficulty::new(1, 0, 100, 0).bullet_cooldown(3), 0);
+    }
+
+    #[test]
+    fn game_applies_first_step_on_start() {
+        let curve = LevelCurve {
+            steps: vec![Difficulty::new(2, 0, 100, 50)],
+            ..LevelCurve::default()
+        };
+        let game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_bullet_cooldown(4)
+            .with_levels(curve)
+            .build();
+        assert_eq!(game.level(), 1);
+        assert_eq!(game.bullet_cooldown_max(), 2);
+        assert_eq!(game.food().config().speed_multiplier, 2);
+    }
+
+    #[test]
+    fn food_lifetime_forces_respawn() {
+        let curve = LevelCurve {
+            steps: vec![Difficulty::new(0, 3, 100, 100)],
+            ..LevelCurve::default()
+        };
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_levels(curve)
+            .build();
+        for _ in 0..10 {
+            game.tick();
+            assert!(game.food().age() < 3);
+        }
+    }
+
+    #[test]
+    fn level_up_describes_itself() {
+        assert_eq!(GameEvent::LevelUp { level: 3 }.to_string(), "level 3");
+    }
+}
+
+mod campaign_tests {
+    use crate::campaign::{Campaign, CampaignLevel};
+    use crate::core::{Direction, GameState, Vec2};
+    use crate::game::{GameBuilder, SnakeStart};
+    use crate::storage::{CampaignProgress, CampaignStore};
+    use crate::systems::{GameEvent, Objective};
+    use std::time::Duration;
+
+    const LEVEL: &str = "\
+# comment
+name = Test
+objective = collect 4
+stars = 5 9
+length = 2
+
+map:
+..........
+..#.......
+....S.....
+..........
+..........
+";
+
+    #[test]
+    fn builtin_levels_all_parse() {
+        assert_eq!(Campaign::builtin().len(), 5);
+    }
+
+    #[test]
+    fn parses_header_and_map() {
+        let level = CampaignLevel::parse("test", LEVEL);
+        let Ok(level) = level else {
+            return assert!(level.is_ok(), "{:?}", level.err());
+        };
+        assert_eq!(level.name, "Test");
+        assert_eq!(level.objective, Objective::Collect(4));
+        assert_eq!((level.width, level.height), (10, 5));
+        assert_eq!(level.walls, vec![Vec2::new(2, 1)]);
+        assert_eq!(level.start.position, Vec2::new(4, 2));
+        assert_eq!(level.start.length, 2);
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_line() {
+        let bad_key = LEVEL.replace("length = 2", "lenght = 2");
+        let err = CampaignLevel::parse("t", &bad_key).err();
+        assert_eq!(err.map(|e| e.line), Some(5));
+
+        let no_head = LEVEL.replace('S', ".");
+        assert!(CampaignLevel::parse("t", &no_head).is_err());
+
+        let into_wall = LEVEL
+            .replace("....S.....", "..S.......")
+            .replace("length = 2", "length = 2\ndirection = down");
+        assert!(CampaignLevel::parse("t", &into_wall).is_err());
+
+        let bad_objective = LEVEL.replace("collect 4", "collect lots");
+        assert!(CampaignLevel::parse("t", &bad_objective).is_err());
+    }
+
+    #[test]
+    fn stars_follow_thresholds() {
+        let Ok(level) = CampaignLevel::parse("t", LEVEL) else {
+            return;
+        };
+        assert_eq!(level.stars_for(0), 1);
+        assert_eq!(level.stars_for(5), 2);
+        assert_eq!(level.stars_for(9), 3);
+    }
+
+    #[test]
+    fn progress_unlocks_in_order_and_keeps_best() {
+        let campaign = Campaign::builtin();
+        let mut progress = CampaignProgress::default();
+        assert!(progress.is_unlocked(&campaign, 0));
+        assert!(!progress.is_unlocked(&campaign, 1));
+
+        assert!(progress.record("first-bite", 2));
+        assert!(!progress.record("first-bite", 1));
+        assert!(progress.is_unlocked(&campaign, 1));
+        assert!(!progress.is_unlocked(&campaign, 2));
+
+        let reparsed = CampaignProgress::parse(&progress.to_file_string());
+        assert_eq!(reparsed, progress);
+        assert_eq!(reparsed.stars("first-bite"), 2);
+    }
+
+    #[test]
+    fn campaign_store_merges_best_stars() {
+        let dir = std::env::temp_dir().join(format!("cool-snake-campaign-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = CampaignStore::new(dir.join("campaign.txt"));
+
+        assert!(store.record("first-bite", 3).is_ok());
+        assert!(store.record("first-bite", 1).is_ok());
+        assert_eq!(store.load().ok().map(|p| p.stars("first-bite")), Some(3));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn meeting_the_objective_wins() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_objective(Objective::Survive { seconds: 2 })
+            .with_tick_duration(Duration::from_secs(1))
+            .build();
+        game.tick();
+        assert_eq!(game.state(), GameState::Playing);
+        game.tick();
+        assert_eq!(game.state(), GameState::Won);
+
+        let mut completed = false;
+        while let Some(timed) = game.events().pop() {
+            completed |= timed.event == GameEvent::ObjectiveCompleted;
+        }
+        assert!(completed);
+
+        game.restart();
+        assert_eq!(game.state(), GameState::Playing);
+    }
+
+    #[test]
+    fn walls_kill_the_snake() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_walls([Vec2::new(6, 5)])
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 5),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        assert_eq!(game.snake().length(), 3);
+        game.move_snake();
+        assert_eq!(game.state(), GameState::Dead);
+    }
+
+    #[test]
+    fn walls_stop_bullets() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_walls([Vec2::new(9, 2)])
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 2),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .build();
+        assert!(game.fire());
+        for _ in 0..3 {
+            game.tick();
+        }
+        assert_eq!(game.bullets().active_count(), 0);
+    }
+}
+
+mod mode_tests {
+    use crate::core::{Direction, GameState, Vec2};
+    use crate::entities::Snake;
+    use crate::game::{GameBuilder, SnakeStart};
+    use crate::glyphs::GlyphSet;
+    use crate::renderer::RenderContext;
+    use crate::systems::{DeathCause, GameEvent, GameMode, LevelCurve};
+    use crate::theme::Theme;
+    use crate::traits::{Moveable, Positioned, Renderable};
+    use ratatui::{buffer::Buffer, layout::Rect};
+    use std::time::Duration;
+
+    #[test]
+    fn modes_parse_by_name() {
+        assert_eq!("time-attack".parse::<GameMode>(), Ok(GameMode::TimeAttack));
+        assert_eq!("ZEN".parse::<GameMode>(), Ok(GameMode::Zen));
+        assert!("arcade".parse::<GameMode>().is_err());
+        assert_eq!(GameMode::Zen.next(), GameMode::Classic);
+    }
+
+    #[test]
+    fn zen_wraps_at_the_edge_and_never_dies() {
+        let mut game = GameBuilder::new()
+            .with_bounds(10, 10)
+            .with_mode(GameMode::Zen)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(9, 5),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        game.move_snake();
+        assert_eq!(game.snake().head(), Vec2::new(0, 5));
+
+        for dir in [Direction::Down, Direction::Left, Direction::Up] {
+            game.set_direction(dir);
+            game.move_snake();
+        }
+        assert_eq!(game.state(), GameState::Playing);
+    }
+
+    #[test]
+    fn time_attack_ends_after_two_minutes() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_mode(GameMode::TimeAttack)
+            .with_levels(LevelCurve::flat())
+            .with_tick_duration(Duration::from_secs(1))
+            .build();
+        for _ in 0..119 {
+            game.tick();
+        }
+        assert_eq!(game.time_left(), Some(Duration::from_secs(1)));
+        assert_eq!(game.state(), GameState::Playing);
+
+        game.tick();
+        assert_eq!(game.state(), GameState::Won);
+        assert!(game.events().drain().any(|e| e.event == GameEvent::TimeUp));
+    }
+
+    #[test]
+    fn survival_scores_seconds_survived() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_mode(GameMode::Survival)
+            .with_levels(LevelCurve::flat())
+            .with_tick_duration(Duration::from_millis(500))
+            .build();
+        for _ in 0..7 {
+            game.tick();
+        }
+        assert_eq!(game.score(), 3);
+        assert_eq!(game.time_left(), None);
+    }
+
+    #[test]
+    fn idle_survival_starves() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_mode(GameMode::Survival)
+            .with_levels(LevelCurve::flat())
+            .with_tick_duration(Duration::from_secs(1))
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(2, 2),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        // Far from the snake and standing still, so nothing gets eaten.
+        game.food_mut().set_position(Vec2::new(15, 15));
+        game.food_mut().set_velocity(Vec2::new(0, 0));
+        for _ in 0..20 {
+            game.tick();
+        }
+        assert_eq!(game.snake().length(), 1);
+        assert_eq!(game.occupancy().snake_cells(), 1);
+        assert_eq!(game.state(), GameState::Playing);
+
+        for _ in 0..10 {
+            game.tick();
+        }
+        assert_eq!(game.state(), GameState::Dead);
+        assert!(game.events().drain().any(|e| e.event
+            == GameEvent::SnakeDied {
+                cause: DeathCause::Starved
+            }));
+    }
+
+    #[test]
+    fn zen_refuses_a_step_into_a_wall() {
+        let mut game = GameBuilder::new()
+            .with_bounds(10, 10)
+            .with_mode(GameMode::Zen)
+            .with_walls([Vec2::new(5, 4)])
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 5),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        game.set_direction(Direction::Up);
+        game.move_snake();
+        assert_eq!(game.snake().head(), Vec2::new(5, 5));
+        assert_eq!(game.snake().queued_turns().count(), 0);
+        assert_eq!(game.state(), GameState::Playing);
+        assert!(game.events().drain().any(|e| e.event
+            == GameEvent::MoveBlocked {
+                position: Vec2::new(5, 4)
+            }));
+
+        // The refused turn is gone, so the next step carries on as before.
+        game.move_snake();
+        assert_eq!(game.snake().head(), Vec2::new(6, 5));
+    }
+
+    #[test]
+    fn zen_passes_through_its_own_body() {
+        let mut game = GameBuilder::new()
+            .with_bounds(10, 10)
+            .with_mode(GameMode::Zen)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 5),
+                direction: Direction::Right,
+                length: 6,
+            })
+            .build();
+        for dir in [Direction::Down, Direction::Left, Direction::Up] {
+            game.set_direction(dir);
+            game.move_snake();
+        }
+        assert_eq!(game.snake().head(), Vec2::new(4, 5));
+        assert!(game
+            .snake()
+            .segments()
+            .iter()
+            .skip(1)
+            .any(|&s| s == Vec2::new(4, 5)));
+        assert_eq!(game.state(), GameState::Playing);
+        assert!(!game
+            .events()
+            .drain()
+            .any(|e| matches!(e.event, GameEvent::MoveBlocked { .. })));
+        assert_eq!(game.occupancy().snake_cells(), game.snake().length());
+    }
+
+    #[test]
+    fn body_links_join_across_the_wrap() {
+        let mut game = GameBuilder::new()
+            .with_bounds(6, 3)
+            .with_mode(GameMode::Zen)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 1),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        game.move_snake();
+        assert_eq!(game.snake().head(), Vec2::new(0, 1));
+
+        let theme = Theme::default();
+        let ctx = RenderContext {
+            offset: Vec2::zero(),
+            bounds: Vec2::new(6, 3),
+            theme: &theme,
+            glyphs: GlyphSet::Ascii,
+        };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 3));
+        game.snake().render(&ctx, &mut buf);
+        let straight = GlyphSet::Ascii.snake_segment(Some(Direction::Right), Some(Direction::Left));
+        assert_eq!(buf[(10, 1)].symbol(), straight[0]);
+        assert_eq!(buf[(11, 1)].symbol(), straight[1]);
+        let head = GlyphSet::Ascii.snake_head(Direction::Right, Some(Direction::Left));
+        assert_eq!(buf[(0, 1)].symbol(), head[0]);
+    }
+
+    #[test]
+    fn steps_across_a_wrapping_edge_read_as_one_cell() {
+        let bounds = Vec2::new(10, 8);
+        assert_eq!(Vec2::new(-9, 0).wrapped_step(bounds), Vec2::new(1, 0));
+        assert_eq!(Vec2::new(9, 0).wrapped_step(bounds), Vec2::new(-1, 0));
+        assert_eq!(Vec2::new(0, 7).wrapped_step(bounds), Vec2::new(0, -1));
+        assert_eq!(Vec2::new(0, -1).wrapped_step(bounds), Vec2::new(0, -1));
+        assert_eq!(Vec2::new(1, 0).wrapped_step(bounds), Vec2::new(1, 0));
+    }
+
+    #[test]
+    fn survival_cuts_grow_every_thirty_seconds() {
+        assert_eq!(GameMode::Survival.extra_cut(Duration::from_secs(29)), 0);
+        assert_eq!(GameMode::Survival.extra_cut(Duration::from_secs(65)), 2);
+        assert_eq!(GameMode::Classic.extra_cut(Duration::from_secs(65)), 0);
+    }
+
+    #[test]
+    fn shedding_always_keeps_the_head() {
+        let mut snake = Snake::with_body(Vec2::new(5, 5), Direction::Right, 4);
+        assert_eq!(snake.shed(2), 2);
+        assert_eq!(snake.length(), 2);
+        assert_eq!(snake.shed(10), 1);
+        assert_eq!(snake.head(), Vec2::new(5, 5));
+    }
+}
+
+mod daily_tests {
+    use crate::daily::{DailyChallenge, Date};
+    use crate::game::GameBuilder;
+    use crate::storage::{DailyHistory, DailyStore};
+    use crate::systems::GameMode;
+    use crate::traits::Positioned;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap_or_else(|_| Date::from_days_since_epoch(0))
+    }
+
+    #[test]
+    fn dates_round_trip_through_day_numbers() {
+        assert_eq!(Date::from_days_since_epoch(0).to_string(), "1970-01-01");
+        assert_eq!(date("2000-03-01").days_since_epoch(), 11_017);
+        for days in [-1, 0, 59, 11_016, 20_744, 50_000] {
+            assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+        }
+    }
+
+    #[test]
+    fn date_parsing_checks_the_calendar() {
+        assert_eq!(
+            Date::new(2024, 2, 29).map(|d| d.to_string()).as_deref(),
+            Some("2024-02-29")
+        );
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2026-13-01".parse::<Date>().is_err());
+        assert!("yesterday".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn challenge_is_fixed_by_the_date() {
+        let today = DailyChallenge::for_date(date("2026-10-18"));
+        assert_eq!(today, DailyChallenge::for_date(date("2026-10-18")));
+        assert_ne!(
+            today.seed,
+            DailyChallenge::for_date(date("2026-10-19")).seed
+        );
+
+        for days in 20_000..20_060 {
+            let challenge = DailyChallenge::for_date(Date::from_days_since_epoch(days));
+            assert!((24..=36).contains(&challenge.width));
+            assert!((14..=20).contains(&challenge.height));
+            assert_ne!(challenge.mode, GameMode::Zen);
+        }
+    }
+
+    #[test]
+    fn seeded_games_replay_the_same_food() {
+        let food_path = |seed| {
+            let mut game = GameBuilder::new()
+                .with_bounds(30, 20)
+                .with_seed(seed)
+                .build();
+            (0..50)
+                .map(|_| {
+                    game.tick();
+                    game.food().position()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(food_path(7), food_path(7));
+        assert_ne!(food_path(7), food_path(8));
+    }
+
+    #[test]
+    fn one_attempt_per_day() {
+        let challenge = DailyChallenge::for_date(date("2026-10-18"));
+        let mut history = DailyHistory::default();
+        assert!(history.begin(&challenge));
+        assert!(!history.begin(&challenge));
+
+        history.finish(challenge.date, 42);
+        let parsed = DailyHistory::parse(&history.to_file_string());
+        assert_eq!(parsed, history);
+        assert_eq!(parsed.get(challenge.date).map(|r| r.score), Some(42));
+    }
+
+    #[test]
+    fn store_keeps_the_history() {
+        let dir = std::env::temp_dir().join(format!("cool-snake-daily-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = DailyStore::new(dir.join("daily.txt"));
+
+        let first = DailyChallenge::for_date(date("2026-10-17"));
+        let second = DailyChallenge::for_date(date("2026-10-18"));
+        assert!(store.begin(&first).unwrap_or(false));
+        assert!(!store.begin(&first).unwrap_or(true));
+        assert!(store.begin(&second).unwrap_or(false));
+        let _ = store.finish(second.date, 9);
+
+        let history = store.load().unwrap_or_default();
+        let scores: Vec<_> = history.results().map(|r| (r.date, r.score)).collect();
+        assert_eq!(scores, vec![(first.date, 0), (second.date, 9)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
+
+mod mouse_tests {
+    use crate::core::{Direction, Vec2};
+    use crate::game::GameBuilder;
+    use crate::renderer::cell_at;
+    use crate::traits::Positioned;
+    use crate::ui::{Menu, MenuOutcome};
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::layout::Rect;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn screen_positions_map_back_to_cells() {
+        let offset = Vec2::new(3, 2);
+        let cell = Vec2::new(5, 4);
+        let (x, y) = cell.to_screen(offset);
+        assert_eq!(Vec2::from_screen(x, y, offset), Some(cell));
+        assert_eq!(Vec2::from_screen(x + 1, y, offset), Some(cell));
+        assert_eq!(Vec2::from_screen(2, 2, offset), None);
+    }
+
+    #[test]
+    fn clicks_on_the_border_miss_the_arena() {
+        let area = Rect::new(10, 5, 22, 12);
+        let bounds = Vec2::new(10, 10);
+        assert_eq!(cell_at(area, bounds, 11, 6), Some(Vec2::new(0, 0)));
+        assert_eq!(cell_at(area, bounds, 30, 15), Some(Vec2::new(9, 9)));
+        assert_eq!(cell_at(area, bounds, 10, 6), None);
+        assert_eq!(cell_at(area, bounds, 31, 6), None);
+        assert_eq!(cell_at(area, bounds, 11, 16), None);
+    }
+
+    #[test]
+    fn dominant_axis_picks_the_aim() {
+        assert_eq!(
+            Direction::dominant(Vec2::new(3, -1)),
+            Some(Direction::Right)
+        );
+        assert_eq!(Direction::dominant(Vec2::new(-1, -4)), Some(Direction::Up));
+        assert_eq!(Direction::dominant(Vec2::new(-2, 2)), Some(Direction::Left));
+        assert_eq!(Direction::dominant(Vec2::zero()), None);
+    }
+
+    #[test]
+    fn aiming_turns_the_gun_until_the_next_steer() {
+        let mut game = GameBuilder::new().with_bounds(20, 20).build();
+        let head = game.snake().head();
+
+        game.aim_at(head + Vec2::new(1, -6));
+        assert_eq!(game.aim_direction(), Direction::Up);
+        assert!(game.fire());
+        assert!(game
+            .bullets()
+            .iter()
+            .any(|b| b.position() == head + Vec2::new(0, -1)));
+
+        game.aim_at(head);
+        assert_eq!(game.aim_direction(), game.snake().direction());
+
+        game.aim_at(head + Vec2::new(-5, 0));
+        game.set_direction(Direction::Down);
+        game.move_snake();
+        assert_eq!(game.aim_target(), None);
+        assert_eq!(game.aim_direction(), Direction::Down);
+    }
+
+    #[test]
+    fn menus_follow_the_pointer() {
+        let mut menu = Menu::new("MENU", ["One", "Two", "Three"]);
+        let area = Rect::new(0, 0, 40, 20);
+        // 3 items + 4 rows of chrome, centred: the first item sits on row 8.
+        assert_eq!(menu.item_at(area, 20, 8), Some(0));
+        assert_eq!(menu.item_at(area, 20, 10), Some(2));
+        assert_eq!(menu.item_at(area, 20, 11), None);
+        assert_eq!(menu.item_at(area, 0, 8), None);
+
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Moved, 20, 9), area),
+            MenuOutcome::None
+        );
+        assert_eq!(menu.selected(), 1);
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 20, 10), area),
+            MenuOutcome::Selected(2)
+        );
+        menu.handle_mouse(mouse(MouseEventKind::ScrollDown, 0, 0), area);
+        assert_eq!(menu.selected(), 0);
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Right), 0, 0), area),
+            MenuOutcome::Back
+        );
+    }
+}
+
+mod turn_queue_tests {
+    use crate::core::{Direction, Vec2};
+    use crate::entities::Snake;
+    use crate::game::{GameBuilder, SnakeStart};
+
+    const BOUNDS: Vec2 = Vec2::new(20, 20);
+
+    #[test]
+    fn quick_turns_are_taken_one_per_step() {
+        let mut snake = Snake::new(Vec2::new(5, 5));
+        snake.set_direction(Direction::Up);
+        snake.set_direction(Direction::Left);
+        assert_eq!(
+            snake.queued_turns().collect::<Vec<_>>(),
+            vec![Direction::Up, Direction::Left]
+        );
+
+        let _ = snake.tick(BOUNDS);
+        assert_eq!(snake.head(), Vec2::new(5, 4));
+        let _ = snake.tick(BOUNDS);
+        assert_eq!(snake.head(), Vec2::new(4, 4));
+        assert_eq!(snake.direction(), Direction::Left);
+    }
+
+    #[test]
+    fn turns_are_checked_against_the_last_queued_one() {
+        let mut snake = Snake::new(Vec2::new(5, 5));
+        snake.set_direction(Direction::Up);
+        snake.set_direction(Direction::Down);
+        snake.set_direction(Direction::Up);
+        assert_eq!(
+            snake.queued_turns().collect::<Vec<_>>(),
+            vec![Direction::Up]
+        );
+
+        // Left reverses the current heading, but not the queued Up.
+        snake.set_direction(Direction::Left);
+        assert_eq!(snake.queued_turns().count(), 2);
+    }
+
+    #[test]
+    fn a_full_buffer_replaces_its_newest_turn() {
+        let mut snake = Snake::new(Vec2::new(5, 5)).with_turn_buffer(2);
+        snake.set_direction(Direction::Up);
+        snake.set_direction(Direction::Left);
+        snake.set_direction(Direction::Right);
+        assert_eq!(
+            snake.queued_turns().collect::<Vec<_>>(),
+            vec![Direction::Up, Direction::Right]
+        );
+
+        // A reversal of the turn before it is ignored without losing anything.
+        snake.set_direction(Direction::Down);
+        assert_eq!(snake.queued_turns().count(), 2);
+    }
+
+    #[test]
+    fn depth_of_one_keeps_the_latest_turn() {
+        let mut snake = Snake::new(Vec2::new(5, 5)).with_turn_buffer(0);
+        assert_eq!(snake.turn_buffer(), 1);
+        snake.set_direction(Direction::Up);
+        snake.set_direction(Direction::Down);
+        assert_eq!(snake.pending_direction(), Direction::Down);
+        // Checked against the heading, so this reversal is dropped.
+        snake.set_direction(Direction::Left);
+        assert_eq!(snake.pending_direction(), Direction::Down);
+    }
+
+    #[test]
+    fn presses_within_a_tick_are_stepped_on_later_ticks() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(10, 10),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        game.queue_step(Direction::Up);
+        game.queue_step(Direction::Left);
+        assert_eq!(game.snake().queued_turns().count(), 2);
+        assert_eq!(game.snake().head(), Vec2::new(10, 10));
+
+        game.tick();
+        assert_eq!(game.snake().head(), Vec2::new(10, 9));
+        game.tick();
+        assert_eq!(game.snake().head(), Vec2::new(9, 9));
+        game.tick();
+        assert_eq!(game.snake().head(), Vec2::new(9, 9));
+    }
+
+    #[test]
+    fn queued_steps_are_capped_by_the_buffer() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_turn_buffer(2)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(10, 10),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .build();
+        for _ in 0..5 {
+            game.queue_step(Direction::Right);
+        }
+        for _ in 0..5 {
+            game.tick();
+        }
+        assert_eq!(game.snake().head(), Vec2::new(12, 10));
+    }
+
+    #[test]
+    fn game_config_sets_the_depth() {
+        let game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_turn_buffer(5)
+            .build();
+        assert_eq!(game.snake().turn_buffer(), 5);
+    }
+}
+
+mod held_key_tests {
+    use crate::core::Direction;
+    use crate::input::{GameAction, HeldActions};
+
+    #[test]
+    fn only_fire_and_movement_repeat() {
+        assert!(GameAction::Fire.repeats_while_held());
+        assert!(GameAction::Move(Direction::Up).repeats_while_held());
+        assert!(!GameAction::Pause.repeats_while_held());
+        assert!(!GameAction::Quit.repeats_while_held());
+    }
+
+    #[test]
+    fn holds_until_release() {
+        let mut held = HeldActions::new(true);
+        held.press(GameAction::Fire);
+        held.press(GameAction::Fire);
+        held.press(GameAction::Pause);
+        assert_eq!(held.held(), &[GameAction::Fire]);
+
+        held.release(GameAction::Fire);
+        assert!(held.held().is_empty());
+    }
+
+    #[test]
+    fn a_new_direction_takes_over() {
+        let mut held = HeldActions::new(true);
+        held.press(GameAction::Fire);
+        held.press(GameAction::Move(Direction::Up));
+        held.press(GameAction::Move(Direction::Left));
+        assert_eq!(
+            held.held(),
+            &[GameAction::Fire, GameAction::Move(Direction::Left)]
+        );
+
+        // Letting go of the key it replaced changes nothing.
+        held.release(GameAction::Move(Direction::Up));
+        assert!(held.is_held(GameAction::Move(Direction::Left)));
+    }
+
+    #[test]
+    fn nothing_is_held_without_release_events() {
+        let mut held = HeldActions::new(false);
+        held.press(GameAction::Fire);
+        assert!(!held.releases_reported());
+        assert!(held.held().is_empty());
+    }
+}
+
+mod collision_tests {
+    use crate::core::GameState;
+    use crate::core::{Direction, Vec2};
+    use crate::entities::{Snake, Walls};
+    use crate::game::{Game, GameBuilder, SnakeStart};
+    use crate::systems::{
+        Collision, CollisionKind, CollisionWorld, DeathCause, GameEvent, GameMode, OccupancyGrid,
+        Sweep, SUBSTEPS,
+    };
+
+    const BOUNDS: Vec2 = Vec2::new(20, 20);
+
+    // A short snake heading up, out of the way of row 5 unless a test puts
+    // it there.
+    fn snake_at(head: Vec2) -> Snake {
+        Snake::with_body(head, Direction::Up, 3)
+    }
+
+    fn sweep(
+        snake: &Snake,
+        walls: &Walls,
+        food: Sweep,
+        bullets: &[(usize, Sweep)],
+    ) -> Vec<Collision> {
+        let grid = OccupancyGrid::build(BOUNDS, walls, snake);
+        CollisionWorld::new(&grid).sweep(Some(food), bullets)
+    }
+
+    fn kinds(collisions: &[Collision]) -> Vec<CollisionKind> {
+        collisions.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn sweep_positions_follow_speed() {
+        let bullet = Sweep::new(Vec2::new(5, 5), Vec2::new(2, 0));
+        assert_eq!(bullet.at(0), Vec2::new(5, 5));
+        assert_eq!(bullet.at(SUBSTEPS / 2), Vec2::new(6, 5));
+        assert_eq!(bullet.at(SUBSTEPS), Vec2::new(7, 5));
+        assert_eq!(
+            Sweep::stationary(Vec2::new(3, 3)).at(SUBSTEPS),
+            Vec2::new(3, 3)
+        );
+    }
+
+    #[test]
+    fn bullet_hits_food_it_starts_on() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let bullet = Sweep::new(Vec2::new(7, 5), Vec2::new(2, 0));
+        let hits = sweep(
+            &snake,
+            &Walls::default(),
+            Sweep::stationary(Vec2::new(7, 5)),
+            &[(0, bullet)],
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, CollisionKind::BulletFood);
+        assert_eq!(hits[0].substep, 0);
+        assert_eq!(hits[0].bullet, Some(0));
+    }
+
+    #[test]
+    fn bullet_hits_food_it_passes_over() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let bullet = Sweep::new(Vec2::new(5, 5), Vec2::new(2, 0));
+        let hits = sweep(
+            &snake,
+            &Walls::default(),
+            Sweep::stationary(Vec2::new(6, 5)),
+            &[(0, bullet)],
+        );
+        assert_eq!(kinds(&hits), vec![CollisionKind::BulletFood]);
+        assert_eq!(hits[0].position, Vec2::new(6, 5));
+        assert_eq!(hits[0].substep, SUBSTEPS / 2);
+    }
+
+    #[test]
+    fn bullet_misses_food_off_its_path() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let bullet = Sweep::new(Vec2::new(5, 5), Vec2::new(2, 0));
+        let hits = sweep(
+            &snake,
+            &Walls::default(),
+            Sweep::stationary(Vec2::new(10, 10)),
+            &[(0, bullet)],
+        );
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn bullet_and_food_swapping_cells_collide() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let bullet = Sweep::new(Vec2::new(5, 5), Vec2::new(2, 0));
+        let food = Sweep::new(Vec2::new(7, 5), Vec2::new(-1, 0));
+        let hits = sweep(&snake, &Walls::default(), food, &[(0, bullet)]);
+        assert_eq!(kinds(&hits), vec![CollisionKind::BulletFood]);
+    }
+
+    #[test]
+    fn fast_food_is_caught_passing_through_the_head() {
+        let snake = snake_at(Vec2::new(4, 5));
+        let food = Sweep::new(Vec2::new(2, 5), Vec2::new(3, 0));
+        let hits = sweep(&snake, &Walls::default(), food, &[]);
+        assert_eq!(kinds(&hits), vec![CollisionKind::FoodHead]);
+        assert_eq!(hits[0].position, Vec2::new(4, 5));
+    }
+
+    #[test]
+    fn food_crossing_the_body_is_damage() {
+        let snake = snake_at(Vec2::new(4, 5));
+        let food = Sweep::new(Vec2::new(6, 6), Vec2::new(-3, 0));
+        let hits = sweep(&snake, &Walls::default(), food, &[]);
+        assert_eq!(kinds(&hits), vec![CollisionKind::FoodBody]);
+        assert_eq!(hits[0].position, Vec2::new(4, 6));
+    }
+
+    #[test]
+    fn earliest_collision_wins() {
+        // The food would reach the head at the end of the tick, but the shot
+        // catches it halfway.
+        let snake = snake_at(Vec2::new(8, 5));
+        let food = Sweep::new(Vec2::new(7, 5), Vec2::new(1, 0));
+        let bullet = Sweep::new(Vec2::new(6, 5), Vec2::new(2, 0));
+        let hits = sweep(&snake, &Walls::default(), food, &[(0, bullet)]);
+        assert_eq!(kinds(&hits), vec![CollisionKind::BulletFood]);
+    }
+
+    #[test]
+    fn ties_go_to_the_lowest_bullet() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let food = Sweep::stationary(Vec2::new(6, 5));
+        let left = Sweep::new(Vec2::new(4, 5), Vec2::new(2, 0));
+        let right = Sweep::new(Vec2::new(8, 5), Vec2::new(-2, 0));
+        let hits = sweep(&snake, &Walls::default(), food, &[(3, right), (1, left)]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bullet, Some(1));
+    }
+
+    #[test]
+    fn walls_stop_bullets_before_the_food() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let walls = Walls::new([Vec2::new(6, 5)]);
+        let bullet = Sweep::new(Vec2::new(5, 5), Vec2::new(2, 0));
+        let hits = sweep(
+            &snake,
+            &walls,
+            Sweep::stationary(Vec2::new(7, 5)),
+            &[(0, bullet)],
+        );
+        assert_eq!(kinds(&hits), vec![CollisionKind::BulletWall]);
+    }
+
+    #[test]
+    fn leaving_the_arena_is_not_a_collision() {
+        let snake = snake_at(Vec2::new(15, 15));
+        let bullet = Sweep::new(Vec2::new(18, 5), Vec2::new(2, 0));
+        let food = Sweep::new(Vec2::new(1, 1), Vec2::new(-2, 0));
+        let hits = sweep(&snake, &Walls::default(), food, &[(0, bullet)]);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn snake_step_reports_walls_self_and_food() {
+        let snake = Snake::with_body(Vec2::new(5, 5), Direction::Right, 4);
+        let walls = Walls::new([Vec2::new(6, 5)]);
+        let grid = OccupancyGrid::build(BOUNDS, &walls, &snake);
+        let world = CollisionWorld::new(&grid);
+        let food = Vec2::new(5, 4);
+
+        let kind = |next, solid| world.snake_step(0, next, solid, food).map(|c| c.kind);
+        assert_eq!(kind(Vec2::new(6, 5), true), Some(CollisionKind::SnakeWall));
+        assert_eq!(kind(Vec2::new(-1, 5), true), Some(CollisionKind::SnakeWall));
+        assert_eq!(kind(Vec2::new(4, 5), true), Some(CollisionKind::SnakeSelf));
+        assert_eq!(kind(Vec2::new(4, 5), false), None);
+        assert_eq!(kind(food, true), Some(CollisionKind::FoodHead));
+        assert_eq!(kind(Vec2::new(5, 6), true), None);
+    }
+
+    #[test]
+    fn game_records_swept_collisions() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_walls([Vec2::new(7, 2)])
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 2),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .build();
+        assert!(game.fire());
+        game.tick();
+        assert!(game
+            .collisions()
+            .iter()
+            .any(|c| c.kind == CollisionKind::BulletWall && c.position == Vec2::new(7, 2)));
+        assert_eq!(game.bullets().active_count(), 0);
+
+        let mut emitted = false;
+        while let Some(timed) = game.events().pop() {
+            emitted |= timed.event
+                == GameEvent::Collision {
+                    kind: CollisionKind::BulletWall,
+                    position: Vec2::new(7, 2),
+                };
+        }
+        assert!(emitted);
+    }
+
+    // The player two cells left of a rival's neck, with the rival's head
+    // just below it.
+    fn duel(mode: GameMode) -> Game {
+        GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_mode(mode)
+            .with_seed(1)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(6, 4),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .with_rival(SnakeStart {
+                position: Vec2::new(8, 5),
+                direction: Direction::Down,
+                length: 3,
+            })
+            .build()
+    }
+
+    #[test]
+    fn rival_bodies_kill_and_block_in_zen() {
+        let mut game = duel(GameMode::Classic);
+        game.move_snake();
+        game.move_snake();
+        let player = &game.players()[0];
+        assert_eq!(player.death(), Some(DeathCause::HitSnake));
+        assert!(game.players()[1].is_alive());
+        assert_eq!(game.state(), GameState::Playing);
+        assert!(!game.occupancy().has_snake(Vec2::new(7, 4)));
+        assert!(game
+            .collisions()
+            .iter()
+            .any(|c| c.kind == CollisionKind::SnakeSnake && c.snake == Some(0)));
+
+        let mut zen = duel(GameMode::Zen);
+        zen.move_snake();
+        zen.move_snake();
+        assert_eq!(zen.snake().head(), Vec2::new(7, 4));
+        assert!(zen.players().iter().all(|p| p.is_alive()));
+    }
+
+    #[test]
+    fn head_on_takes_out_both_snakes() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_seed(1)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 4),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .with_rival(SnakeStart {
+                position: Vec2::new(8, 4),
+                direction: Direction::Left,
+                length: 2,
+            })
+            .build();
+        game.move_snake();
+        game.move_snake_for(1);
+        game.move_snake();
+        assert!(game
+            .collisions()
+            .iter()
+            .any(|c| c.kind == CollisionKind::HeadOn));
+        for player in game.players() {
+            assert_eq!(player.death(), Some(DeathCause::HitSnake));
+        }
+        assert_eq!(game.state(), GameState::Dead);
+        assert_eq!(game.occupancy().snake_cells(), 0);
+    }
+}
+
+mod occupancy_tests {
+    use crate::core::{Direction, Edge, GameState, SegmentIndex, Vec2};
+    use crate::entities::{Snake, Walls};
+    use crate::game::{GameBuilder, SnakeStart};
+    use crate::systems::{GameMode, LevelCurve, OccupancyGrid, Occupant};
+    use crate::traits::{Damageable, Moveable, Positioned, Segmented};
+
+    const BOUNDS: Vec2 = Vec2::new(10, 8);
+
+    #[test]
+    fn build_marks_walls_and_snake() {
+        let walls = Walls::new([Vec2::new(1, 1)]);
+        let snake = Snake::with_body(Vec2::new(5, 5), Direction::Right, 3);
+        let grid = OccupancyGrid::build(BOUNDS, &walls, &snake);
+
+        assert_eq!(grid.occupant(Vec2::new(1, 1)), Some(Occupant::Wall));
+        assert_eq!(grid.occupant(Vec2::new(3, 5)), Some(Occupant::Snake));
+        assert_eq!(grid.occupant(Vec2::new(6, 5)), None);
+        assert_eq!(grid.occupant(Vec2::new(-1, 0)), None);
+        assert!(grid.is_blocked(Vec2::new(-1, 0)));
+        assert_eq!(grid.snake_cells(), 3);
+    }
+
+    #[test]
+    fn snake_cells_are_counted_so_overlaps_survive_removal() {
+        let mut grid = OccupancyGrid::new(BOUNDS);
+        let cell = Vec2::new(2, 2);
+        grid.add_snake(0, cell);
+        grid.add_snake(0, cell);
+        grid.remove_snake(cell);
+        assert!(grid.has_snake(cell));
+        grid.remove_snake(cell);
+        assert!(grid.is_free(cell));
+        grid.remove_snake(cell);
+        assert_eq!(grid.snake_cells(), 0);
+    }
+
+    fn assert_indices_match(game: &crate::game::Game) {
+        for (i, &segment) in game.snake().segments().iter().enumerate() {
+            assert_eq!(
+                game.occupancy().segment_index(segment),
+                Some(SegmentIndex::new(i))
+            );
+        }
+    }
+
+    #[test]
+    fn grid_knows_each_segments_index() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 12)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 4),
+                direction: Direction::Right,
+                length: 4,
+            })
+            .build();
+        assert_indices_match(&game);
+        for dir in [Direction::Down, Direction::Down, Direction::Left] {
+            game.set_direction(dir);
+            game.move_snake();
+            assert_indices_match(&game);
+        }
+        assert_eq!(game.occupancy().segment_index(Vec2::new(15, 10)), None);
+    }
+
+    #[test]
+    fn trait_lookups_agree_with_the_grid() {
+        let walls = Walls::new([]);
+        let mut snake = Snake::with_body(Vec2::new(5, 5), Direction::Right, 4);
+        let grid = OccupancyGrid::build(BOUNDS, &walls, &snake);
+
+        for &cell in snake.segments() {
+            assert_eq!(snake.find_segment(cell), grid.segment_index(cell));
+            assert!(snake.contains_position(cell));
+        }
+        assert!(!snake.body_contains(Vec2::new(5, 5)));
+        assert!(snake.body_contains(Vec2::new(3, 5)));
+        assert_eq!(snake.find_segment(Vec2::new(7, 5)), None);
+
+        let lost = snake
+            .damage_at_position(Vec2::new(4, 5))
+            .map(|r| r.segments_lost);
+        assert_eq!(lost, Some(2));
+        assert!(snake.damage_at_position(Vec2::new(1, 1)).is_none());
+    }
+
+    #[test]
+    fn food_cut_frees_exactly_the_lost_cells() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 12)
+            .with_levels(LevelCurve::flat())
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 4),
+                direction: Direction::Right,
+                length: 5,
+            })
+            .build();
+        game.food_mut().set_position(Vec2::new(3, 5));
+        game.food_mut().set_velocity(Vec2::new(0, -1));
+        game.tick();
+
+        let kept = [Vec2::new(5, 4), Vec2::new(4, 4), Vec2::new(3, 4)];
+        assert!(game.snake().segments().iter().eq(kept.iter()));
+        assert_eq!(game.occupancy().snake_cells(), 3);
+        assert!(!game.occupancy().has_snake(Vec2::new(2, 4)));
+        assert!(!game.occupancy().has_snake(Vec2::new(1, 4)));
+        assert_indices_match(&game);
+    }
+
+    #[test]
+    fn food_is_occupied_but_not_blocking() {
+        let mut grid = OccupancyGrid::new(BOUNDS);
+        grid.set_food(Some(Vec2::new(4, 4)));
+        assert_eq!(grid.occupant(Vec2::new(4, 4)), Some(Occupant::Food));
+        assert!(!grid.is_blocked(Vec2::new(4, 4)));
+        assert!(!grid.is_free(Vec2::new(4, 4)));
+    }
+
+    #[test]
+    fn free_on_edge_skips_taken_cells() {
+        let walls = Walls::new([Vec2::new(0, 0), Vec2::new(0, 3)]);
+        let grid = OccupancyGrid::build(BOUNDS, &walls, &Snake::new(Vec2::new(5, 5)));
+        let left = grid.free_on_edge(Edge::Left);
+        assert_eq!(left.len(), BOUNDS.y as usize - 2);
+        assert!(left.iter().all(|pos| pos.x == 0));
+        assert!(!left.contains(&Vec2::new(0, 3)));
+        assert_eq!(grid.free_on_edge(Edge::Bottom).len(), BOUNDS.x as usize);
+    }
+
+    #[test]
+    fn nearest_free_searches_outward() {
+        let walls = Walls::new([Vec2::new(4, 4), Vec2::new(4, 3)]);
+        let grid = OccupancyGrid::build(BOUNDS, &walls, &Snake::new(Vec2::new(9, 7)));
+        assert_eq!(grid.nearest_free(Vec2::new(1, 1)), Some(Vec2::new(1, 1)));
+        assert_eq!(grid.nearest_free(Vec2::new(4, 4)), Some(Vec2::new(3, 3)));
+
+        let full =
+            Walls::new((0..BOUNDS.x).flat_map(|x| (0..BOUNDS.y).map(move |y| Vec2::new(x, y))));
+        let grid = OccupancyGrid::build(BOUNDS, &full, &Snake::new(Vec2::new(0, 0)));
+        assert_eq!(grid.nearest_free(Vec2::new(4, 4)), None);
+    }
+
+    #[test]
+    fn game_keeps_the_grid_in_step_with_the_snake() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 12)
+            .with_mode(GameMode::Zen)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 5),
+                direction: Direction::Right,
+                length: 4,
+            })
+            .build();
+        for dir in [
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+        ] {
+            game.set_direction(dir);
+            game.move_snake();
+        }
+        assert_eq!(game.state(), GameState::Playing);
+
+        let grid = game.occupancy();
+        assert_eq!(grid.snake_cells(), game.snake().length());
+        for &segment in game.snake().segments() {
+            assert!(grid.has_snake(segment));
+        }
+        assert_eq!(grid.occupant(game.food().position()), Some(Occupant::Food));
+    }
+
+    #[test]
+    fn food_never_spawns_on_the_snake() {
+        // A snake lining the whole top edge leaves the other three edges.
+        let mut game = GameBuilder::new()
+            .with_bounds(8, 6)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(7, 0),
+                direction: Direction::Right,
+                length: 8,
+            })
+            .build();
+        for _ in 0..50 {
+            game.restart();
+            assert!(!game.snake().contains(game.food().position()));
+        }
+    }
+}
+
+mod spawn_tests {
+    use crate::core::{Direction, Edge, GameState, Vec2};
+    use crate::entities::{Snake, Walls};
+    use crate::game::{GameBuilder, SnakeStart};
+    use crate::systems::{
+        GameEvent, OccupancyGrid, SpawnCandidate, SpawnContext, SpawnError, SpawnRules, Spawner,
+    };
+    use crate::traits::{Active, Positioned};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const BOUNDS: Vec2 = Vec2::new(12, 10);
+
+    fn context<'a>(grid: &'a OccupancyGrid, snake: &Snake) -> SpawnContext<'a> {
+        SpawnContext {
+            grid,
+            head: snake.head(),
+            heading: snake.direction(),
+            food_speed: 1,
+        }
+    }
+
+    #[test]
+    fn food_keeps_its_distance_from_the_head() {
+        let snake = Snake::with_body(Vec2::new(1, 1), Direction::Down, 2);
+        let grid = OccupancyGrid::build(BOUNDS, &Walls::default(), &snake);
+        let ctx = context(&grid, &snake);
+        let spawner = Spawner::new(SpawnRules::default());
+        let candidates = spawner.candidates(&ctx);
+        assert!(!candidates.is_empty());
+        assert!(candidates
+            .iter()
+            .all(|c| c.position.manhattan_distance(snake.head()) >= 4));
+    }
+
+    #[test]
+    fn food_stays_off_the_cells_ahead_of_the_head() {
+        let snake = Snake::with_body(Vec2::new(5, 3), Direction::Up, 2);
+        let grid = OccupancyGrid::build(BOUNDS, &Walls::default(), &snake);
+        let ctx = context(&grid, &snake);
+        let rules = SpawnRules {
+            min_head_distance: 0,
+            head_lookahead: 3,
+            clear_ticks: 0,
+        };
+        let ahead = SpawnCandidate {
+            position: Vec2::new(5, 0),
+            edge: Edge::Top,
+        };
+        assert!(!Spawner::new(rules).is_fair(&ctx, &ahead));
+        assert!(Spawner::new(SpawnRules::none()).is_fair(&ctx, &ahead));
+    }
+
+    #[test]
+    fn food_is_not_launched_straight_into_the_body() {
+        // A body lying across column 1 right next to the left edge.
+        let snake = Snake::with_body(Vec2::new(1, 2), Direction::Up, 6);
+        let grid = OccupancyGrid::build(BOUNDS, &Walls::default(), &snake);
+        let ctx = context(&grid, &snake);
+        let rules = SpawnRules {
+            min_head_distance: 0,
+            head_lookahead: 0,
+            clear_ticks: 1,
+        };
+        let spawner = Spawner::new(rules);
+        let left: Vec<_> = spawner
+            .candidates(&ctx)
+            .into_iter()
+            .filter(|c| c.edge == Edge::Left)
+            .map(|c| c.position.y)
+            .collect();
+        assert!(left.iter().all(|&y| !(2..=7).contains(&y)));
+        assert!(left.contains(&0));
+    }
+
+    #[test]
+    fn failure_is_reported_instead_of_retried() {
+        let snake = Snake::new(Vec2::new(1, 1));
+        let strict = SpawnRules {
+            min_head_distance: 100,
+            ..SpawnRules::default()
+        };
+        let grid = OccupancyGrid::build(BOUNDS, &Walls::default(), &snake);
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(
+            Spawner::new(strict).choose(&context(&grid, &snake), &mut rng),
+            Err(SpawnError::NoFairCell)
+        );
+
+        let bounds = Vec2::new(3, 1);
+        let full = Snake::with_body(Vec2::new(2, 0), Direction::Right, 3);
+        let grid = OccupancyGrid::build(bounds, &Walls::default(), &full);
+        assert_eq!(
+            Spawner::default().choose(&context(&grid, &full), &mut rng),
+            Err(SpawnError::BoardFull)
+        );
+    }
+
+    #[test]
+    fn choices_repeat_for_a_seed() {
+        let snake = Snake::new(Vec2::new(6, 5));
+        let grid = OccupancyGrid::build(BOUNDS, &Walls::default(), &snake);
+        let ctx = context(&grid, &snake);
+        let pick = |seed| Spawner::default().choose(&ctx, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(pick(7), pick(7));
+        assert!(pick(7).is_ok());
+    }
+
+    #[test]
+    fn food_falls_back_to_the_interior_when_edges_are_walled() {
+        let (w, h) = (7, 5);
+        let ring = (0..w)
+            .flat_map(|x| [Vec2::new(x, 0), Vec2::new(x, h - 1)])
+            .chain((0..h).flat_map(|y| [Vec2::new(0, y), Vec2::new(w - 1, y)]));
+        let game = GameBuilder::new()
+            .with_bounds(w, h)
+            .with_walls(ring)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(1, 1),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .build();
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.food().position(), Vec2::new(3, 2));
+    }
+
+    #[test]
+    fn a_full_board_is_a_win() {
+        let mut game = GameBuilder::new()
+            .with_bounds(3, 1)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(2, 0),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        assert_eq!(game.state(), GameState::Won);
+        assert!(!game.food().is_active());
+
+        let mut filled = false;
+        while let Some(timed) = game.events().pop() {
+            filled |= timed.event == GameEvent::BoardFilled;
+        }
+        assert!(filled);
+    }
+}
+
+mod world_tests {
+    use crate::core::{Direction, Vec2};
+    use crate::entities::{Bullet, EntityId, World};
+    use crate::game::{Game, GameBuilder, SnakeStart};
+    use crate::renderer::{GameRenderer, RenderContext, RenderLayer};
+    use crate::systems::{CollisionKind, Occupant};
+    use crate::traits::{
+        Active, BoundedTickable, Collidable, Layered, Moveable, Positioned, Renderable,
+    };
+    use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+    // A stationary marker that burns out after a few ticks.
+    struct Beacon {
+        position: Vec2,
+        ticks_left: u8,
+        layer: RenderLayer,
+    }
+
+    impl Beacon {
+        fn new(position: Vec2, layer: RenderLayer) -> Self {
+            Self {
+                position,
+                ticks_left: 3,
+                layer,
+            }
+        }
+    }
+
+    impl Positioned for Beacon {
+        fn position(&self) -> Vec2 {
+            self.position
+        }
+
+        fn set_position(&mut self, pos: Vec2) {
+            self.position = pos;
+        }
+    }
+
+    impl Renderable for Beacon {
+        fn render(&self, ctx: &RenderContext, buf: &mut Buffer) {
+            let (x, y) = self.position.to_screen(ctx.offset);
+            buf.set_string(x, y, "B", Style::default());
+        }
+    }
+
+    impl Layered for Beacon {
+        fn layer(&self) -> RenderLayer {
+            self.layer
+        }
+    }
+
+    impl BoundedTickable for Beacon {
+        fn tick(&mut self, _bounds: Vec2) {
+            self.ticks_left = self.ticks_left.saturating_sub(1);
+        }
+    }
+
+    impl Active for Beacon {
+        fn is_active(&self) -> bool {
+            self.ticks_left > 0
+        }
+
+        fn deactivate(&mut self) {
+            self.ticks_left = 0;
+        }
+    }
+
+    // Burns out as soon as anything runs into it.
+    impl Collidable for Beacon {
+        fn on_collision(&mut self, _kind: CollisionKind) {
+            self.ticks_left = 0;
+        }
+    }
+
+    fn game_with_snake_at(head: Vec2) -> Game {
+        let mut game = GameBuilder::new()
+            .with_bounds(10, 6)
+            .with_snake_start(SnakeStart {
+                position: head,
+                direction: Direction::Right,
+                length: 3,
+            })
+            .build();
+        game.food_mut().set_position(Vec2::new(9, 5));
+        game.food_mut().set_velocity(Vec2::zero());
+        game
+    }
+
+    fn entity_hits(game: &Game) -> Vec<(Vec2, Option<EntityId>)> {
+        game.collisions()
+            .iter()
+            .filter(|c| c.kind == CollisionKind::SnakeEntity)
+            .map(|c| (c.position, c.entity))
+            .collect()
+    }
+
+    #[test]
+    fn snake_runs_into_a_world_entity() {
+        let mut game = game_with_snake_at(Vec2::new(3, 2));
+        let id = game
+            .world_mut()
+            .spawn(Beacon::new(Vec2::new(4, 2), RenderLayer::Items));
+
+        game.move_snake();
+        assert_eq!(game.snake().head(), Vec2::new(4, 2));
+        assert_eq!(entity_hits(&game), vec![(Vec2::new(4, 2), Some(id))]);
+        assert!(game.world().get(id).is_some_and(|e| !e.is_active()));
+
+        game.tick();
+        assert!(!game.world().contains(id));
+    }
+
+    #[test]
+    fn world_entity_moving_onto_the_body_collides() {
+        let mut game = game_with_snake_at(Vec2::new(3, 2));
+        let id = game
+            .world_mut()
+            .spawn(Bullet::new(Vec2::new(2, 0), Direction::Down));
+        let parked = game
+            .world_mut()
+            .spawn(Beacon::new(Vec2::new(1, 2), RenderLayer::Items));
+
+        game.tick();
+        assert_eq!(entity_hits(&game), vec![(Vec2::new(2, 2), Some(id))]);
+        assert!(game.world().contains(parked), "already there, so no hit");
+
+        game.tick();
+        assert!(entity_hits(&game).is_empty());
+    }
+
+    #[test]
+    fn world_entities_take_up_grid_cells() {
+        let mut game = game_with_snake_at(Vec2::new(3, 2));
+        let cell = Vec2::new(6, 4);
+        let id = game
+            .world_mut()
+            .spawn(Beacon::new(cell, RenderLayer::Items));
+        game.tick();
+        assert_eq!(game.occupancy().occupant(cell), Some(Occupant::Entity));
+        // Not in the snake's way, but not somewhere food can spawn either.
+        assert!(!game.occupancy().is_blocked(cell));
+        assert!(!game.occupancy().is_free(cell));
+
+        game.world_mut().despawn(id);
+        game.tick();
+        assert_eq!(game.occupancy().occupant(cell), None);
+    }
+
+    #[test]
+    fn ids_are_stable_and_never_reused() {
+        let mut world = World::new();
+        let a = world.spawn(Beacon::new(Vec2::new(1, 1), RenderLayer::Items));
+        let b = world.spawn(Bullet::new(Vec2::new(2, 2), Direction::Right));
+        assert!(a < b);
+
+        assert!(world.despawn(a).is_some());
+        let c = world.spawn(Beacon::new(Vec2::new(3, 3), RenderLayer::Items));
+        assert!(c > b);
+        assert!(!world.contains(a));
+        assert_eq!(world.len(), 2);
+    }
+
+    #[test]
+    fn entities_are_fetched_by_type() {
+        let mut world = World::new();
+        let beacon = world.spawn(Beacon::new(Vec2::new(1, 1), RenderLayer::Items));
+        let bullet = world.spawn(Bullet::new(Vec2::new(2, 2), Direction::Right));
+
+        assert!(world.get_as::<Beacon>(beacon).is_some());
+        assert!(world.get_as::<Bullet>(beacon).is_none());
+        if let Some(b) = world.get_as_mut::<Bullet>(bullet) {
+            b.set_position(Vec2::new(5, 5));
+        }
+        assert_eq!(
+            world.get(bullet).map(|e| e.position()),
+            Some(Vec2::new(5, 5))
+        );
+        assert_eq!(world.of_type::<Beacon>().count(), 1);
+        assert_eq!(world.at(Vec2::new(5, 5)), Some(bullet));
+        assert_eq!(world.at(Vec2::new(9, 9)), None);
+    }
+
+    #[test]
+    fn tick_drops_inactive_entities() {
+        let mut world = World::new();
+        let beacon = world.spawn(Beacon::new(Vec2::new(1, 1), RenderLayer::Items));
+        let bullet = world.spawn(Bullet::new(Vec2::new(9, 1), Direction::Right));
+
+        assert_eq!(world.tick(Vec2::new(10, 10)), vec![bullet]);
+        assert_eq!(world.tick(Vec2::new(10, 10)), vec![]);
+        assert_eq!(world.tick(Vec2::new(10, 10)), vec![beacon]);
+        assert!(world.is_empty());
+    }
+
+    #[test]
+    fn only_spawned_entities_count_as_changes() {
+        let mut world = World::new();
+        assert!(!world.take_changed());
+
+        let id = world.spawn(Beacon::new(Vec2::new(1, 1), RenderLayer::Items));
+        assert!(world.take_changed());
+        assert!(!world.take_changed());
+
+        assert!(world.get(id).is_some());
+        world.food_mut().set_position(Vec2::new(4, 4));
+        assert!(!world.take_changed(), "reads and core entities don't count");
+
+        world.tick(Vec2::new(10, 10));
+        assert!(world.take_changed());
+        world.despawn(id);
+        assert!(world.take_changed());
+        world.tick(Vec2::new(10, 10));
+        assert!(!world.take_changed(), "nothing left to move");
+    }
+
+    #[test]
+    fn core_entities_live_in_the_world() {
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 10)
+            .with_rival(SnakeStart {
+                position: Vec2::new(15, 7),
+                direction: Direction::Left,
+                length: 2,
+            })
+            .build();
+        assert_eq!(game.world().snakes().len(), 2);
+        assert_eq!(game.world().snakes()[0].head(), game.snake().head());
+        assert_eq!(game.world().food().position(), game.food().position());
+        assert!(game.fire());
+        assert_eq!(game.world().bullets().active_count(), 1);
+        // Only the spawned entities are counted and iterated.
+        assert!(game.world().is_empty());
+
+        let layers: Vec<RenderLayer> = game.drawables().iter().map(|d| d.layer()).collect();
+        let bodies = layers.iter().filter(|&&l| l == RenderLayer::Bodies).count();
+        assert_eq!(bodies, 2);
+    }
+
+    #[test]
+    fn game_draws_world_entities_by_layer() {
+        let mut game = Game::new(10, 5);
+        let head = game.snake().head();
+        let beside = head + Vec2::new(0, 1);
+        game.world_mut()
+            .spawn(Beacon::new(head, RenderLayer::Terrain));
+        game.world_mut()
+            .spawn(Beacon::new(beside, RenderLayer::Items));
+
+        let layers: Vec<RenderLayer> = game.drawables().iter().map(|d| d.layer()).collect();
+        assert!(layers.windows(2).all(|w| w[0] <= w[1]));
+
+        let area = Rect::new(0, 0, 22, 7);
+        let mut buf = Buffer::empty(area);
+        GameRenderer::new(&game).render(area, &mut buf);
+        let cell = |pos: Vec2| {
+            buf[(1 + pos.x as u16 * 2, 1 + pos.y as u16)]
+                .symbol()
+                .to_string()
+        };
+        // The snake is drawn over terrain but the item shows.
+        assert_ne!(cell(head), "B");
+        assert_eq!(cell(beside), "B");
+    }
+
+    #[test]
+    fn world_ticks_with_the_game_and_clears_on_restart() {
+        let mut game = Game::new(10, 5);
+        let id = game
+            .world_mut()
+            .spawn(Beacon::new(Vec2::new(0, 0), RenderLayer::Items));
+        game.tick();
+        assert_eq!(
+            game.world().get_as::<Beacon>(id).map(|b| b.ticks_left),
+            Some(2)
+        );
+
+        game.restart();
+        assert!(game.world().is_empty());
+    }
+}
+
+mod rule_tests {
+    use crate::core::{Direction, GameState, Vec2};
+    use crate::game::{Game, GameBuilder, SnakeStart};
+    use crate::rules::GameRule;
+    use crate::systems::{Collision, GameEvent};
+    use crate::traits::Moveable;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    struct Recorder(Log);
+
+    impl GameRule for Recorder {
+        fn on_tick(&mut self, _game: &mut Game) {
+            self.0.borrow_mut().push("tick".into());
+        }
+
+        fn on_snake_move(&mut self, _game: &mut Game, head: Vec2) {
+            self.0
+                .borrow_mut()
+                .push(format!("move {},{}", head.x, head.y));
+        }
+
+        fn on_event(&mut self, _game: &mut Game, event: &GameEvent) {
+            self.0.borrow_mut().push(format!("event {event}"));
+        }
+
+        fn on_collision(&mut self, _game: &mut Game, collision: &Collision) {
+            self.0
+                .borrow_mut()
+                .push(format!("hit {}", collision.kind.as_str()));
+        }
+
+        fn on_spawn(&mut self, _game: &mut Game) {
+            self.0.borrow_mut().push("spawn".into());
+        }
+    }
+
+    // The house variant from the request: every food that gets away makes
+    // the next one faster, up to the fastest speed the sweep handles.
+    #[derive(Default)]
+    struct FoodSpeedsUpWhenMissed {
+        spawned: bool,
+        caught: bool,
+        speed: i16,
+    }
+
+    impl GameRule for FoodSpeedsUpWhenMissed {
+        fn on_event(&mut self, _game: &mut Game, event: &GameEvent) {
+            match event {
+                GameEvent::FoodCollected { .. } => self.caught = true,
+                GameEvent::GameRestarted => *self = Self::default(),
+                _ => {}
+            }
+        }
+
+        fn on_spawn(&mut self, game: &mut Game) {
+            if self.speed == 0 {
+                self.speed = game.food().config().speed_multiplier;
+            }
+            if self.spawned && !self.caught {
+                self.speed = (self.speed + 1).min(4);
+            }
+            self.spawned = true;
+            self.caught = false;
+            let food = game.food_mut();
+            let v = food.velocity();
+            food.set_velocity(Vec2::new(v.x.signum(), v.y.signum()) * self.speed);
+        }
+    }
+
+    fn speed(game: &Game) -> i16 {
+        let v = game.food().velocity();
+        v.x.abs().max(v.y.abs())
+    }
+
+    fn recorded(seed: u64) -> (Game, Log) {
+        let log = Log::default();
+        let game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_seed(seed)
+            .with_rule(Recorder(log.clone()))
+            .build();
+        (game, log)
+    }
+
+    #[test]
+    fn rules_see_the_first_spawn() {
+        let (game, log) = recorded(1);
+        assert_eq!(game.rule_count(), 1);
+        assert_eq!(*log.borrow(), vec!["spawn"]);
+    }
+
+    #[test]
+    fn hooks_fire_at_their_points() {
+        let (mut game, log) = recorded(1);
+        let head = game.snake().head();
+        log.borrow_mut().clear();
+
+        game.move_snake();
+        let next = head + game.snake().direction().to_vec2();
+        assert!(log
+            .borrow()
+            .contains(&format!("move {},{}", next.x, next.y)));
+
+        log.borrow_mut().clear();
+        game.tick();
+        assert_eq!(log.borrow().last().map(String::as_str), Some("tick"));
+
+        log.borrow_mut().clear();
+        game.toggle_pause();
+        assert_eq!(*log.borrow(), vec!["event paused"]);
+    }
+
+    #[test]
+    fn collisions_reach_rules_after_their_event() {
+        let log = Log::default();
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_seed(3)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(5, 15),
+                direction: Direction::Right,
+                length: 3,
+            })
+            .with_walls((0..20).map(|y| Vec2::new(9, y)))
+            .with_rule(Recorder(log.clone()))
+            .build();
+        game.aim_at(Vec2::new(19, 15));
+        assert!(game.fire());
+        for _ in 0..4 {
+            game.tick();
+        }
+        let log = log.borrow();
+        let hit = log.iter().position(|l| l == "hit bullet/wall");
+        let event = log
+            .iter()
+            .position(|l| l.starts_with("event") && l.contains("bullet/wall"));
+        assert!(hit.is_some());
+        assert!(event < hit);
+    }
+
+    struct Echo(Rc<RefCell<u32>>);
+
+    impl GameRule for Echo {
+        fn on_event(&mut self, game: &mut Game, _event: &GameEvent) {
+            *self.0.borrow_mut() += 1;
+            game.emit(GameEvent::ComboBroken { streak: 0 });
+        }
+    }
+
+    #[test]
+    fn events_from_rules_are_not_fed_back() {
+        let heard = Rc::new(RefCell::new(0));
+        let mut game = GameBuilder::new()
+            .with_bounds(20, 20)
+            .with_seed(1)
+            .with_rule(Echo(heard.clone()))
+            .build();
+        game.events().drain();
+
+        game.toggle_pause();
+        assert_eq!(*heard.borrow(), 1);
+        let events: Vec<GameEvent> = game.events().drain().map(|e| e.event).collect();
+        assert_eq!(
+            events,
+            vec![GameEvent::GamePaused, GameEvent::ComboBroken { streak: 0 }]
+        );
+    }
+
+    #[test]
+    fn missed_food_comes_back_faster() {
+        let mut game = GameBuilder::new()
+            .with_bounds(12, 12)
+            .with_seed(7)
+            .with_snake_start(SnakeStart {
+                position: Vec2::new(0, 11),
+                direction: Direction::Right,
+                length: 1,
+            })
+            .with_rule(FoodSpeedsUpWhenMissed::default())
+            .build();
+        assert_eq!(speed(&game), 1);
+
+        let mut fastest = 1;
+        for _ in 0..60 {
+            game.tick();
+            fastest = fastest.max(speed(&game));
+        }
+        assert_eq!(game.state(), GameState::Playing);
+        assert!(fastest > 1);
+        assert!(fastest <= 4);
+    }
+
+    #[test]
+    fn rules_survive_restart_and_hear_about_it() {
+        let (mut game, log) = recorded(1);
+        log.borrow_mut().clear();
+        game.restart();
+        assert_eq!(game.rule_count(), 1);
+        assert_eq!(*log.borrow(), vec!["spawn", "event restarted"]);
+    }
+}
+
+mod bot_tests {
+    use crate::bot::{
+        run_match, state_message, ArenaConfig, Bot, BotAction, BotError, BotProcess, MatchEnd,
+        MatchReport,
+    };
+    use crate::core::{Direction, Vec2};
+    use crate::systems::DeathCause;
+    use serde_json::Value;
+    use std::time::Duration;
+
+    // Plays a fixed action every turn, or fails once it has seen `fail_at`
+    // states.
+    struct Scripted {
+        action: BotAction,
+        fail_at: Option<usize>,
+        seen: Vec<Value>,
+        end: Option<Value>,
+    }
+
+    impl Scripted {
+        fn new(action: BotAction) -> Self {
+            Self {
+                action,
+                fail_at: None,
+                seen: Vec::new(),
+                end: None,
+            }
+        }
+    }
+
+    impl Bot for Scripted {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn act(&mut self, state: &Value) -> Result<BotAction, BotError> {
+            self.seen.push(state.clone());
+            if self.fail_at == Some(self.seen.len()) {
+                return Err(BotError::Timeout(Duration::from_millis(5)));
+            }
+            Ok(self.action)
+        }
+
+        fn finish(&mut self, end: &Value) {
+            self.end = Some(end.clone());
+        }
+    }
+
+    fn config() -> ArenaConfig {
+        ArenaConfig {
+            max_ticks: 50,
+            ..ArenaConfig::new(7)
+        }
+    }
+
+    fn solo(bot: &mut dyn Bot) -> MatchReport {
+        run_match(&config(), &mut [bot]).remove(0)
+    }
+
+    #[test]
+    fn malformed_replies_are_rejected() {
+        for source in ["", "{", "[1,]", "{\"a\" 1}", "nul", "1 2", "\"open", "01x"] {
+            assert!(source.parse::<BotAction>().is_err(), "{source}");
+        }
+        let deep = "[".repeat(10_000) + &"]".repeat(10_000);
+        assert!(deep.parse::<BotAction>().is_err());
+    }
+
+    #[test]
+    fn actions_parse_from_replies() {
+        assert_eq!("{\"action\":\"wait\"}".parse(), Ok(BotAction::Wait));
+        assert_eq!(
+            " {\"action\": \"left\"} ".parse(),
+            Ok(BotAction::Move(Direction::Left))
+        );
+        assert_eq!("{\"action\":\"fire\"}".parse(), Ok(BotAction::Fire(None)));
+        assert_eq!(
+            "{\"action\":\"fire\",\"target\":[3,4]}".parse(),
+            Ok(BotAction::Fire(Some(Vec2::new(3, 4))))
+        );
+        for bad in [
+            "{}",
+            "{\"action\":3}",
+            "{\"action\":\"jump\"}",
+            "{\"action\":\"fire\",\"target\":[1.5,2]}",
+            "left",
+        ] {
+            assert!(bad.parse::<BotAction>().is_err(), "{bad}");
+        }
+        let fire = BotAction::Fire(Some(Vec2::new(1, 2)));
+        assert_eq!(fire.to_string().parse(), Ok(fire));
+    }
+
+    #[test]
+    fn state_carries_what_a_bot_needs() {
+        let game = config().build_game(1);
+        let state = state_message(&game, 0, &[]);
+        assert_eq!(state.get("type").and_then(Value::as_str), Some("state"));
+        assert_eq!(
+            state.get("bounds").map(Value::to_string).as_deref(),
+            Some("[30,20]")
+        );
+        let head = game.snake().head();
+        let snake = state
+            .get("snake")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        assert_eq!(snake.len(), game.snake().length());
+        assert_eq!(snake[0].to_string(), format!("[{},{}]", head.x, head.y));
+        for key in [
+            "food", "bullets", "walls", "cooldown", "events", "tick", "you", "snakes",
+        ] {
+            assert!(state.get(key).is_some(), "{key}");
+        }
+        let food = state.get("food").and_then(|f| f.get("velocity"));
+        assert!(food.and_then(Value::as_array).is_some());
+    }
+
+    #[test]
+    fn waiting_bot_runs_to_the_tick_limit() {
+        let mut bot = Scripted::new(BotAction::Wait);
+        let report = solo(&mut bot);
+        assert_eq!(report.end, MatchEnd::TickLimit);
+        assert_eq!(report.ticks, 50);
+        assert_eq!(bot.seen.len(), 50);
+        let end = bot
+            .end
+            .as_ref()
+            .and_then(|e| e.get("result"))
+            .and_then(Value::as_str);
+        assert_eq!(end, Some("tick limit"));
+    }
+
+    #[test]
+    fn bot_steering_into_the_wall_dies() {
+        let mut bot = Scripted::new(BotAction::Move(Direction::Up));
+        let report = solo(&mut bot);
+        assert_eq!(report.end, MatchEnd::Died(DeathCause::HitWall));
+        assert!(report.ticks < 20);
+    }
+
+    #[test]
+    fn matches_are_repeatable_for_a_seed() {
+        let fire = BotAction::Fire(None);
+        let first = solo(&mut Scripted::new(fire));
+        let second = solo(&mut Scripted::new(fire));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn bots_share_one_arena() {
+        let mut left = Scripted::new(BotAction::Wait);
+        let mut right = Scripted::new(BotAction::Wait);
+        let reports = run_match(&config(), &mut [&mut left, &mut right]);
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.end == MatchEnd::TickLimit));
+
+        let first = |bot: &Scripted| bot.seen.first().cloned().unwrap_or_default();
+        let (a, b) = (first(&left), first(&right));
+        assert_eq!(a.get("you"), Some(&Value::from(0)));
+        assert_eq!(b.get("you"), Some(&Value::from(1)));
+        assert_eq!(a.get("snakes"), b.get("snakes"));
+        let snakes = a.get("snakes").and_then(Value::as_array).map(Vec::len);
+        assert_eq!(snakes, Some(2));
+        assert_ne!(a.get("snake"), b.get("snake"));
+    }
+
+    #[test]
+    fn snakes_meeting_head_on_both_die() {
+        // They start on the middle row facing each other.
+        let mut left = Scripted::new(BotAction::Move(Direction::Right));
+        let mut right = Scripted::new(BotAction::Move(Direction::Left));
+        let reports = run_match(&config(), &mut [&mut left, &mut right]);
+        for report in &reports {
+            assert_eq!(report.end, MatchEnd::Died(DeathCause::HitSnake));
+        }
+        assert_eq!(reports[0].ticks, reports[1].ticks);
+        assert!(left.end.is_some() && right.end.is_some());
+    }
+
+    #[test]
+    fn the_last_snake_left_wins() {
+        let mut crasher = Scripted::new(BotAction::Move(Direction::Up));
+        let mut waiter = Scripted::new(BotAction::Wait);
+        let reports = run_match(&config(), &mut [&mut crasher, &mut waiter]);
+        assert_eq!(reports[0].end, MatchEnd::Died(DeathCause::HitWall));
+        assert_eq!(reports[1].end, MatchEnd::Won);
+        assert_eq!(reports[0].ticks, reports[1].ticks);
+    }
+
+    #[test]
+    fn faults_end_the_match_and_keep_the_score() {
+        let mut bot = Scripted {
+            fail_at: Some(3),
+            ..Scripted::new(BotAction::Wait)
+        };
+        let report = solo(&mut bot);
+        assert!(report.end.is_fault());
+        assert_eq!(report.ticks, 2);
+        assert!(bot.end.is_some());
+    }
+
+    #[test]
+    fn faults_round_trip_through_json() {
+        for fault in [
+            BotError::Spawn("No such file".to_string()),
+            BotError::Timeout(Duration::from_millis(150)),
+            BotError::Malformed {
+                reply: "{\"action\":\"jump\"}".to_string(),
+                reason: "unknown action".to_string(),
+            },
+            BotError::Crashed(Some(3)),
+            BotError::Crashed(None),
+        ] {
+            assert_eq!(BotError::from_json(&fault.to_json()), Ok(fault));
+        }
+        assert!(BotError::from_json(&Value::Null).is_err());
+    }
+
+    fn process_fault(command: &str) -> Option<BotError> {
+        let mut bot = BotProcess::spawn(command, Duration::from_millis(100))
+            .ok()?
+            .with_startup_grace(Duration::ZERO);
+        match solo(&mut bot).end {
+            MatchEnd::Fault(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn process_bots_are_reported_when_they_misbehave() {
+        assert!(matches!(
+            process_fault("cat"),
+            Some(BotError::Malformed { .. })
+        ));
+        assert_eq!(process_fault("true"), Some(BotError::Crashed(Some(0))));
+        assert_eq!(
+            process_fault("sleep 5"),
+            Some(BotError::Timeout(Duration::from_millis(100)))
+        );
+        assert!(matches!(
+            BotProcess::spawn("./no-such-bot", Duration::from_millis(100)),
+            Err(BotError::Spawn(_))
+        ));
+    }
+}
+
+mod tournament_tests {
+    use crate::bot::{
+        elo_update, expected_score, replay, ArenaConfig, BotAction, BotError, Entrant, MatchEnd,
+        MatchRecord, Strategy, Tournament, TournamentConfig, INITIAL_RATING,
+    };
+    use crate::core::Direction;
+    use serde_json::json;
+
+    fn config() -> TournamentConfig {
+        TournamentConfig {
+            arena: ArenaConfig {
+                max_ticks: 200,
+                ..ArenaConfig::new(11)
+            },
+            ..TournamentConfig::new(11)
+        }
+    }
+
+    #[test]
+    fn hunter_never_steps_onto_its_own_tail() {
+        // Curled up so the tail sits right of the head, the step that
+        // would get closest to the food.
+        let state = json!({
+            "bounds": [10, 10],
+            "snake": [[5, 5], [5, 6], [6, 6], [6, 5]],
+            "direction": "up",
+            "walls": [],
+            "food": { "active": true, "position": [8, 5], "velocity": [0, 0] },
+            "cooldown": 3,
+        });
+        let action = Strategy::Hunter.bot().act(&state);
+        assert!(action.is_ok());
+        assert_ne!(action, Ok(BotAction::Move(Direction::Right)));
+    }
+
+    fn builtins() -> Vec<Entrant> {
+        Strategy::ALL.into_iter().map(Entrant::Builtin).collect()
+    }
+
+    #[test]
+    fn elo_is_zero_sum_and_favours_upsets() {
+        assert_eq!(expected_score(1500.0, 1500.0), 0.5);
+        assert_eq!(elo_update(1500.0, 1500.0, 1.0), (1516.0, 1484.0));
+        assert_eq!(elo_update(1500.0, 1500.0, 0.5), (1500.0, 1500.0));
+
+        let (fav, dog) = elo_update(1700.0, 1300.0, 0.0);
+        assert!(1700.0 - fav > 16.0);
+        assert!((fav + dog - 3000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn entrants_prefer_builtins_by_name() {
+        assert_eq!(Entrant::parse("Hunter"), Entrant::Builtin(Strategy::Hunter));
+        assert_eq!(
+            Entrant::parse("./hunter"),
+            Entrant::Command("./hunter".to_string())
+        );
+        assert!("camper".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn every_pair_meets_once_a_round() {
+        let tournament = Tournament::new(
+            builtins(),
+            TournamentConfig {
+                rounds: 2,
+                ..config()
+            },
+        );
+        let schedule = tournament.schedule();
+        assert_eq!(schedule.len(), 6);
+        for pair in [(0, 1), (0, 2), (1, 2)] {
+            let meetings = schedule.iter().filter(|&&(_, a, b)| (a, b) == pair).count();
+            assert_eq!(meetings, 2);
+        }
+    }
+
+    #[test]
+    fn tournaments_repeat_for_a_seed() {
+        let tournament = Tournament::new(builtins(), config());
+        let first = tournament.run(|_| {});
+        let second = tournament.run(|_| {});
+        assert_eq!(first.matches, second.matches);
+        assert_eq!(first.standings, second.standings);
+
+        let total: f64 = first.standings.iter().map(|s| s.rating).sum();
+        assert!((total - INITIAL_RATING * 3.0).abs() < 1e-9);
+        assert_eq!(first.matches.len(), 3);
+        assert!(first.matches.iter().all(|m| m.sides[0].report.ticks > 0));
+    }
+
+    #[test]
+    fn pairs_swap_snakes_every_other_round() {
+        let tournament = Tournament::new(
+            builtins(),
+            TournamentConfig {
+                rounds: 2,
+                ..config()
+            },
+        );
+        let seats = |m: &MatchRecord| m.sides.iter().map(|s| s.entrant).collect::<Vec<_>>();
+        let matches = tournament.run(|_| {}).matches;
+        assert_eq!(seats(&matches[0]), vec![0, 1]);
+        assert_eq!(seats(&matches[3]), vec![1, 0]);
+    }
+
+    #[test]
+    fn matches_get_their_own_arenas() {
+        let tournament = Tournament::new(builtins(), config());
+        let mut seen = Vec::new();
+        let result = tournament.run(|record| seen.push(record.index));
+        assert_eq!(seen, vec![0, 1, 2]);
+        let seeds: Vec<u64> = result.matches.iter().map(|m| m.arena.seed).collect();
+        assert_ne!(seeds[0], seeds[1]);
+    }
+
+    #[test]
+    fn replays_play_back_to_the_same_result() {
+        let tournament = Tournament::new(builtins(), config());
+        let names = tournament.names();
+        for record in tournament.run(|_| {}).matches {
+            let replayed = replay(&record.replay(&names));
+            let replayed = replayed.unwrap_or_default();
+            assert_eq!(replayed.len(), 2);
+            for (side, (name, report)) in record.sides.iter().zip(&replayed) {
+                assert_eq!(name, &names[side.entrant]);
+                assert_eq!(report, &side.report);
+            }
+        }
+        assert!(replay("").is_err());
+        assert!(replay("{\"type\":\"match\"}").is_err());
+    }
+
+    #[test]
+    fn bots_that_fail_to_start_forfeit() {
+        let tournament = Tournament::new(
+            vec![
+                Entrant::Builtin(Strategy::Idle),
+                Entrant::Command("./no-such-bot".to_string()),
+            ],
+            config(),
+        );
+        let result = tournament.run(|_| {});
+        assert_eq!(result.matches[0].winner(), Some(0));
+        let record = &result.matches[0];
+        let replayed = replay(&record.replay(&tournament.names())).unwrap_or_default();
+        let reports: Vec<_> = replayed.into_iter().map(|(_, report)| report).collect();
+        let recorded: Vec<_> = record.sides.iter().map(|s| s.report.clone()).collect();
+        assert_eq!(reports, recorded);
+        assert!(matches!(
+            recorded[1].end,
+            MatchEnd::Fault(BotError::Spawn(_))
+        ));
+        let loser = &result.standings[1];
+        assert_eq!(loser.name, "./no-such-bot");
+        assert_eq!((loser.losses, loser.faults), (1, 1));
+        assert!(loser.rating < INITIAL_RATING);
+    }
+}
@@ -0,0 +1,384 @@
+use ratatui::style::Color;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Monochrome,
+    HighContrast,
+    ColorBlind,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Default,
+        ThemeName::Monochrome,
+        ThemeName::HighContrast,
+        ThemeName::ColorBlind,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ThemeName::Default => "default",
+            ThemeName::Monochrome => "monochrome",
+            ThemeName::HighContrast => "high-contrast",
+            ThemeName::ColorBlind => "colorblind",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            ThemeName::Default => Palette {
+                snake_head: Color::Rgb(200, 255, 200),
+                snake_body: Color::White,
+                food: Color::Rgb(138, 43, 226),
+                bullet_fresh: (255, 255, 0),
+                bullet_spent: (255, 0, 0),
+                scope_aligned: Color::Green,
+                scope_unaligned: Color::White,
+                border: Color::DarkGray,
+                text: Color::White,
+                hud: Color::Cyan,
+                accent: Color::Yellow,
+                muted: Color::DarkGray,
+                danger: Color::Red,
+            },
+            ThemeName::Monochrome => Palette {
+                snake_head: Color::White,
+                snake_body: Color::Gray,
+                food: Color::White,
+                bullet_fresh: (255, 255, 255),
+                bullet_spent: (128, 128, 128),
+                scope_aligned: Color::White,
+                scope_unaligned: Color::DarkGray,
+                border: Color::DarkGray,
+                text: Color::White,
+                hud: Color::White,
+                accent: Color::White,
+                muted: Color::DarkGray,
+                danger: Color::White,
+            },
+            ThemeName::HighContrast => Palette {
+                snake_head: Color::LightGreen,
+                snake_body: Color::White,
+                food: Color::LightMagenta,
+                bullet_fresh: (255, 255, 0),
+                bullet_spent: (255, 128, 0),
+                scope_aligned: Color::LightGreen,
+                scope_unaligned: Color::White,
+                border: Color::White,
+                text: Color::White,
+                hud: Color::LightCyan,
+                accent: Color::LightYellow,
+                muted: Color::Gray,
+                danger: Color::LightRed,
+            },
+            // Okabe-Ito palette: distinguishable under the common forms of
+            // colour-vision deficiency.
+            ThemeName::ColorBlind => Palette {
+                snake_head: Color::Rgb(0, 158, 115),
+                snake_body: Color::Rgb(240, 240, 240),
+                food: Color::Rgb(230, 159, 0),
+                bullet_fresh: (240, 228, 66),
+                bullet_spent: (213, 94, 0),
+                scope_aligned: Color::Rgb(86, 180, 233),
+                scope_unaligned: Color::Gray,
+                border: Color::DarkGray,
+                text: Color::White,
+                hud: Color::Rgb(86, 180, 233),
+                accent: Color::Rgb(240, 228, 66),
+                muted: Color::DarkGray,
+                danger: Color::Rgb(213, 94, 0),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|t| t.as_str()).collect();
+                format!(
+                    "unknown theme '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub snake_head: Color,
+    pub snake_body: Color,
+    pub food: Color,
+    pub bullet_fresh: (u8, u8, u8),
+    pub bullet_spent: (u8, u8, u8),
+    pub scope_aligned: Color,
+    pub scope_unaligned: Color,
+    pub border: Color,
+    pub text: Color,
+    pub hud: Color,
+    pub accent: Color,
+    pub muted: Color,
+    pub danger: Color,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ColorDepth {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorDepth {
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        let colorterm = colorterm.unwrap_or("").to_ascii_lowercase();
+        let term = term.unwrap_or("").to_ascii_lowercase();
+
+        if colorterm == "truecolor" || colorterm == "24bit" || term.contains("direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    pub fn adapt(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, c) => c,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(rgb_to_ansi256(r, g, b)),
+            (ColorDepth::Ansi256, c) => c,
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16((r, g, b)),
+            (ColorDepth::Ansi16, Color::Indexed(i)) => nearest_ansi16(ansi256_to_rgb(i)),
+            (ColorDepth::Ansi16, c) => c,
+        }
+    }
+}
+
+impl FromStr for ColorDepth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::detect()),
+            "truecolor" | "24bit" => Ok(ColorDepth::TrueColor),
+            "256" => Ok(ColorDepth::Ansi256),
+            "16" => Ok(ColorDepth::Ansi16),
+            _ => Err(format!(
+                "unknown colour depth '{}', expected auto, truecolor, 256 or 16",
+                s
+            )),
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> i32 {
+    let dr = r1 as i32 - r2 as i32;
+    let dg = g1 as i32 - g2 as i32;
+    let db = b1 as i32 - b2 as i32;
+    dr * dr + dg * dg + db * db
+}
+
+fn nearest_cube_index(v: u8) -> usize {
+    CUBE_LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &level)| (level as i32 - v as i32).abs())
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let (ri, gi, bi) = (
+        nearest_cube_index(r),
+        nearest_cube_index(g),
+        nearest_cube_index(b),
+    );
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (r as u16 + g as u16 + b as u16) / 3;
+    let gray_step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + gray_step * 10;
+    let gray = (gray_level, gray_level, gray_level);
+
+    if distance((r, g, b), gray) < distance((r, g, b), cube) {
+        232 + gray_step
+    } else {
+        cube_index as u8
+    }
+}
+
+fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize].1,
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+fn nearest_ansi16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    name: ThemeName,
+    palette: Palette,
+    depth: ColorDepth,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName, depth: ColorDepth) -> Self {
+        Self {
+            name,
+            palette: name.palette(),
+            depth,
+        }
+    }
+
+    pub fn named(name: ThemeName) -> Self {
+        Self::new(name, ColorDepth::detect())
+    }
+
+    pub fn name(&self) -> ThemeName {
+        self.name
+    }
+
+    pub fn depth(&self) -> ColorDepth {
+        self.depth
+    }
+
+    pub fn with_name(self, name: ThemeName) -> Self {
+        Self::new(name, self.depth)
+    }
+
+    pub fn with_depth(self, depth: ColorDepth) -> Self {
+        Self::new(self.name, depth)
+    }
+
+    fn adapt(&self, color: Color) -> Color {
+        self.depth.adapt(color)
+    }
+
+    pub fn snake_head(&self) -> Color {
+        self.adapt(self.palette.snake_head)
+    }
+
+    pub fn snake_body(&self) -> Color {
+        self.adapt(self.palette.snake_body)
+    }
+
+    pub fn food(&self) -> Color {
+        self.adapt(self.palette.food)
+    }
+
+    pub fn bullet(&self, lifetime_fraction: f32) -> Color {
+        let t = lifetime_fraction.clamp(0.0, 1.0);
+        let lerp = |spent: u8, fresh: u8| (spent as f32 + (fresh as f32 - spent as f32) * t) as u8;
+        let (fr, fg, fb) = self.palette.bullet_fresh;
+        let (sr, sg, sb) = self.palette.bullet_spent;
+        self.adapt(Color::Rgb(lerp(sr, fr), lerp(sg, fg), lerp(sb, fb)))
+    }
+
+    pub fn scope(&self, aligned: bool) -> Color {
+        if aligned {
+            self.adapt(self.palette.scope_aligned)
+        } else {
+            self.adapt(self.palette.scope_unaligned)
+        }
+    }
+
+    pub fn border(&self) -> Color {
+        self.adapt(self.palette.border)
+    }
+
+    pub fn text(&self) -> Color {
+        self.adapt(self.palette.text)
+    }
+
+    pub fn hud(&self) -> Color {
+        self.adapt(self.palette.hud)
+    }
+
+    pub fn accent(&self) -> Color {
+        self.adapt(self.palette.accent)
+    }
+
+    pub fn muted(&self) -> Color {
+        self.adapt(self.palette.muted)
+    }
+
+    pub fn danger(&self) -> Color {
+        self.adapt(self.palette.danger)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeName::Default, ColorDepth::TrueColor)
+    }
+}
@@ -1,4 +1,6 @@
 use crate::core::{Edge, SegmentIndex, Vec2};
+use crate::renderer::{RenderContext, RenderLayer};
+use crate::systems::CollisionKind;
 use ratatui::buffer::Buffer;
 
 pub trait Positioned {
@@ -11,6 +13,8 @@ pub trait Segmented: Positioned {
     fn segment_at(&self, index: SegmentIndex) -> Option<Vec2>;
     fn contains_position(&self, pos: Vec2) -> bool;
 
+    // These walk the body; the game itself looks positions up through
+    // `OccupancyGrid::segment_index` instead.
     fn find_segment(&self, pos: Vec2) -> Option<SegmentIndex> {
         (0..self.segment_count())
             .map(SegmentIndex::from)
@@ -52,27 +56,45 @@ pub trait Moveable: Positioned {
 }
 
 pub trait Collidable: Positioned {
-    fn collides_with<T: Positioned>(&self, other: &T) -> bool {
-        self.position() == other.position()
+    fn occupies(&self, pos: Vec2) -> bool {
+        self.position() == pos
     }
 
-    fn collides_with_any<'a, T: Positioned + 'a>(
-        &self,
-        others: impl Iterator<Item = &'a T>,
-    ) -> bool {
+    // Called on a world entity when the game reports it in a collision.
+    fn on_collision(&mut self, _kind: CollisionKind) {}
+
+    fn collides_with<T: Positioned>(&self, other: &T) -> bool
+    where
+        Self: Sized,
+    {
+        self.occupies(other.position())
+    }
+
+    fn collides_with_any<'a, T: Positioned + 'a>(&self, others: impl Iterator<Item = &'a T>) -> bool
+    where
+        Self: Sized,
+    {
         let pos = self.position();
         others.into_iter().any(|o| o.position() == pos)
     }
 }
 
 pub trait Renderable {
-    fn render(&self, offset: Vec2, buf: &mut Buffer);
+    fn render(&self, ctx: &RenderContext, buf: &mut Buffer);
 }
 
 pub trait GameEntity: Positioned + Renderable {}
 
 impl<T: Positioned + Renderable> GameEntity for T {}
 
+pub trait Layered {
+    fn layer(&self) -> RenderLayer;
+}
+
+pub trait Drawable: Renderable + Layered {}
+
+impl<T: Renderable + Layered> Drawable for T {}
+
 pub trait Targetable: Positioned {
     fn is_valid_target(&self) -> bool;
 }
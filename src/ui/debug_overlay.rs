@@ -0,0 +1,166 @@
+use crate::game::Game;
+use crate::systems::TimingWindow;
+use crate::theme::Theme;
+use crate::traits::{Moveable, Positioned};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+use std::time::Duration;
+
+const PANEL_WIDTH: u16 = 34;
+const MAX_BULLET_LINES: usize = 6;
+
+#[derive(Default)]
+pub struct DebugTimings {
+    pub tick_work: TimingWindow,
+    pub tick_interval: TimingWindow,
+    pub frame: TimingWindow,
+    pub queue_high_water: usize,
+    pub dispatched: u64,
+    pub dropped: u64,
+}
+
+pub struct DebugOverlay<'a> {
+    game: &'a Game,
+    timings: &'a DebugTimings,
+    theme: &'a Theme,
+}
+
+impl<'a> DebugOverlay<'a> {
+    pub fn new(game: &'a Game, timings: &'a DebugTimings, theme: &'a Theme) -> Self {
+        Self {
+            game,
+            timings,
+            theme,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let game = self.game;
+        let snake = game.snake();
+        let head = snake.head();
+        let food = game.food();
+        let (w, h) = game.bounds();
+        let queue = game.event_queue();
+        let turns = if snake.queued_turns().next().is_some() {
+            let queued: Vec<_> = snake.queued_turns().map(|d| format!("{:?}", d)).collect();
+            queued.join(" ")
+        } else {
+            format!("{:?}", snake.pending_direction())
+        };
+
+        let hits = if game.collisions().is_empty() {
+            "-".to_string()
+        } else {
+            let kinds: Vec<_> = game.collisions().iter().map(|c| c.kind.as_str()).collect();
+            kinds.join(" ")
+        };
+
+        let scope = match game.scope().distance_to_target() {
+            Some(d2) => format!(
+                "d={:.1}{}",
+                (d2 as f32).sqrt(),
+                if game.is_scope_aligned() {
+                    " aligned"
+                } else {
+                    ""
+                }
+            ),
+            None => "-".to_string(),
+        };
+
+        let mut lines = vec![
+            format!("grid     {}x{} tick {}", w, h, game.ticks()),
+            format!("head     ({}, {})", head.x, head.y),
+            format!("dir      {:?} -> {}", snake.direction(), turns),
+            format!(
+                "food     ({}, {}) v=({}, {})",
+                food.position().x,
+                food.position().y,
+                food.velocity().x,
+                food.velocity().y
+            ),
+            format!("scope    {}", scope),
+            format!("hits     {}", hits),
+            format!(
+                "events   {}/{} peak {}",
+                queue.len(),
+                queue.capacity(),
+                self.timings.queue_high_water
+            ),
+            format!(
+                "bus      {} sent {} dropped",
+                self.timings.dispatched, self.timings.dropped
+            ),
+            format!("tick     {}", summary(&self.timings.tick_work)),
+            format!("interval {}", summary(&self.timings.tick_interval)),
+            format!("frame    {}", summary(&self.timings.frame)),
+            format!(
+                "bullets  {}/{} cooldown {}",
+                game.bullets().active_count(),
+                game.bullets().capacity(),
+                game.bullet_cooldown()
+            ),
+        ];
+
+        for bullet in game.bullets().iter().take(MAX_BULLET_LINES) {
+            let pos = bullet.position();
+            lines.push(format!(
+                "  ({}, {}) life {}/{}",
+                pos.x,
+                pos.y,
+                bullet.lifetime(),
+                bullet.max_lifetime()
+            ));
+        }
+        lines
+    }
+}
+
+fn millis(d: Duration) -> String {
+    format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+}
+
+fn summary(window: &TimingWindow) -> String {
+    match (window.average(), window.max()) {
+        (Some(avg), Some(max)) => format!("{} avg {} max", millis(avg), millis(max)),
+        _ => "-".to_string(),
+    }
+}
+
+impl Widget for DebugOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let width = PANEL_WIDTH.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        if width < 4 || height < 3 {
+            return;
+        }
+        let panel = Rect::new(area.right() - width, area.y, width, height);
+
+        Clear.render(panel, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" DEBUG ")
+            .border_style(Style::default().fg(self.theme.danger()))
+            .render(panel, buf);
+
+        let text = Style::default().fg(self.theme.text());
+        for (i, line) in lines.iter().enumerate() {
+            let y = panel.y + 1 + i as u16;
+            if y >= panel.bottom().saturating_sub(1) {
+                break;
+            }
+            buf.set_stringn(
+                panel.x + 1,
+                y,
+                line,
+                panel.width.saturating_sub(2) as usize,
+                text,
+            );
+        }
+    }
+}
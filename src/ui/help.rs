@@ -0,0 +1,78 @@
+use super::centered_rect;
+use crate::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+const CONTROLS: [(&str, &str); 14] = [
+    ("W A S D / H J K L", "Move"),
+    ("Arrow keys", "Move"),
+    ("F / X", "Fire"),
+    ("Mouse", "Aim at the cursor"),
+    ("Left click", "Fire toward the cursor"),
+    ("Space", "Pause / resume"),
+    ("Tab", "Stats and achievements"),
+    ("T", "Cycle theme"),
+    ("G", "Cycle glyphs"),
+    ("O", "Settings"),
+    ("M", "Back to menu"),
+    ("F3", "Debug overlay"),
+    ("Enter", "Restart when dead"),
+    ("Q / Esc", "Quit"),
+];
+
+const TIPS: [&str; 3] = [
+    "The snake only moves when you press a direction.",
+    "Bullets collect food from a distance.",
+    "Food that hits your body cuts the snake.",
+];
+
+pub struct HelpScreen<'a> {
+    theme: &'a Theme,
+}
+
+impl<'a> HelpScreen<'a> {
+    pub fn new(theme: &'a Theme) -> Self {
+        Self { theme }
+    }
+}
+
+impl Widget for HelpScreen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let height = (CONTROLS.len() + TIPS.len() + 5) as u16;
+        let popup = centered_rect(56, height, area);
+
+        Clear.render(popup, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" HELP ")
+            .border_style(Style::default().fg(self.theme.hud()))
+            .render(popup, buf);
+
+        let x = popup.x + 2;
+        let width = popup.width.saturating_sub(4) as usize;
+        let bottom = popup.bottom().saturating_sub(1);
+        let mut y = popup.y + 1;
+
+        for (keys, action) in CONTROLS {
+            if y >= bottom {
+                return;
+            }
+            let line = format!("{:<20} {}", keys, action);
+            buf.set_stringn(x, y, &line, width, Style::default().fg(self.theme.text()));
+            y += 1;
+        }
+
+        y += 1;
+        for tip in TIPS {
+            if y >= bottom {
+                return;
+            }
+            buf.set_stringn(x, y, tip, width, Style::default().fg(self.theme.muted()));
+            y += 1;
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use super::{centered_rect, Themed};
+use crate::glyphs::GlyphSet;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+pub struct LevelComplete {
+    name: String,
+    score: u32,
+    stars: u8,
+    new_best: bool,
+    has_next: bool,
+    glyphs: GlyphSet,
+}
+
+impl LevelComplete {
+    pub fn new(name: &str, score: u32, stars: u8) -> Self {
+        Self {
+            name: name.to_string(),
+            score,
+            stars,
+            new_best: false,
+            has_next: false,
+            glyphs: GlyphSet::default(),
+        }
+    }
+
+    pub fn with_new_best(mut self, new_best: bool) -> Self {
+        self.new_best = new_best;
+        self
+    }
+
+    pub fn with_next(mut self, has_next: bool) -> Self {
+        self.has_next = has_next;
+        self
+    }
+
+    pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    pub fn stars(&self) -> u8 {
+        self.stars
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.has_next
+    }
+}
+
+impl Widget for Themed<'_, LevelComplete> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (panel, theme) = (self.inner, self.theme);
+        let popup = centered_rect(34, 8, area);
+        Clear.render(popup, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" LEVEL COMPLETE ")
+            .border_style(Style::default().fg(theme.accent()))
+            .render(popup, buf);
+
+        let x = popup.x + 2;
+        let width = popup.width.saturating_sub(4) as usize;
+        let text = Style::default().fg(theme.text());
+
+        buf.set_stringn(x, popup.y + 1, &panel.name, width, text);
+
+        let stars: Vec<_> = (0..3).map(|i| panel.glyphs.star(i < panel.stars)).collect();
+        buf.set_stringn(
+            x,
+            popup.y + 2,
+            stars.join(" "),
+            width,
+            Style::default().fg(theme.accent()),
+        );
+
+        let score = if panel.new_best {
+            format!("SCORE: {}  NEW BEST", panel.score)
+        } else {
+            format!("SCORE: {}", panel.score)
+        };
+        buf.set_stringn(x, popup.y + 3, score, width, text);
+
+        let hint = if panel.has_next {
+            "SPACE next  R retry  M menu"
+        } else {
+            "Campaign done!  R retry  M menu"
+        };
+        buf.set_stringn(
+            x,
+            popup.y + 5,
+            hint,
+            width,
+            Style::default().fg(theme.muted()),
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use super::{centered_rect, Themed};
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuOutcome {
+    None,
+    Selected(usize),
+    Adjust(usize, i8),
+    Back,
+}
+
+pub struct Menu {
+    title: String,
+    items: Vec<String>,
+    selected: usize,
+}
+
+impl Menu {
+    pub fn new<S: Into<String>>(
+        title: impl Into<String>,
+        items: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            items: items.into_iter().map(Into::into).collect(),
+            selected: 0,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+    }
+
+    pub fn set_items<S: Into<String>>(&mut self, items: impl IntoIterator<Item = S>) {
+        self.items = items.into_iter().map(Into::into).collect();
+        self.select(self.selected);
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> MenuOutcome {
+        if self.items.is_empty() {
+            return match code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Backspace => MenuOutcome::Back,
+                _ => MenuOutcome::None,
+            };
+        }
+
+        let len = self.items.len();
+        match code {
+            KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => {
+                self.selected = (self.selected + len - 1) % len;
+                MenuOutcome::None
+            }
+            KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('j') | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % len;
+                MenuOutcome::None
+            }
+            KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('h') => {
+                MenuOutcome::Adjust(self.selected, -1)
+            }
+            KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('l') => {
+                MenuOutcome::Adjust(self.selected, 1)
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => MenuOutcome::Selected(self.selected),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Backspace => MenuOutcome::Back,
+            _ => MenuOutcome::None,
+        }
+    }
+
+    // Hovering highlights an item, clicking picks it, and the wheel moves
+    // the selection. `area` must be the one the menu was last drawn into.
+    pub fn handle_mouse(&mut self, event: MouseEvent, area: Rect) -> MenuOutcome {
+        let hit = self.item_at(area, event.column, event.row);
+        match (event.kind, hit) {
+            (MouseEventKind::Moved, Some(i)) => {
+                self.selected = i;
+                MenuOutcome::None
+            }
+            (MouseEventKind::Down(MouseButton::Left), Some(i)) => {
+                self.selected = i;
+                MenuOutcome::Selected(i)
+            }
+            (MouseEventKind::Down(MouseButton::Right), _) => MenuOutcome::Back,
+            (MouseEventKind::ScrollUp, _) => self.handle_key(KeyCode::Up),
+            (MouseEventKind::ScrollDown, _) => self.handle_key(KeyCode::Down),
+            _ => MenuOutcome::None,
+        }
+    }
+
+    pub fn item_at(&self, area: Rect, column: u16, row: u16) -> Option<usize> {
+        let popup = self.popup_area(area);
+        let inside = column > popup.x && column + 1 < popup.right();
+        let first = popup.y + 2;
+        if !inside || row < first || row + 1 >= popup.bottom() {
+            return None;
+        }
+        let index = (row - first) as usize;
+        (index < self.items.len()).then_some(index)
+    }
+
+    fn popup_area(&self, area: Rect) -> Rect {
+        let content_width = self
+            .items
+            .iter()
+            .map(|item| item.chars().count())
+            .chain([self.title.chars().count()])
+            .max()
+            .unwrap_or(0) as u16;
+        centered_rect(content_width + 10, self.items.len() as u16 + 4, area)
+    }
+}
+
+impl Widget for Themed<'_, Menu> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (menu, theme) = (self.inner, self.theme);
+        let popup = menu.popup_area(area);
+
+        Clear.render(popup, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", menu.title))
+            .border_style(Style::default().fg(theme.hud()))
+            .render(popup, buf);
+
+        let width = popup.width.saturating_sub(4) as usize;
+        for (i, item) in menu.items.iter().enumerate() {
+            let y = popup.y + 2 + i as u16;
+            if y >= popup.bottom().saturating_sub(1) {
+                break;
+            }
+            let (marker, style) = if i == menu.selected {
+                ("> ", Style::default().fg(theme.accent()))
+            } else {
+                ("  ", Style::default().fg(theme.text()))
+            };
+            buf.set_stringn(popup.x + 2, y, format!("{}{}", marker, item), width, style);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+mod debug_overlay;
+mod help;
+mod level_complete;
+mod menu;
+mod name_entry;
+mod scores_screen;
+mod screen;
+mod sidebar;
+mod stats_screen;
+mod toast;
+
+pub use debug_overlay::{DebugOverlay, DebugTimings};
+pub use help::HelpScreen;
+pub use level_complete::LevelComplete;
+pub use menu::{Menu, MenuOutcome};
+pub use name_entry::{NameEntry, NameEntryOutcome};
+pub use scores_screen::ScoresScreen;
+pub use screen::{Screen, ScreenStack};
+pub use sidebar::{Sidebar, SIDEBAR_WIDTH};
+pub use stats_screen::StatsScreen;
+pub use toast::Toasts;
+
+use crate::theme::Theme;
+use ratatui::layout::Rect;
+
+pub struct Themed<'a, T> {
+    pub inner: &'a T,
+    pub theme: &'a Theme,
+}
+
+impl<'a, T> Themed<'a, T> {
+    pub fn new(inner: &'a T, theme: &'a Theme) -> Self {
+        Self { inner, theme }
+    }
+}
+
+pub(crate) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
@@ -0,0 +1,97 @@
+use super::{centered_rect, Themed};
+use crate::storage::MAX_NAME_LEN;
+use crossterm::event::KeyCode;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameEntryOutcome {
+    Editing,
+    Submitted,
+    Cancelled,
+}
+
+pub struct NameEntry {
+    name: String,
+    score: u32,
+    error: Option<String>,
+}
+
+impl NameEntry {
+    pub fn new(score: u32, initial_name: &str) -> Self {
+        Self {
+            name: initial_name.chars().take(MAX_NAME_LEN).collect(),
+            score,
+            error: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> NameEntryOutcome {
+        match code {
+            KeyCode::Enter => return NameEntryOutcome::Submitted,
+            KeyCode::Esc => return NameEntryOutcome::Cancelled,
+            KeyCode::Backspace => {
+                self.name.pop();
+            }
+            KeyCode::Char(c) if !c.is_control() && self.name.chars().count() < MAX_NAME_LEN => {
+                self.name.push(c);
+            }
+            _ => {}
+        }
+        self.error = None;
+        NameEntryOutcome::Editing
+    }
+}
+
+impl Widget for Themed<'_, NameEntry> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (entry, theme) = (self.inner, self.theme);
+        let popup = centered_rect(30, 7, area);
+        Clear.render(popup, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" NEW HIGH SCORE ")
+            .border_style(Style::default().fg(theme.accent()))
+            .render(popup, buf);
+
+        let text = Style::default().fg(theme.text());
+        let x = popup.x + 2;
+
+        buf.set_string(x, popup.y + 1, format!("SCORE: {}", entry.score), text);
+        buf.set_string(x, popup.y + 3, format!("NAME: {}_", entry.name), text);
+
+        match &entry.error {
+            Some(error) => {
+                buf.set_stringn(
+                    x,
+                    popup.y + 5,
+                    error,
+                    popup.width.saturating_sub(4) as usize,
+                    Style::default().fg(theme.danger()),
+                );
+            }
+            None => buf.set_string(
+                x,
+                popup.y + 5,
+                "ENTER save  ESC skip",
+                Style::default().fg(theme.muted()),
+            ),
+        }
+    }
+}
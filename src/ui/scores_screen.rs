@@ -0,0 +1,76 @@
+use super::centered_rect;
+use crate::storage::HighScores;
+use crate::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+pub struct ScoresScreen<'a> {
+    scores: &'a HighScores,
+    theme: &'a Theme,
+}
+
+impl<'a> ScoresScreen<'a> {
+    pub fn new(scores: &'a HighScores, theme: &'a Theme) -> Self {
+        Self { scores, theme }
+    }
+
+    fn lines(&self) -> Vec<(String, bool)> {
+        if self.scores.is_empty() {
+            return vec![("No high scores yet".to_string(), false)];
+        }
+
+        let mut lines = Vec::new();
+        for (key, entries) in self.scores.tables() {
+            if !lines.is_empty() {
+                lines.push((String::new(), false));
+            }
+            lines.push((
+                format!(
+                    "{} | speed {} | {}x{}",
+                    key.mode, key.speed, key.width, key.height
+                ),
+                true,
+            ));
+            for (rank, entry) in entries.iter().enumerate() {
+                lines.push((
+                    format!("{:>2}. {:<12} {:>6}", rank + 1, entry.name, entry.score),
+                    false,
+                ));
+            }
+        }
+        lines
+    }
+}
+
+impl Widget for ScoresScreen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let popup = centered_rect(44, lines.len() as u16 + 2, area);
+
+        Clear.render(popup, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" HIGH SCORES ")
+            .border_style(Style::default().fg(self.theme.hud()))
+            .render(popup, buf);
+
+        let x = popup.x + 2;
+        let width = popup.width.saturating_sub(4) as usize;
+        for (i, (line, heading)) in lines.iter().enumerate() {
+            let y = popup.y + 1 + i as u16;
+            if y >= popup.bottom().saturating_sub(1) {
+                break;
+            }
+            let color = if *heading {
+                self.theme.accent()
+            } else {
+                self.theme.text()
+            };
+            buf.set_stringn(x, y, line, width, Style::default().fg(color));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Screen {
+    Title,
+    ModeSelect,
+    Campaign,
+    Settings,
+    HighScores,
+    Help,
+    Game,
+}
+
+// The title screen is always at the bottom, so popping can never leave the
+// app without a screen to show.
+pub struct ScreenStack {
+    screens: Vec<Screen>,
+}
+
+impl ScreenStack {
+    pub fn new() -> Self {
+        Self {
+            screens: vec![Screen::Title],
+        }
+    }
+
+    pub fn current(&self) -> Screen {
+        self.screens.last().copied().unwrap_or(Screen::Title)
+    }
+
+    pub fn depth(&self) -> usize {
+        self.screens.len()
+    }
+
+    pub fn push(&mut self, screen: Screen) {
+        if self.current() != screen {
+            self.screens.push(screen);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<Screen> {
+        if self.screens.len() > 1 {
+            self.screens.pop()
+        } else {
+            None
+        }
+    }
+
+    pub fn return_to_title(&mut self) {
+        self.screens.truncate(1);
+    }
+}
+
+impl Default for ScreenStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
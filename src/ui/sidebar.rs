@@ -0,0 +1,180 @@
+use crate::game::Game;
+use crate::glyphs::GlyphSet;
+use crate::renderer::cooldown_meter;
+use crate::systems::{EventFeed, GameEvent};
+use crate::theme::Theme;
+use crate::traits::Moveable;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+use std::time::Duration;
+
+pub const SIDEBAR_WIDTH: u16 = 28;
+
+pub struct Sidebar<'a> {
+    game: &'a Game,
+    feed: &'a EventFeed,
+    theme: &'a Theme,
+    glyphs: GlyphSet,
+    mode: &'a str,
+    elapsed: Duration,
+    tick_duration: Duration,
+}
+
+impl<'a> Sidebar<'a> {
+    pub fn new(game: &'a Game, feed: &'a EventFeed, theme: &'a Theme) -> Self {
+        Self {
+            game,
+            feed,
+            theme,
+            glyphs: GlyphSet::default(),
+            mode: "",
+            elapsed: Duration::ZERO,
+            tick_duration: Duration::from_millis(70),
+        }
+    }
+
+    pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: &'a str) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_timing(mut self, elapsed: Duration, tick_duration: Duration) -> Self {
+        self.elapsed = elapsed;
+        self.tick_duration = tick_duration;
+        self
+    }
+
+    fn stat_lines(&self) -> Vec<(&'static str, String)> {
+        let (time_label, secs) = match self.game.time_left() {
+            Some(left) => ("LEFT", left.as_secs()),
+            None => ("TIME", self.elapsed.as_secs()),
+        };
+        let velocity = self.game.food().velocity();
+        let cells_per_tick = velocity.x.abs().max(velocity.y.abs()) as f32;
+        let food_speed = cells_per_tick / self.tick_duration.as_secs_f32().max(0.001);
+        let bullets = self.game.bullets();
+        let scoring = self.game.scoring();
+
+        let mut lines = vec![
+            ("MODE", self.mode.to_string()),
+            ("LEVEL", self.game.level().to_string()),
+            ("SCORE", self.game.score().to_string()),
+            (
+                "COMBO",
+                format!("x{} ({} hits)", scoring.multiplier(), scoring.streak()),
+            ),
+            ("LENGTH", self.game.snake().length().to_string()),
+            (time_label, format!("{:02}:{:02}", secs / 60, secs % 60)),
+            ("GUN", cooldown_meter(self.game, self.glyphs, 10)),
+            (
+                "BULLETS",
+                format!("{}/{}", bullets.active_count(), bullets.capacity()),
+            ),
+            ("FOOD", format!("{:.1}/s", food_speed)),
+        ];
+
+        if let Some(objective) = self.game.objective() {
+            let (current, target) = objective.progress(&self.game.tally());
+            lines.push(("GOAL", objective.to_string()));
+            lines.push(("DONE", format!("{}/{}", current.min(target), target)));
+        }
+        lines
+    }
+
+    fn event_style(&self, event: &GameEvent) -> Style {
+        let color = match event {
+            GameEvent::SnakeDied { .. }
+            | GameEvent::SnakeDamaged { .. }
+            | GameEvent::ComboBroken { .. }
+            | GameEvent::MoveBlocked { .. }
+            | GameEvent::EventsDropped { .. } => self.theme.danger(),
+            GameEvent::FoodCollected { .. }
+            | GameEvent::SnakeGrew { .. }
+            | GameEvent::ComboStarted { .. }
+            | GameEvent::LevelUp { .. }
+            | GameEvent::ObjectiveCompleted
+            | GameEvent::BoardFilled
+            | GameEvent::TimeUp => self.theme.accent(),
+            GameEvent::BulletFired { .. } => self.theme.text(),
+            GameEvent::Collision { .. }
+            | GameEvent::GamePaused
+            | GameEvent::GameResumed
+            | GameEvent::GameRestarted => self.theme.muted(),
+        };
+        Style::default().fg(color)
+    }
+
+    fn panel(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", title))
+            .border_style(Style::default().fg(self.theme.border()))
+    }
+}
+
+impl Widget for Sidebar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let stats = self.stat_lines();
+        let [stats_area, feed_area] = Layout::vertical([
+            Constraint::Length(stats.len() as u16 + 2),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+
+        Clear.render(area, buf);
+
+        let block = self.panel("STATUS");
+        let inner = block.inner(stats_area);
+        block.render(stats_area, buf);
+
+        let width = inner.width.saturating_sub(1) as usize;
+        for (i, (label, value)) in stats.iter().enumerate() {
+            let y = inner.y + i as u16;
+            if y >= inner.bottom() {
+                break;
+            }
+            buf.set_stringn(
+                inner.x + 1,
+                y,
+                format!("{:<8}", label),
+                width,
+                Style::default().fg(self.theme.muted()),
+            );
+            buf.set_stringn(
+                inner.x + 9,
+                y,
+                value,
+                width.saturating_sub(8),
+                Style::default().fg(self.theme.text()),
+            );
+        }
+
+        if feed_area.height < 3 {
+            return;
+        }
+
+        let block = self.panel("EVENTS");
+        let inner = block.inner(feed_area);
+        block.render(feed_area, buf);
+
+        for (i, entry) in self.feed.recent().take(inner.height as usize).enumerate() {
+            let line = format!("{:>5} {}", entry.tick, entry.event);
+            buf.set_stringn(
+                inner.x + 1,
+                inner.y + i as u16,
+                &line,
+                width,
+                self.event_style(&entry.event),
+            );
+        }
+    }
+}
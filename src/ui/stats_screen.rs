@@ -0,0 +1,97 @@
+use super::centered_rect;
+use crate::systems::{Achievement, StatsTracker};
+use crate::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+pub struct StatsScreen<'a> {
+    tracker: &'a StatsTracker,
+    theme: &'a Theme,
+}
+
+impl<'a> StatsScreen<'a> {
+    pub fn new(tracker: &'a StatsTracker, theme: &'a Theme) -> Self {
+        Self { tracker, theme }
+    }
+
+    fn stat_lines(&self) -> Vec<String> {
+        let s = self.tracker.lifetime();
+        let accuracy = s
+            .accuracy()
+            .map(|a| format!("{:.0}%", a * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+
+        vec![
+            format!("Shots fired       {}", s.shots_fired),
+            format!("Accuracy          {}", accuracy),
+            format!("Bullet pickups    {}", s.bullet_collections),
+            format!("Head pickups      {}", s.head_collections),
+            format!("Segments lost     {}", s.segments_lost),
+            format!("Deaths (wall)     {}", s.deaths_by_wall),
+            format!("Deaths (self)     {}", s.deaths_by_self),
+            format!("Deaths (hunger)   {}", s.deaths_by_starving),
+            format!("Longest snake     {}", s.longest_snake),
+        ]
+    }
+}
+
+impl Widget for StatsScreen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let stat_lines = self.stat_lines();
+        let height = (stat_lines.len() + Achievement::ALL.len() + 5) as u16;
+        let popup = centered_rect(52, height, area);
+
+        Clear.render(popup, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" STATS ")
+            .border_style(Style::default().fg(self.theme.hud()))
+            .render(popup, buf);
+
+        let x = popup.x + 2;
+        let width = popup.width.saturating_sub(4) as usize;
+        let mut y = popup.y + 1;
+        let text = Style::default().fg(self.theme.text());
+
+        for line in &stat_lines {
+            if y >= popup.bottom().saturating_sub(1) {
+                return;
+            }
+            buf.set_stringn(x, y, line, width, text);
+            y += 1;
+        }
+
+        y += 1;
+        buf.set_stringn(
+            x,
+            y,
+            "ACHIEVEMENTS",
+            width,
+            Style::default().fg(self.theme.hud()),
+        );
+        y += 1;
+
+        for achievement in Achievement::ALL {
+            if y >= popup.bottom().saturating_sub(1) {
+                return;
+            }
+            let (mark, style) = if self.tracker.is_unlocked(achievement) {
+                ("*", Style::default().fg(self.theme.accent()))
+            } else {
+                (" ", Style::default().fg(self.theme.muted()))
+            };
+            let line = format!(
+                "[{}] {:<14} {}",
+                mark,
+                achievement.title(),
+                achievement.description()
+            );
+            buf.set_stringn(x, y, &line, width, style);
+            y += 1;
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::Themed;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Widget},
+};
+use std::collections::VecDeque;
+
+const DEFAULT_TTL_TICKS: u32 = 45;
+const MAX_VISIBLE: usize = 3;
+
+struct Toast {
+    title: String,
+    body: String,
+    remaining: u32,
+}
+
+pub struct Toasts {
+    items: VecDeque<Toast>,
+    ttl_ticks: u32,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_TTL_TICKS)
+    }
+
+    pub fn with_ttl(ttl_ticks: u32) -> Self {
+        Self {
+            items: VecDeque::new(),
+            ttl_ticks,
+        }
+    }
+
+    pub fn push(&mut self, title: impl Into<String>, body: impl Into<String>) {
+        self.items.push_back(Toast {
+            title: title.into(),
+            body: body.into(),
+            remaining: self.ttl_ticks,
+        });
+    }
+
+    // Only the visible toasts age, so a burst of unlocks is shown in turn
+    // rather than expiring off-screen.
+    pub fn tick(&mut self) {
+        for toast in self.items.iter_mut().take(MAX_VISIBLE) {
+            toast.remaining = toast.remaining.saturating_sub(1);
+        }
+        self.items.retain(|t| t.remaining > 0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Themed<'_, Toasts> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        let mut y = area.y + 1;
+
+        for toast in self.inner.items.iter().take(MAX_VISIBLE) {
+            let content_width = toast.title.len().max(toast.body.len()) as u16;
+            let width = (content_width + 4).min(area.width.saturating_sub(2));
+            let height = 4;
+            if y + height > area.bottom() || width < 5 {
+                break;
+            }
+
+            let rect = Rect::new(area.right().saturating_sub(width + 1), y, width, height);
+            Clear.render(rect, buf);
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent()))
+                .render(rect, buf);
+
+            let inner_width = width.saturating_sub(4) as usize;
+            buf.set_stringn(
+                rect.x + 2,
+                rect.y + 1,
+                &toast.title,
+                inner_width,
+                Style::default().fg(theme.accent()),
+            );
+            buf.set_stringn(
+                rect.x + 2,
+                rect.y + 2,
+                &toast.body,
+                inner_width,
+                Style::default().fg(theme.text()),
+            );
+
+            y += height;
+        }
+    }
+}